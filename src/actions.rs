@@ -0,0 +1,35 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::uncles::UncleType;
+
+/// Everything a player can do that changes the simulation.
+/// Input systems only queue these; they are applied at the start of the next
+/// fixed tick so the same actions on the same ticks always give the same run.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum PlayerAction {
+    /// Choose which uncle type the next placement hires
+    SelectUncleType(UncleType),
+    /// Hire the selected uncle type on the tile at (x, y)
+    PlaceUncle { x: i32, y: i32 },
+    /// Build a dock over the water tile at (x, y) so uncles can stand further out
+    BuildDock { x: i32, y: i32 },
+    /// Select the uncle standing on (x, y) to view and cash out their basket
+    SelectPlacedUncle { x: i32, y: i32 },
+    /// Throw back the lowest value fish in the selected uncle's basket
+    RemoveLowestFish,
+    CashOutSelected,
+    CashOutAll,
+}
+
+/// Actions queued since the last fixed tick
+#[derive(Resource, Default)]
+pub struct PendingActions {
+    pub queue: Vec<PlayerAction>,
+}
+
+impl PendingActions {
+    pub fn push(&mut self, action: PlayerAction) {
+        self.queue.push(action);
+    }
+}
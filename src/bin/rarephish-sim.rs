@@ -0,0 +1,299 @@
+//! Headless balance simulator.
+//!
+//! Runs the fishing, escape, cash-out and day/night systems under `MinimalPlugins`
+//! with a fixed seed and a scripted strategy, then writes per-day samples as CSV or JSON.
+//! The strategy queues the same `PlayerAction`s a player would, so it goes through the
+//! real placement and cash-out rules. It decides once per simulation tick, so the report
+//! does not depend on `--step-ms`:
+//!
+//! ```text
+//! cargo run --release --bin rarephish-sim -- --days 30 --seed 42 --format json --out run.json
+//! ```
+
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::Duration;
+
+use rarephish_bevy::actions::{PendingActions, PlayerAction};
+use rarephish_bevy::components::{FishRarity, Uncle};
+use rarephish_bevy::events::UnclePlaced;
+use rarephish_bevy::grid::TileGrid;
+use rarephish_bevy::resources::{DayNightCycle, FishingStats, GameState, WorldSeed};
+use rarephish_bevy::seed::parse_seed;
+use rarephish_bevy::species::{SpeciesCatalogue, SPECIES_CATALOGUE_PATH};
+use rarephish_bevy::systems::economy::cash_out_available;
+use rarephish_bevy::systems::gameplay::apply_player_actions;
+use rarephish_bevy::{RarephishPlugin, TickSet};
+use rarephish_bevy::uncles::{UncleRoster, UncleType, UNCLE_ROSTER_PATH};
+
+const USAGE: &str = "usage: rarephish-sim [--days N] [--seed N|CODE|WORDS] [--step-ms N] [--format csv|json] [--out PATH]\n\
+    defaults: 10 days, seed 42, 50 ms frames, csv to rarephish-sim.csv";
+
+/// Fraction of the day after which the strategy cashes out even if no basket is full
+const CASH_OUT_DAY_PROGRESS: f32 = 0.9;
+
+#[derive(Clone, Copy)]
+enum OutputFormat {
+    Csv,
+    Json,
+}
+
+struct SimArgs {
+    days: u32,
+    seed: u64,
+    step_ms: u64,
+    format: OutputFormat,
+    out: Option<String>,
+}
+
+impl SimArgs {
+    fn parse() -> Result<Self, String> {
+        let mut args = SimArgs {
+            days: 10,
+            seed: 42,
+            step_ms: 50,
+            format: OutputFormat::Csv,
+            out: None,
+        };
+
+        let mut iter = std::env::args().skip(1);
+        while let Some(flag) = iter.next() {
+            let mut value = || iter.next().ok_or(format!("{} needs a value", flag));
+            match flag.as_str() {
+                "--days" => args.days = value()?.parse().map_err(|e| format!("--days: {}", e))?,
+                "--seed" => args.seed = parse_seed(&value()?).ok_or("--seed needs a value")?,
+                "--step-ms" => args.step_ms = value()?.parse().map_err(|e| format!("--step-ms: {}", e))?,
+                "--format" => {
+                    args.format = match value()?.as_str() {
+                        "csv" => OutputFormat::Csv,
+                        "json" => OutputFormat::Json,
+                        other => return Err(format!("unknown format '{}'", other)),
+                    }
+                }
+                "--out" => args.out = Some(value()?),
+                "-h" | "--help" => return Err(USAGE.to_string()),
+                other => return Err(format!("unknown argument '{}'\n{}", other, USAGE)),
+            }
+        }
+
+        if args.step_ms == 0 {
+            return Err("--step-ms must be positive".to_string());
+        }
+        Ok(args)
+    }
+}
+
+/// One row of output, taken at the start of every day
+#[derive(Serialize, Clone)]
+struct DaySample {
+    day: u32,
+    gold: u32,
+    fish_sold: u32,
+    multiplier: f32,
+    uncles: usize,
+    stats: FishingStats,
+}
+
+#[derive(Serialize)]
+struct SimReport {
+    seed: u64,
+    days: u32,
+    samples: Vec<DaySample>,
+    totals: FishingStats,
+    uncles_hired: BTreeMap<UncleType, u32>,
+}
+
+/// Strategy state and collected samples
+#[derive(Resource, Default)]
+struct SimLog {
+    samples: Vec<DaySample>,
+    last_day: u32,
+    next_hire: usize,
+    uncles_hired: BTreeMap<UncleType, u32>,
+}
+
+/// Records a sample whenever a new day starts
+fn record_day(
+    mut log: ResMut<SimLog>,
+    game_state: Res<GameState>,
+    day_night: Res<DayNightCycle>,
+    stats: Res<FishingStats>,
+    uncles_q: Query<&Uncle>,
+) {
+    if day_night.day_number != log.last_day {
+        log.last_day = day_night.day_number;
+        log.samples.push(DaySample {
+            day: day_night.day_number,
+            gold: game_state.gold,
+            fish_sold: game_state.fish_count,
+            multiplier: game_state.multiplier,
+            uncles: uncles_q.iter().count(),
+            stats: stats.clone(),
+        });
+    }
+}
+
+/// Hires uncles in a fixed rotation, each on the free coastal tile with the most water around it
+fn scripted_placement(
+    mut actions: ResMut<PendingActions>,
+    log: Res<SimLog>,
+    game_state: Res<GameState>,
+    grid: Res<TileGrid>,
+    roster: Res<UncleRoster>,
+    uncles_q: Query<&Uncle>,
+) {
+    let def = roster.iter().cycle().nth(log.next_hire).expect("roster is not empty");
+    if game_state.gold < def.cost {
+        return;
+    }
+
+    let water_neighbours = |x: i32, y: i32| {
+        grid.within(x, y, 1).filter(|&(nx, ny)| grid.get(nx, ny).is_some_and(|t| t.is_water())).count()
+    };
+
+    let best = grid
+        .iter()
+        .filter(|&(_, _, tile_type)| tile_type.can_place_uncle())
+        .filter(|&(_, _, tile_type)| game_state.gold >= def.cost + tile_type.placement_cost())
+        .filter(|&(x, y, _)| !uncles_q.iter().any(|u| u.x == x && u.y == y))
+        .map(|(x, y, _)| (water_neighbours(x, y), x, y))
+        .filter(|&(count, _, _)| count > 0)
+        // Most water first, ties broken by position so runs are reproducible
+        .max_by(|a, b| a.0.cmp(&b.0).then(b.2.cmp(&a.2)).then(b.1.cmp(&a.1)));
+
+    if let Some((_, x, y)) = best {
+        actions.push(PlayerAction::SelectUncleType(def.id.clone()));
+        actions.push(PlayerAction::PlaceUncle { x, y });
+    }
+}
+
+/// Counts the hires that went through and moves the rotation on past them
+fn count_hires(mut log: ResMut<SimLog>, mut placed: EventReader<UnclePlaced>) {
+    for event in placed.read() {
+        *log.uncles_hired.entry(event.uncle_type.clone()).or_default() += 1;
+        log.next_hire += 1;
+    }
+}
+
+/// Cashes out all uncles when any basket is full or the day is nearly over
+fn scripted_cash_out(
+    mut actions: ResMut<PendingActions>,
+    uncles_q: Query<&Uncle>,
+    game_state: Res<GameState>,
+    day_night: Res<DayNightCycle>,
+) {
+    let any_fish = uncles_q.iter().any(|uncle| !uncle.basket.fish.is_empty());
+    let any_full = uncles_q.iter().any(|uncle| uncle.basket.is_full());
+    let late_in_day = day_night.day_progress >= CASH_OUT_DAY_PROGRESS;
+
+    if any_fish
+        && (any_full || late_in_day)
+        && cash_out_available(&game_state, &day_night)
+    {
+        actions.push(PlayerAction::CashOutAll);
+    }
+}
+
+fn write_csv(report: &SimReport, roster: &UncleRoster) -> String {
+    let mut header = vec!["day", "gold", "fish_sold", "multiplier", "uncles"]
+        .into_iter()
+        .map(String::from)
+        .collect::<Vec<_>>();
+    for rarity in FishRarity::ALL {
+        header.push(format!("caught_{}", rarity.name().to_lowercase()));
+        header.push(format!("escaped_{}", rarity.name().to_lowercase()));
+    }
+    for def in roster.iter() {
+        let key = def.id.as_str().to_lowercase();
+        header.push(format!("caught_{}", key));
+        header.push(format!("escaped_{}", key));
+    }
+
+    let mut out = header.join(",");
+    out.push('\n');
+
+    for row in &report.samples {
+        let mut cells = vec![
+            row.day.to_string(),
+            row.gold.to_string(),
+            row.fish_sold.to_string(),
+            format!("{:.1}", row.multiplier),
+            row.uncles.to_string(),
+        ];
+        for rarity in FishRarity::ALL {
+            cells.push(row.stats.caught_by_rarity.get(&rarity).copied().unwrap_or(0).to_string());
+            cells.push(row.stats.escaped_by_rarity.get(&rarity).copied().unwrap_or(0).to_string());
+        }
+        for def in roster.iter() {
+            cells.push(row.stats.caught_by_uncle.get(&def.id).copied().unwrap_or(0).to_string());
+            cells.push(row.stats.escaped_by_uncle.get(&def.id).copied().unwrap_or(0).to_string());
+        }
+        out.push_str(&cells.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+fn main() {
+    let args = match SimArgs::parse() {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("{}", message);
+            std::process::exit(2);
+        }
+    };
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .insert_resource(WorldSeed::from_seed(args.seed))
+        .insert_resource(UncleRoster::read_or_default(Path::new(UNCLE_ROSTER_PATH)))
+        .insert_resource(SpeciesCatalogue::read_or_default(Path::new(SPECIES_CATALOGUE_PATH)))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(args.step_ms)))
+        .add_plugins(RarephishPlugin::headless())
+        .init_resource::<SimLog>()
+        // Decided on the tick, before the actions are applied, so every tick sees the last one's outcome
+        .add_systems(
+            FixedUpdate,
+            (scripted_placement, scripted_cash_out)
+                .chain()
+                .in_set(TickSet::ApplyActions)
+                .before(apply_player_actions),
+        )
+        .add_systems(FixedUpdate, (count_hires.after(apply_player_actions), record_day.after(TickSet::Simulate)));
+    app.finish();
+    app.cleanup();
+
+    while app.world().resource::<DayNightCycle>().day_number <= args.days {
+        app.update();
+    }
+
+    // The loop stops on the first update of day `days + 1`, which `record_day` already sampled
+    let world = app.world();
+    let log = world.resource::<SimLog>();
+    let report = SimReport {
+        seed: args.seed,
+        days: args.days,
+        samples: log.samples.clone(),
+        totals: world.resource::<FishingStats>().clone(),
+        uncles_hired: log.uncles_hired.clone(),
+    };
+
+    let output = match args.format {
+        OutputFormat::Csv => write_csv(&report, world.resource::<UncleRoster>()),
+        OutputFormat::Json => serde_json::to_string_pretty(&report).expect("report is serializable"),
+    };
+
+    // Results always go to a file; stderr carries the summary line
+    let path = args.out.unwrap_or_else(|| match args.format {
+        OutputFormat::Csv => "rarephish-sim.csv".to_string(),
+        OutputFormat::Json => "rarephish-sim.json".to_string(),
+    });
+    if let Err(err) = std::fs::write(&path, output) {
+        eprintln!("failed to write {}: {}", path, err);
+        std::process::exit(1);
+    }
+    eprintln!("Wrote {} samples to {}", report.samples.len(), path);
+}
@@ -1,33 +1,162 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use crate::constants;
+use crate::species::{FishTraits, SpeciesDef, SpeciesId};
+use crate::uncles::{UncleDef, UncleType};
 
 /// Marker component for tile entities
 #[derive(Component)]
 pub struct Tile {
-    pub x: usize,
-    pub y: usize,
+    pub x: i32,
+    pub y: i32,
     pub tile_type: TileType,
 }
 
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub enum TileType {
+    DeepWater,  // Slow bites, more rare fish
     Water,
+    Shallows,   // Quick bites, mostly commons
+    River,      // Current carries fish past: quicker bites, more uncommons
+    Beach,      // Open casting line: uncles here fish faster
     Land,
+    Forest,     // Has to be cleared before an uncle can stand here
+    Rock,       // Blocks placement
+    Dock,       // Built over water; uncles on it reach further out
+}
+
+impl TileType {
+    pub const ALL: [TileType; 9] = [
+        TileType::DeepWater, TileType::Water, TileType::Shallows, TileType::River,
+        TileType::Beach, TileType::Land, TileType::Forest, TileType::Rock, TileType::Dock,
+    ];
+
+    /// Tiles uncles fish from
+    pub fn is_water(&self) -> bool {
+        matches!(self, TileType::DeepWater | TileType::Water | TileType::Shallows | TileType::River)
+    }
+
+    /// Tiles an uncle can be hired onto (still needs water within casting range)
+    pub fn can_place_uncle(&self) -> bool {
+        matches!(self, TileType::Beach | TileType::Land | TileType::Forest | TileType::Dock)
+    }
+
+    /// Water tiles a dock can be built over
+    pub fn can_build_dock(&self) -> bool {
+        matches!(self, TileType::Water | TileType::Shallows)
+    }
+
+    /// Extra gold on top of the uncle's cost to hire onto this tile
+    pub fn placement_cost(&self) -> u32 {
+        match self {
+            TileType::Forest => constants::FOREST_CLEARING_COST,
+            _ => 0,
+        }
+    }
+
+    /// Fishing timer speed for water tiles, or the bonus for standing on this tile
+    pub fn catch_speed(&self) -> f32 {
+        match self {
+            TileType::DeepWater => constants::DEEP_WATER_CATCH_SPEED,
+            TileType::Shallows => constants::SHALLOWS_CATCH_SPEED,
+            TileType::River => constants::RIVER_CATCH_SPEED,
+            TileType::Beach => constants::BEACH_CATCH_SPEED,
+            _ => 1.0,
+        }
+    }
+
+    /// Relative amount of fish a water tile holds
+    pub fn density(&self) -> f32 {
+        match self {
+            TileType::DeepWater => constants::DEEP_WATER_DENSITY,
+            TileType::Water => 1.0,
+            TileType::Shallows => constants::SHALLOWS_DENSITY,
+            TileType::River => constants::RIVER_DENSITY,
+            _ => 0.0,
+        }
+    }
+
+    /// Added to the rare fish chance for fish from this water
+    pub fn rare_bonus(&self) -> f32 {
+        match self {
+            TileType::DeepWater => constants::DEEP_WATER_RARE_BONUS,
+            _ => 0.0,
+        }
+    }
+
+    /// Added to the uncommon fish chance for fish from this water
+    pub fn uncommon_bonus(&self) -> f32 {
+        match self {
+            TileType::River => constants::RIVER_UNCOMMON_BONUS,
+            _ => 0.0,
+        }
+    }
+
+    /// Scales the rare and uncommon chances for fish from this water
+    pub fn rarity_multiplier(&self) -> f32 {
+        match self {
+            TileType::Shallows => constants::SHALLOWS_RARITY_MULTIPLIER,
+            _ => 1.0,
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            TileType::DeepWater => Color::srgb(0.024, 0.176, 0.306),
+            TileType::Water => Color::srgb(0.047, 0.290, 0.431),
+            TileType::Shallows => Color::srgb(0.133, 0.455, 0.576),
+            TileType::River => Color::srgb(0.176, 0.443, 0.690),
+            TileType::Beach => Color::srgb(0.851, 0.769, 0.545),
+            TileType::Land => Color::srgb(0.086, 0.639, 0.290),
+            TileType::Forest => Color::srgb(0.055, 0.388, 0.173),
+            TileType::Rock => Color::srgb(0.459, 0.459, 0.478),
+            TileType::Dock => Color::srgb(0.545, 0.365, 0.204),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TileType::DeepWater => "Deep Water",
+            TileType::Water => "Water",
+            TileType::Shallows => "Shallows",
+            TileType::River => "River",
+            TileType::Beach => "Beach",
+            TileType::Land => "Land",
+            TileType::Forest => "Forest",
+            TileType::Rock => "Rock",
+            TileType::Dock => "Dock",
+        }
+    }
 }
 
-/// Component for uncle entities placed on tiles
+/// Component for uncle entities placed on tiles; `uncle_type` is its definition in the `UncleRoster`
 #[derive(Component)]
 pub struct Uncle {
     pub uncle_type: UncleType,
-    pub x: usize,
-    pub y: usize,
+    pub x: i32,
+    pub y: i32,
     pub fishing_timer: Timer,
     pub basket: UncleBasket,
 }
 
+impl Uncle {
+    pub fn new(def: &UncleDef, x: i32, y: i32) -> Self {
+        Self {
+            uncle_type: def.id.clone(),
+            x,
+            y,
+            fishing_timer: Timer::from_seconds(
+                def.speed_ms as f32 / 1000.0,
+                TimerMode::Repeating,
+            ),
+            basket: UncleBasket::new(def.basket_capacity),
+        }
+    }
+}
+
 /// Individual uncle's fishing basket
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct UncleBasket {
     pub fish: Vec<Fish>,
     pub capacity: usize,
@@ -79,141 +208,99 @@ impl UncleBasket {
 #[derive(Component)]
 pub struct SelectedUncleMarker;
 
-#[derive(Clone, Copy, PartialEq)]
-pub enum UncleType {
-    Mongolian,  // Basic: 2s, 50g, best retention, small basket
-    Somali,     // Fast: 1.5s, 150g, good retention, medium basket
-    Japanese,   // RareFinder: 2.5s, 300g, bonus rare, large basket
+/// Component for fish entities with escape physics
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Fish {
+    pub name: String,
+    #[serde(default)]
+    pub species: SpeciesId,  // Empty for fish caught before the species catalogue
+    #[serde(default)]
+    pub traits: FishTraits,
+    #[serde(default)]
+    pub length_cm: f32,  // 0 for fish caught before fish were measured
+    #[serde(default)]
+    pub weight_kg: f32,
+    pub rarity: FishRarity,
+    pub value: u32,
+    pub time_alive: f32,
+    pub failed_escape_attempts: u32,  // Flops that did not reach the water
+    pub caught_by_uncle: UncleType,
+    #[serde(default)]
+    pub next_flop_at: f32,  // Value of `time_alive` at which the fish flops next
+    #[serde(default = "full_strength")]
+    pub escape_strength: f32,  // From its species and shape; multiplies the chance of every flop
 }
 
-impl UncleType {
-    pub fn speed_ms(&self) -> u64 {
-        match self {
-            UncleType::Mongolian => 2000,
-            UncleType::Somali => 1500,
-            UncleType::Japanese => 2500,
-        }
-    }
-
-    pub fn cost(&self) -> u32 {
-        match self {
-            UncleType::Mongolian => constants::MONGOLIAN_COST,
-            UncleType::Somali => constants::SOMALI_COST,
-            UncleType::Japanese => constants::JAPANESE_COST,
-        }
-    }
-
-    /// Basket capacity - trade-off with other stats
-    pub fn basket_capacity(&self) -> usize {
-        match self {
-            UncleType::Mongolian => 5,   // Small basket, best retention
-            UncleType::Somali => 8,      // Medium basket, fast speed
-            UncleType::Japanese => 12,   // Large basket, rare finder
-        }
-    }
-
-    /// Asset path for uncle sprite
-    pub fn asset_path(&self) -> Option<&'static str> {
-        match self {
-            UncleType::Mongolian => None,
-            UncleType::Somali => None,
-            UncleType::Japanese => None,
-        }
-    }
-
-    pub fn letter(&self) -> &'static str {
-        match self {
-            UncleType::Mongolian => "M",
-            UncleType::Somali => "S",
-            UncleType::Japanese => "J",
-        }
-    }
-
-    pub fn color(&self) -> Color {
-        match self {
-            UncleType::Mongolian => Color::srgb(0.824, 0.706, 0.549),
-            UncleType::Somali => Color::srgb(0.247, 0.596, 0.757),
-            UncleType::Japanese => Color::srgb(0.969, 0.706, 0.788),
-        }
-    }
-
-    pub fn emoji(&self) -> &'static str {
-        match self {
-            UncleType::Mongolian => "🏜️",
-            UncleType::Somali => "🌊",
-            UncleType::Japanese => "🗾",
-        }
-    }
-
-    pub fn name(&self) -> &'static str {
-        match self {
-            UncleType::Mongolian => "Mongolian Uncle",
-            UncleType::Somali => "Somali Uncle",
-            UncleType::Japanese => "Japanese Uncle",
-        }
-    }
+fn full_strength() -> f32 {
+    1.0
+}
 
-    pub fn ability(&self) -> &'static str {
-        match self {
-            UncleType::Mongolian => "Strong Grip",
-            UncleType::Somali => "Quick Reflexes",
-            UncleType::Japanese => "Rare Finder",
-        }
+/// A fish weight in grams below a kilogram, e.g. "350 g" or "4.3 kg"
+pub fn format_weight(kg: f32) -> String {
+    if kg < 1.0 {
+        format!("{:.0} g", kg * 1000.0)
+    } else {
+        format!("{:.1} kg", kg)
     }
+}
 
-    pub fn description(&self) -> &'static str {
-        match self {
-            UncleType::Mongolian => "Best retention, small basket",
-            UncleType::Somali => "Fast + good retention, medium basket",
-            UncleType::Japanese => "Rare fish, large basket, weak grip",
+impl Fish {
+    pub fn new(name: String, rarity: FishRarity, value: u32, caught_by_uncle: UncleType) -> Self {
+        Self {
+            name,
+            species: SpeciesId::default(),
+            traits: FishTraits::default(),
+            length_cm: 0.0,
+            weight_kg: 0.0,
+            rarity,
+            value,
+            time_alive: 0.0,
+            failed_escape_attempts: 0,
+            caught_by_uncle,
+            next_flop_at: 1.0 / constants::BURST_FLOP_HZ,
+            escape_strength: full_strength(),
         }
     }
 
-    pub fn rare_bonus(&self) -> f32 {
-        match self {
-            UncleType::Japanese => 0.05,
-            _ => 0.0,
+    /// A freshly caught fish of a catalogue species, `length_cm` long
+    pub fn of_species(def: &SpeciesDef, traits: FishTraits, length_cm: f32, value: u32, caught_by_uncle: UncleType) -> Self {
+        Self {
+            species: def.id.clone(),
+            traits,
+            length_cm,
+            weight_kg: def.weight_at(length_cm),
+            escape_strength: def.escape_strength(&traits),
+            ..Self::new(def.name.clone(), def.rarity, value, caught_by_uncle)
         }
     }
 
-    pub fn retention_multiplier(&self) -> f32 {
-        match self {
-            UncleType::Mongolian => constants::MONGOLIAN_RETENTION,
-            UncleType::Somali => constants::SOMALI_RETENTION,
-            UncleType::Japanese => constants::JAPANESE_RETENTION,
+    /// Length and weight for display, e.g. "62 cm, 4.3 kg"; `None` for fish that were never measured
+    pub fn measurements(&self) -> Option<String> {
+        if self.weight_kg <= 0.0 {
+            return None;
         }
+        Some(format!("{:.0} cm, {}", self.length_cm, format_weight(self.weight_kg)))
     }
-}
-
-/// Component for fish entities with escape physics
-#[derive(Clone)]
-pub struct Fish {
-    pub name: String,
-    pub rarity: FishRarity,
-    pub value: u32,
-    pub time_alive: f32,
-    pub failed_escape_attempts: u32,
-    pub caught_by_uncle: UncleType,
-}
 
-impl Fish {
     pub fn get_phase(&self) -> MetabolicPhase {
-        if self.time_alive < crate::constants::BURST_PHASE_DURATION {
+        if self.time_alive < constants::BURST_PHASE_DURATION {
             MetabolicPhase::Burst
-        } else if self.time_alive < crate::constants::BURST_PHASE_DURATION + crate::constants::STOCHASTIC_PHASE_DURATION {
+        } else if self.time_alive < constants::BURST_PHASE_DURATION + constants::STOCHASTIC_PHASE_DURATION {
             MetabolicPhase::Stochastic
         } else {
             MetabolicPhase::Fatigue
         }
     }
 
-    pub fn calculate_escape_chance(&self) -> f32 {
+    /// Chance that the next flop gets the fish back into the water, for a fish in the basket of
+    /// an uncle with the given `UncleDef::retention`
+    pub fn calculate_escape_chance(&self, retention: f32) -> f32 {
         use crate::constants::*;
 
-        let phase_base = match self.get_phase() {
-            MetabolicPhase::Burst => BURST_ESCAPE_BASE,
-            MetabolicPhase::Stochastic => STOCHASTIC_ESCAPE_BASE,
-            MetabolicPhase::Fatigue => FATIGUE_ESCAPE_BASE,
+        let flop_success = match self.get_phase() {
+            MetabolicPhase::Burst => BURST_FLOP_SUCCESS,
+            MetabolicPhase::Stochastic => STOCHASTIC_FLOP_SUCCESS,
+            MetabolicPhase::Fatigue => FATIGUE_FLOP_SUCCESS,
         };
 
         let rarity_mult = match self.rarity {
@@ -222,12 +309,35 @@ impl Fish {
             FishRarity::Rare => RARE_ESCAPE_MULTIPLIER,
         };
 
-        let uncle_mult = self.caught_by_uncle.retention_multiplier();
-        let failed_reduction = self.failed_escape_attempts as f32 * ESCAPE_REDUCTION_PER_FAIL;
+        let failed_decay = FAILED_FLOP_DECAY.powi(self.failed_escape_attempts as i32);
 
-        (phase_base * rarity_mult * uncle_mult - failed_reduction)
+        // Bigger fish fight harder
+        let size_mult = self.traits.size.escape_multiplier();
+
+        (flop_success * rarity_mult * self.escape_strength * size_mult * retention * failed_decay)
             .max(MIN_ESCAPE_CHANCE)
     }
+
+    /// Ages the fish by `delta` seconds, rolling once for every flop that falls inside
+    /// that window. Flops happen at fixed points in the fish's life, so splitting the
+    /// same time into more or fewer steps gives the same flops and the same rolls.
+    /// Returns true if a flop got the fish back into the water.
+    pub fn advance(&mut self, delta: f32, retention: f32, rng: &mut impl rand::Rng) -> bool {
+        let end = self.time_alive + delta;
+
+        while self.next_flop_at <= end {
+            // Evaluate phase and chance at the moment of the flop, not the end of the step
+            self.time_alive = self.next_flop_at;
+            if rng.gen::<f32>() < self.calculate_escape_chance(retention) {
+                return true;
+            }
+            self.failed_escape_attempts += 1;
+            self.next_flop_at += 1.0 / self.get_phase().flop_hz();
+        }
+
+        self.time_alive = end;
+        false
+    }
 }
 
 #[derive(Clone, Copy, PartialEq)]
@@ -237,7 +347,18 @@ pub enum MetabolicPhase {
     Fatigue,
 }
 
-#[derive(Clone, Copy, PartialEq)]
+impl MetabolicPhase {
+    /// How often a fish in this phase flops, in flops per second
+    pub fn flop_hz(&self) -> f32 {
+        match self {
+            MetabolicPhase::Burst => constants::BURST_FLOP_HZ,
+            MetabolicPhase::Stochastic => constants::STOCHASTIC_FLOP_HZ,
+            MetabolicPhase::Fatigue => constants::FATIGUE_FLOP_HZ,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize)]
 pub enum FishRarity {
     Common,
     Uncommon,
@@ -245,6 +366,8 @@ pub enum FishRarity {
 }
 
 impl FishRarity {
+    pub const ALL: [FishRarity; 3] = [FishRarity::Common, FishRarity::Uncommon, FishRarity::Rare];
+
     pub fn color(&self) -> Color {
         match self {
             FishRarity::Common => Color::srgb(0.58, 0.64, 0.72),
@@ -275,6 +398,18 @@ pub struct MultiplierText;
 #[derive(Component)]
 pub struct SeedText;
 
+/// The seed code in the header; clicking it copies the code to the system clipboard.
+/// Without a clipboard (or if copying fails) a toast shows the code to type out instead.
+#[derive(Component)]
+pub struct SeedCodeButton;
+
+/// Text field for typing the seed of a new world
+#[derive(Component)]
+pub struct SeedInputField;
+
+#[derive(Component)]
+pub struct SeedInputText;
+
 #[derive(Component)]
 pub struct CooldownText;
 
@@ -290,6 +425,16 @@ pub struct CashOutAllButton;
 #[derive(Component)]
 pub struct NewWorldButton;
 
+// New World confirmation dialog
+#[derive(Component)]
+pub struct NewWorldPanel;
+
+#[derive(Component)]
+pub struct NewWorldConfirmButton;
+
+#[derive(Component)]
+pub struct NewWorldCancelButton;
+
 #[derive(Component)]
 pub struct UncleSelectButton {
     pub uncle_type: UncleType,
@@ -304,6 +449,10 @@ pub struct FishFeedEntry;
 #[derive(Component)]
 pub struct UncleBasketDisplay;
 
+/// Cycles the `BasketSort` of the basket panel
+#[derive(Component)]
+pub struct BasketSortButton;
+
 // Day/Night cycle UI markers
 #[derive(Component)]
 pub struct DayNumberText;
@@ -319,3 +468,79 @@ pub struct CashoutsRemainingText;
 pub struct RemoveFishButton {
     pub fish_index: usize,
 }
+
+// "While you were away" popup
+#[derive(Component)]
+pub struct OfflineReportPanel;
+
+#[derive(Component)]
+pub struct OfflineReportDismissButton;
+
+// Main menu, pause and run summary screens
+#[derive(Component)]
+pub struct MainMenuScreen;
+
+#[derive(Component)]
+pub struct PauseScreen;
+
+#[derive(Component)]
+pub struct RunSummaryScreen;
+
+/// Line on the main menu describing the run CONTINUE goes back to
+#[derive(Component)]
+pub struct MainMenuRunText;
+
+/// A button on one of the menu screens, by what it does
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MenuButton {
+    NewRun,    // Start over in a random world
+    Continue,  // Back to the current run
+    LoadSeed,  // Open the New World dialog to type a seed
+    Resume,
+    EndRun,    // Show the run summary
+    MainMenu,
+    Fishdex,   // Open the Fishdex panel
+}
+
+// Fishdex panel
+#[derive(Component)]
+pub struct FishdexScreen;
+
+#[derive(Component)]
+pub struct FishdexCloseButton;
+
+/// Scrollable entry list inside the Fishdex panel
+#[derive(Component)]
+pub struct FishdexList;
+
+/// A caught fish's sprite leaping over the uncle that caught it; despawned when it lands
+#[derive(Component)]
+pub struct CatchAnimation {
+    pub origin: Vec3,
+    pub elapsed: f32,
+}
+
+// Notification UI
+#[derive(Component)]
+pub struct ToastContainer;
+
+/// A notification popup that fades out after `TOAST_LIFETIME_SECONDS`
+#[derive(Component)]
+pub struct Toast {
+    pub age: f32,
+}
+
+/// Scrollable list inside the event log panel; `offset` is how far it is scrolled down
+#[derive(Component, Default)]
+pub struct NotificationHistoryList {
+    pub offset: f32,
+}
+
+#[derive(Component)]
+pub struct NotificationHistoryEntry;
+
+/// Fish overlay label showing how full one water body is
+#[derive(Component)]
+pub struct FishOverlayLabel {
+    pub body: crate::fishing::BodyId,
+}
@@ -1,11 +1,35 @@
 // Tilemap dimensions
+// The world is unbounded; TILE_WIDTH × TILE_HEIGHT is the home region the falloff shapes
+// and the camera starts over
 pub const TILE_WIDTH: usize = 16;
 pub const TILE_HEIGHT: usize = 12;
 pub const TILE_SIZE: f32 = 40.0;
+pub const CHUNK_SIZE: usize = 16;             // Chunks are 16×16 tiles, generated and spawned as one
+pub const CHUNK_SPAWN_MARGIN: i32 = 1;        // Chunks spawned beyond the edge of the view
+pub const CHUNK_DESPAWN_MARGIN: i32 = 2;      // Chunks further than this off screen are despawned
+
+// Camera
+pub const CAMERA_PAN_SPEED: f32 = 600.0;      // Pixels per second at zoom 1 (WASD / arrows / edge)
+pub const CAMERA_EDGE_SCROLL_MARGIN: f32 = 12.0;  // Cursor this close to the window edge scrolls
+pub const CAMERA_ZOOM_STEP: f32 = 1.1;        // Zoom factor per mouse wheel line
+pub const CAMERA_MIN_ZOOM: f32 = 0.5;         // Projection scale: 0.5 = twice as close
+pub const CAMERA_MAX_ZOOM: f32 = 4.0;
 
 // Uncle sprite dimensions
 pub const UNCLE_SPRITE_SIZE: f32 = 32.0;
 
+// Fish sprites (drawn in-process from fish traits, see fish_sprite.rs)
+pub const FISH_SPRITE_WIDTH: u32 = 48;        // Pixels; fish face right
+pub const FISH_SPRITE_HEIGHT: u32 = 24;
+pub const FISH_SPRITE_VARIANTS: u32 = 4;      // Pattern layouts per shape, color and pattern
+pub const CATCH_ANIMATION_SECONDS: f32 = 1.2; // A caught fish leaps over its uncle for this long
+pub const CATCH_ANIMATION_HEIGHT: f32 = 36.0; // Top of the leap, in world pixels
+
+// Simulation
+// All gameplay (fishing, escapes, cash-outs, day/night) runs in `FixedUpdate` at this rate,
+// so a seed plus the same actions on the same ticks reproduces a run on any machine.
+pub const SIMULATION_TICK_HZ: f64 = 20.0;  // 20 ticks per second = 50 ms per tick
+
 // Game balance
 pub const STARTING_GOLD: u32 = 100;
 pub const MULTIPLIER_INCREMENT: f32 = 0.1;
@@ -33,46 +57,88 @@ pub const BURST_PHASE_DURATION: f32 = 10.0;      // 0-10 seconds: high intensity
 pub const STOCHASTIC_PHASE_DURATION: f32 = 20.0; // 10-30 seconds: lactic buildup
 pub const FATIGUE_PHASE_START: f32 = 30.0;       // 30+ seconds: exhaustion
 
-// Base escape probabilities per phase (per second, not per frame)
-// These represent "flop frequency" × "success chance per flop"
-pub const BURST_ESCAPE_BASE: f32 = 0.15;       // 15% per second (3-5 Hz flops)
-pub const STOCHASTIC_ESCAPE_BASE: f32 = 0.08;  // 8% per second (1-2 Hz flops)
-pub const FATIGUE_ESCAPE_BASE: f32 = 0.02;     // 2% per second (<0.5 Hz flops)
+// Flop frequency per phase (flops per second). Escapes are rolled once per flop,
+// never per frame, so the same fish behaves the same at any frame or tick rate.
+pub const BURST_FLOP_HZ: f32 = 4.0;       // 3-5 Hz flops
+pub const STOCHASTIC_FLOP_HZ: f32 = 1.5;  // 1-2 Hz flops
+pub const FATIGUE_FLOP_HZ: f32 = 0.4;     // <0.5 Hz flops
 
-// Escape reduction per failed attempt (represents building "distance to safety")
-// Each failed flop moves fish slightly away or at wrong angle
-pub const ESCAPE_REDUCTION_PER_FAIL: f32 = 0.02; // -2% per failed attempt
-pub const MIN_ESCAPE_CHANCE: f32 = 0.001;        // Never goes below 0.1%
+// Chance that a single flop carries the fish back into the water
+// (flop frequency × success per flop ≈ 15% / 8% / 2% per second at the start of each phase)
+pub const BURST_FLOP_SUCCESS: f32 = 0.0375;
+pub const STOCHASTIC_FLOP_SUCCESS: f32 = 0.053;
+pub const FATIGUE_FLOP_SUCCESS: f32 = 0.05;
+
+// Each failed flop moves the fish slightly away or lands at a wrong angle,
+// multiplying the success chance of every later flop by this factor
+pub const FAILED_FLOP_DECAY: f32 = 0.97;
+pub const MIN_ESCAPE_CHANCE: f32 = 0.001;  // Per-flop chance never goes below 0.1%
 
 // Rarity modifiers (rarer fish are stronger/more desperate)
 pub const COMMON_ESCAPE_MULTIPLIER: f32 = 0.6;    // 60% of base
 pub const UNCOMMON_ESCAPE_MULTIPLIER: f32 = 1.0;  // 100% of base  
 pub const RARE_ESCAPE_MULTIPLIER: f32 = 1.4;      // 140% of base (more vigorous)
 
-// Uncle retention abilities (reduce escape chance)
-pub const MONGOLIAN_RETENTION: f32 = 1.0;  // No bonus (base)
-pub const SOMALI_RETENTION: f32 = 0.9;     // 10% better retention (faster response)
-pub const JAPANESE_RETENTION: f32 = 1.1;   // 10% worse (focused on rarity, not retention)
-
-// Fish value ranges
-pub const COMMON_VALUE_MIN: u32 = 1;
-pub const COMMON_VALUE_MAX: u32 = 8;
-pub const UNCOMMON_VALUE_MIN: u32 = 10;
-pub const UNCOMMON_VALUE_MAX: u32 = 24;
-pub const RARE_VALUE_MIN: u32 = 30;
-pub const RARE_VALUE_MAX: u32 = 100;
-
-// Fish generation
-pub const FISH_COLORS: &[&str] = &["Blue", "Red", "Green", "Yellow", "Purple", "Orange", "Pink", "Teal"];
-pub const FISH_PATTERNS: &[&str] = &["Striped", "Spotted", "Solid", "Marbled", "Gradient"];
-pub const FISH_SIZES: &[&str] = &["Tiny", "Small", "Medium", "Large", "Huge"];
-pub const FISH_SHAPES: &[&str] = &["Slim", "Round", "Flat", "Long", "Bulky"];
-
-// Uncle costs
-pub const MONGOLIAN_COST: u32 = 50;
-pub const SOMALI_COST: u32 = 150;
-pub const JAPANESE_COST: u32 = 300;
-
-// World generation
-pub const WATER_THRESHOLD: f32 = 0.3;
-pub const CENTER_WATER_RADIUS: f32 = 3.0;
+// Fish generation (species, values and colors come from assets/species.json)
+pub const FISH_SIZE_LENGTH_RATIOS: [f32; 4] = [0.75, 0.9, 1.15, 1.4];  // Length over the species median where Small, Medium, Large and Huge begin
+pub const OFF_HOURS_BITE_MULTIPLIER: f32 = 0.25;  // Day fish at night and night fish by day bite this much
+
+// Terrain
+pub const DOCK_COST: u32 = 40;                      // Building a dock over water next to the shore
+pub const FOREST_CLEARING_COST: u32 = 20;           // Extra cost to hire onto a forest tile
+pub const BEACH_CATCH_SPEED: f32 = 1.15;            // Uncles on a beach fish 15% faster
+pub const DEEP_WATER_CATCH_SPEED: f32 = 0.75;       // Deep water bites come slower...
+pub const DEEP_WATER_RARE_BONUS: f32 = 0.06;        // ...but +6% rare chance
+pub const SHALLOWS_CATCH_SPEED: f32 = 1.5;          // Shallows bite quickly...
+pub const SHALLOWS_RARITY_MULTIPLIER: f32 = 0.5;    // ...but rare and uncommon chances are halved
+pub const RIVER_CATCH_SPEED: f32 = 1.25;            // The current keeps carrying fish past
+pub const RIVER_UNCOMMON_BONUS: f32 = 0.10;         // +10% uncommon chance (migrating fish)
+
+// Fishing grounds
+pub const WATER_TILE_CAPACITY: f32 = 30.0;          // Fish a tile of average density adds to its water body
+pub const POPULATION_GROWTH_PER_DAY: f32 = 1.0;     // Logistic growth rate per in-game day
+pub const POPULATION_RESEED: f32 = 0.5;             // Fished-out populations regrow from this many strays
+pub const DEEP_WATER_DENSITY: f32 = 0.7;            // Deep water holds fewer fish...
+pub const SHALLOWS_DENSITY: f32 = 1.3;              // ...shallows more
+pub const RIVER_DENSITY: f32 = 0.8;
+pub const DEPTH_RARE_BONUS: f32 = 0.04;             // Up to +4% rare chance at the deepest point
+pub const SPECIES_REGION_SIZE: usize = 4;           // Blocks of tiles that share a species pool
+
+// World generation (defaults for `WorldGenConfig`)
+pub const WORLDGEN_NOISE_SCALE: f32 = 6.0;        // Largest features span about 6 tiles
+pub const WORLDGEN_OCTAVES: u32 = 4;
+pub const WORLDGEN_PERSISTENCE: f32 = 0.5;
+pub const WORLDGEN_LACUNARITY: f32 = 2.0;
+pub const WORLDGEN_WATER_LEVEL: f32 = 0.45;       // Elevation below which tiles are water
+pub const WORLDGEN_FALLOFF_STRENGTH: f32 = 0.25;
+pub const WORLDGEN_SMOOTHING_PASSES: u32 = 2;
+pub const WORLDGEN_DEEP_WATER_DEPTH: f32 = 0.1;   // Water this far below the water level is deep
+pub const WORLDGEN_SHALLOWS_DEPTH: f32 = 0.03;    // Water within this of the water level is shallow
+pub const WORLDGEN_BEACH_HEIGHT: f32 = 0.05;      // Low land next to water within this of the water level
+pub const WORLDGEN_ROCK_LEVEL: f32 = 0.68;        // Elevation above which land is bare rock
+pub const WORLDGEN_FOREST_LEVEL: f32 = 0.58;      // Moisture above which land is forest
+pub const WORLDGEN_RIVER_SCALE: f32 = 8.0;        // Size in tiles of river bends
+pub const WORLDGEN_RIVER_WIDTH: f32 = 0.05;       // Band of river noise around zero that becomes river
+
+// Save/load
+pub const SAVE_FILE_PATH: &str = "rarephish_save.json";
+pub const FISHDEX_FILE_PATH: &str = "rarephish_fishdex.json";  // Kept across runs, unlike the save
+pub const AUTOSAVE_INTERVAL_SECONDS: f32 = 60.0;  // Autosave once a minute
+
+// Offline catch-up
+pub const MAX_OFFLINE_SECONDS: f32 = 8.0 * 60.0 * 60.0;  // Idle progress is capped at 8 hours
+pub const OFFLINE_STEP_SECONDS: f32 = 1.0;               // Coarse step size for the catch-up simulation
+pub const MIN_OFFLINE_REPORT_SECONDS: f32 = 60.0;        // Shorter absences skip the summary popup
+
+// Replays
+pub const REPLAY_FILE_PATH: &str = "rarephish_replay.json";
+
+// Notifications
+pub const TOAST_LIFETIME_SECONDS: f32 = 4.0;  // How long a toast stays on screen
+pub const TOAST_FADE_SECONDS: f32 = 1.0;      // Final part of the lifetime spent fading out
+pub const MAX_VISIBLE_TOASTS: usize = 5;      // Older toasts are dropped when more stack up
+pub const NOTIFICATION_HISTORY_LEN: usize = 300;  // Entries kept in the scrollable event log
+pub const HISTORY_SCROLL_SPEED: f32 = 20.0;   // Pixels per mouse wheel line
+
+// Seed entry
+pub const SEED_ENTRY_MAX_LEN: usize = 40;  // Characters the header's seed field accepts
@@ -0,0 +1,125 @@
+use bevy::prelude::*;
+
+use crate::components::Fish;
+use crate::uncles::UncleType;
+
+// Gameplay events. Simulation systems send these from `FixedUpdate`; UI, logging and
+// statistics read them instead of reaching into gameplay code.
+
+/// A fish landed in an uncle's basket
+#[derive(Event, Clone, Debug)]
+pub struct FishCaught {
+    pub uncle: Entity,
+    pub uncle_type: UncleType,
+    pub fish: Fish,
+}
+
+/// A caught fish was the first of its species and color in the Fishdex
+#[derive(Event, Clone, Debug)]
+pub struct FishDiscovered {
+    pub uncle: Entity,
+    pub uncle_type: UncleType,
+    pub fish: Fish,
+}
+
+/// A caught fish outweighed the previous record of its species
+#[derive(Event, Clone, Debug)]
+pub struct RecordBroken {
+    pub uncle: Entity,
+    pub uncle_type: UncleType,
+    pub fish: Fish,
+    pub previous_kg: f32,
+}
+
+/// A fish flopped out of an uncle's basket and back into the water
+#[derive(Event, Clone, Debug)]
+pub struct FishEscaped {
+    pub uncle: Entity,
+    pub uncle_type: UncleType,
+    pub fish: Fish,
+}
+
+/// The player threw a fish back to make space (R)
+#[derive(Event, Clone, Debug)]
+pub struct FishDiscarded {
+    pub uncle: Entity,
+    pub fish: Fish,
+}
+
+/// An uncle was hired and placed on the map
+#[derive(Event, Clone, Debug)]
+pub struct UnclePlaced {
+    pub uncle: Entity,
+    pub uncle_type: UncleType,
+    pub x: i32,
+    pub y: i32,
+    pub cost: u32,
+}
+
+/// A dock was built over the water tile at (x, y)
+#[derive(Event, Clone, Copy, Debug)]
+pub struct DockBuilt {
+    pub x: i32,
+    pub y: i32,
+    pub cost: u32,
+}
+
+/// A new in-game day began and cash-outs were refreshed
+#[derive(Event, Clone, Copy, Debug)]
+pub struct DayStarted {
+    pub day_number: u32,
+    pub cashouts_remaining: u32,
+}
+
+/// Dawn (`is_day` true) or dusk (`is_day` false)
+#[derive(Event, Clone, Copy, Debug)]
+pub struct DaylightChanged {
+    pub is_day: bool,
+}
+
+/// Which baskets a cash-out sells
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CashOutScope {
+    /// Only the basket of the uncle carrying `SelectedUncleMarker`
+    Selected,
+    /// Every non-empty basket, counted as a single cash-out
+    All,
+}
+
+/// Asks the economy to sell baskets. Keyboard, buttons and scripted strategies all
+/// end up here, so the daily limit, cooldown and multiplier rules are applied once.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct CashOutRequest {
+    pub scope: CashOutScope,
+}
+
+/// The player asked to abandon the run and start over in the world of `seed`
+#[derive(Event, Clone, Copy, Debug)]
+pub struct NewWorldRequested {
+    pub seed: u64,
+}
+
+/// A cash-out that went through
+#[derive(Event, Clone, Copy, Debug)]
+pub struct CashedOut {
+    pub scope: CashOutScope,
+    pub fish_sold: u32,
+    pub gold_earned: u32,
+    pub multiplier: f32,  // Multiplier the fish were sold at
+    pub cashouts_remaining: u32,
+}
+
+/// Why a cash-out request was turned down
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CashOutDenial {
+    Cooldown,
+    NoCashoutsRemaining,
+    NothingToSell,
+}
+
+/// A cash-out request that was turned down
+#[derive(Event, Clone, Copy, Debug)]
+pub struct CashOutDenied {
+    pub scope: CashOutScope,
+    pub reason: CashOutDenial,
+}
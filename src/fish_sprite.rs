@@ -0,0 +1,211 @@
+//! Fish sprites painted from fish traits.
+//!
+//! There is no fish art on disk: every sprite is drawn into an `Image` in-process. The
+//! shape gives the outline of the body and tail, the color the paint and the pattern what is
+//! painted over it. A fish always gets the same sprite: its species, length and value pick
+//! one of `FISH_SPRITE_VARIANTS` layouts of its pattern, so two fish of a kind can differ a
+//! little while the number of images stays small. `FishSprites` draws each look once and
+//! hands out the same handle afterwards.
+
+use bevy::prelude::*;
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy::render::texture::ImageSampler;
+use std::collections::HashMap;
+
+use crate::components::Fish;
+use crate::constants::{FISH_SPRITE_HEIGHT, FISH_SPRITE_VARIANTS, FISH_SPRITE_WIDTH};
+use crate::species::{FishColor, FishPattern, FishShape, SpeciesDef};
+use crate::worldgen::{hash01, perlin};
+
+const EYE_COLOR: [u8; 4] = [20, 20, 28, 255];
+
+/// Everything that decides what a sprite looks like
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct FishLook {
+    pub shape: FishShape,
+    pub color: FishColor,
+    pub pattern: FishPattern,
+    pub variant: u32,  // Layout of the pattern, 0..FISH_SPRITE_VARIANTS
+}
+
+impl FishLook {
+    /// The look of one caught fish
+    pub fn of(fish: &Fish) -> Self {
+        // FNV-1a of the species id, so the pick is the same on every machine and build
+        let species = fish.species.as_str().bytes().fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+        });
+        let pick = hash01(species, fish.length_cm.to_bits() as i32, fish.value as i32);
+        Self {
+            shape: fish.traits.shape,
+            color: fish.traits.color,
+            pattern: fish.traits.pattern,
+            variant: ((pick * FISH_SPRITE_VARIANTS as f32) as u32).min(FISH_SPRITE_VARIANTS - 1),
+        }
+    }
+
+    /// A species in one of its colors, where no particular fish is shown (the Fishdex)
+    pub fn of_species(def: &SpeciesDef, color: FishColor) -> Self {
+        Self { shape: def.shape, color, pattern: def.pattern, variant: 0 }
+    }
+
+    fn seed(&self) -> u64 {
+        (self.shape as u64) << 24 | (self.color as u64) << 16 | (self.pattern as u64) << 8 | self.variant as u64
+    }
+}
+
+/// Body and tail proportions of a shape, as fractions of the sprite's width (lengths) and
+/// height (heights). `roundness` is the exponent of the body's outline: 1 is a diamond, 2 an
+/// ellipse and higher values are boxier.
+struct Outline {
+    half_length: f32,
+    half_height: f32,
+    roundness: f32,
+    tail_length: f32,
+    tail_half_height: f32,
+}
+
+impl Outline {
+    fn of(shape: FishShape) -> Self {
+        let (half_length, half_height, roundness, tail_length, tail_half_height) = match shape {
+            FishShape::Slim => (0.34, 0.2, 2.0, 0.22, 0.28),
+            FishShape::Round => (0.3, 0.4, 2.0, 0.18, 0.34),
+            FishShape::Flat => (0.34, 0.44, 1.2, 0.18, 0.3),
+            FishShape::Long => (0.42, 0.13, 2.5, 0.12, 0.2),
+            FishShape::Bulky => (0.32, 0.4, 3.0, 0.2, 0.38),
+        };
+        Self { half_length, half_height, roundness, tail_length, tail_half_height }
+    }
+
+    /// Horizontal centre of the body; the head almost touches the right edge
+    fn centre(&self) -> f32 {
+        0.97 - self.half_length
+    }
+
+    fn in_body(&self, u: f32, v: f32) -> bool {
+        let x = ((u - self.centre()) / self.half_length).abs();
+        let y = (v / self.half_height).abs();
+        x.powf(self.roundness) + y.powf(self.roundness) <= 1.0
+    }
+
+    /// A forked tail that widens away from the body
+    fn in_tail(&self, u: f32, v: f32) -> bool {
+        let joint = self.centre() - self.half_length * 0.85;
+        let t = (joint - u) / self.tail_length;
+        if !(0.0..=1.0).contains(&t) {
+            return false;
+        }
+        let half = self.tail_half_height * (0.2 + 0.8 * t);
+        let fork = self.tail_half_height * (t - 0.65).max(0.0) * 1.6;
+        v.abs() <= half && v.abs() >= fork
+    }
+}
+
+/// Paints the sprite of `look`: `FISH_SPRITE_WIDTH` × `FISH_SPRITE_HEIGHT` pixels, facing
+/// right, transparent around the fish
+pub fn render_fish(look: FishLook) -> Image {
+    let (width, height) = (FISH_SPRITE_WIDTH as usize, FISH_SPRITE_HEIGHT as usize);
+    let outline = Outline::of(look.shape);
+    let uv = |x: usize, y: usize| ((x as f32 + 0.5) / width as f32, (y as f32 + 0.5) / height as f32 - 0.5);
+
+    let mask: Vec<bool> = (0..width * height)
+        .map(|i| {
+            let (u, v) = uv(i % width, i / width);
+            outline.in_body(u, v) || outline.in_tail(u, v)
+        })
+        .collect();
+    let inside = |x: i32, y: i32| {
+        x >= 0 && y >= 0 && (x as usize) < width && (y as usize) < height && mask[y as usize * width + x as usize]
+    };
+
+    let seed = look.seed();
+    let spots: Vec<(f32, f32, f32)> = (0..4 + look.variant as i32)
+        .map(|i| {
+            let u = outline.centre() + (hash01(seed, i, 1) * 1.4 - 0.8) * outline.half_length;
+            let v = (hash01(seed, i, 2) * 1.2 - 0.6) * outline.half_height;
+            (u, v, 1.2 + hash01(seed, i, 3) * 1.6)
+        })
+        .collect();
+    let stripes = 3.0 + (look.variant % 2) as f32;
+    let stripe_phase = hash01(seed, 0, 0);
+    let eye = (outline.centre() + outline.half_length * 0.6, -outline.half_height * 0.25);
+    let base = look.color.color().to_srgba();
+
+    let mut data = vec![0u8; width * height * 4];
+    for y in 0..height {
+        for x in 0..width {
+            if !mask[y * width + x] {
+                continue;
+            }
+            let (u, v) = uv(x, y);
+            let pixel = &mut data[(y * width + x) * 4..][..4];
+            let distance_px = |(pu, pv): (f32, f32)| (((u - pu) * width as f32).powi(2) + ((v - pv) * height as f32).powi(2)).sqrt();
+            if distance_px(eye) <= 1.3 {
+                pixel.copy_from_slice(&EYE_COLOR);
+                continue;
+            }
+
+            // Patterns cover the body; the tail keeps the plain color
+            let on_body = outline.in_body(u, v);
+            let mut shade = match look.pattern {
+                FishPattern::Solid => 1.0,
+                FishPattern::Striped if on_body => {
+                    let across = (u - outline.centre()) / outline.half_length;
+                    if ((across + 1.0) * stripes / 2.0 + stripe_phase).fract() < 0.35 { 0.55 } else { 1.0 }
+                }
+                FishPattern::Spotted if on_body && spots.iter().any(|&(su, sv, r)| distance_px((su, sv)) <= r) => 0.5,
+                FishPattern::Marbled if on_body => {
+                    let n = perlin(seed, x as f32 * 0.22, y as f32 * 0.3);
+                    if n.abs() < 0.12 { 1.45 } else { 0.9 + 0.15 * n }
+                }
+                FishPattern::Gradient => 0.55 + 0.8 * (v + 0.5),  // Dark back, pale belly
+                _ => 1.0,
+            };
+            // Darker rim
+            let (xi, yi) = (x as i32, y as i32);
+            if ![(xi - 1, yi), (xi + 1, yi), (xi, yi - 1), (xi, yi + 1)].iter().all(|&(nx, ny)| inside(nx, ny)) {
+                shade *= 0.5;
+            }
+
+            // Above 1 mixes in white instead of saturating
+            let channel = |c: f32| {
+                let lit = if shade > 1.0 { c + (1.0 - c) * (shade - 1.0) } else { c * shade };
+                (lit.clamp(0.0, 1.0) * 255.0).round() as u8
+            };
+            pixel.copy_from_slice(&[channel(base.red), channel(base.green), channel(base.blue), 255]);
+        }
+    }
+
+    let mut image = Image::new(
+        Extent3d { width: FISH_SPRITE_WIDTH, height: FISH_SPRITE_HEIGHT, depth_or_array_layers: 1 },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    );
+    // Crisp pixels when scaled up
+    image.sampler = ImageSampler::nearest();
+    image
+}
+
+/// Fish sprite images drawn so far, one per look
+#[derive(Resource, Default)]
+pub struct FishSprites {
+    images: HashMap<FishLook, Handle<Image>>,
+}
+
+impl FishSprites {
+    /// The sprite of `look`, drawn the first time it is asked for
+    pub fn get(&mut self, look: FishLook, images: &mut Assets<Image>) -> Handle<Image> {
+        self.images.entry(look).or_insert_with(|| images.add(render_fish(look))).clone()
+    }
+
+    pub fn len(&self) -> usize {
+        self.images.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.images.is_empty()
+    }
+}
@@ -0,0 +1,186 @@
+//! The Fishdex: a collection log of every kind of fish ever caught.
+//!
+//! An entry is a species in one of its colors (pattern and shape are fixed per species).
+//! The Fishdex remembers when each entry was first caught and by whom, how many were caught
+//! and got away, and the largest and most valuable specimen. Unlike a run it is never reset:
+//! it lives in its own file next to the save and carries over to new worlds.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::components::{Fish, FishRarity};
+use crate::save::SaveError;
+use crate::species::{FishColor, SpeciesCatalogue, SpeciesId};
+use crate::uncles::UncleType;
+
+/// Current Fishdex file version
+pub const FISHDEX_VERSION: u32 = 1;
+
+/// One remembered fish: the largest or the most valuable of an entry
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Specimen {
+    pub length_cm: f32,
+    pub weight_kg: f32,
+    pub value: u32,
+    pub caught_by_uncle: UncleType,
+    pub day: u32,  // In-game day of the run it was caught in
+}
+
+impl Specimen {
+    fn of(fish: &Fish, day: u32) -> Self {
+        Self {
+            length_cm: fish.length_cm,
+            weight_kg: fish.weight_kg,
+            value: fish.value,
+            caught_by_uncle: fish.caught_by_uncle.clone(),
+            day,
+        }
+    }
+}
+
+/// Everything known about one species in one color
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FishdexEntry {
+    pub species: SpeciesId,
+    pub color: FishColor,
+    pub first_caught_day: u32,
+    pub first_caught_by: UncleType,
+    pub caught: u32,
+    pub escaped: u32,  // Caught fish that flopped back into the water
+    pub largest: Specimen,
+    pub most_valuable: Specimen,
+}
+
+#[derive(Serialize, Deserialize)]
+struct FishdexFile {
+    version: u32,
+    entries: Vec<FishdexEntry>,
+}
+
+/// Every species and color combination caught so far, across all runs
+#[derive(Resource, Default, Clone)]
+pub struct Fishdex {
+    entries: BTreeMap<(SpeciesId, FishColor), FishdexEntry>,
+}
+
+impl Fishdex {
+    /// Counts a catch made on `day`, returning true if it is the first of its entry.
+    /// Fish caught before the species catalogue are not collected.
+    pub fn record_catch(&mut self, fish: &Fish, day: u32) -> bool {
+        if fish.species.as_str().is_empty() {
+            return false;
+        }
+        let specimen = Specimen::of(fish, day);
+        match self.entries.get_mut(&(fish.species.clone(), fish.traits.color)) {
+            Some(entry) => {
+                entry.caught += 1;
+                if specimen.weight_kg > entry.largest.weight_kg {
+                    entry.largest = specimen.clone();
+                }
+                if specimen.value > entry.most_valuable.value {
+                    entry.most_valuable = specimen;
+                }
+                false
+            }
+            None => {
+                let entry = FishdexEntry {
+                    species: fish.species.clone(),
+                    color: fish.traits.color,
+                    first_caught_day: day,
+                    first_caught_by: fish.caught_by_uncle.clone(),
+                    caught: 1,
+                    escaped: 0,
+                    largest: specimen.clone(),
+                    most_valuable: specimen,
+                };
+                self.entries.insert((entry.species.clone(), entry.color), entry);
+                true
+            }
+        }
+    }
+
+    /// Counts an escape against the fish's entry
+    pub fn record_escape(&mut self, fish: &Fish) {
+        if let Some(entry) = self.entries.get_mut(&(fish.species.clone(), fish.traits.color)) {
+            entry.escaped += 1;
+        }
+    }
+
+    pub fn get(&self, species: &SpeciesId, color: FishColor) -> Option<&FishdexEntry> {
+        self.entries.get(&(species.clone(), color))
+    }
+
+    /// Every discovered entry, by species id and color
+    pub fn iter(&self) -> impl Iterator<Item = &FishdexEntry> {
+        self.entries.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Discovered and total entries of a rarity in `catalogue`. Entries of species or colors
+    /// no longer in the catalogue are not counted.
+    pub fn completion(&self, catalogue: &SpeciesCatalogue, rarity: FishRarity) -> (usize, usize) {
+        catalogue
+            .iter()
+            .filter(|def| def.rarity == rarity)
+            .flat_map(|def| def.colors.iter().map(move |&color| (&def.id, color)))
+            .fold((0, 0), |(found, total), (id, color)| {
+                (found + usize::from(self.get(id, color).is_some()), total + 1)
+            })
+    }
+
+    pub fn from_json(text: &str) -> Result<Self, SaveError> {
+        let file: FishdexFile = serde_json::from_str(text)?;
+        if file.version == 0 || file.version > FISHDEX_VERSION {
+            return Err(SaveError::UnsupportedVersion(file.version));
+        }
+        let entries = file.entries
+            .into_iter()
+            .map(|entry| ((entry.species.clone(), entry.color), entry))
+            .collect();
+        Ok(Self { entries })
+    }
+
+    pub fn to_json(&self) -> Result<String, SaveError> {
+        let file = FishdexFile {
+            version: FISHDEX_VERSION,
+            entries: self.entries.values().cloned().collect(),
+        };
+        Ok(serde_json::to_string_pretty(&file)?)
+    }
+
+    pub fn read(path: &Path) -> Result<Self, SaveError> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+
+    /// The Fishdex at `path`, or an empty one if there is none yet or it cannot be read
+    pub fn read_or_default(path: &Path) -> Self {
+        match Self::read(path) {
+            Ok(fishdex) => {
+                println!("📖 Loaded {} Fishdex entries from {}", fishdex.len(), path.display());
+                fishdex
+            }
+            Err(SaveError::Io(err)) if err.kind() == std::io::ErrorKind::NotFound => Self::default(),
+            Err(err) => {
+                eprintln!("⚠️ Could not load {}: {}; starting an empty Fishdex", path.display(), err);
+                Self::default()
+            }
+        }
+    }
+
+    /// Writes to a temporary file first, like the save
+    pub fn write(&self, path: &Path) -> Result<(), SaveError> {
+        let tmp_path = path.with_extension("tmp");
+        std::fs::write(&tmp_path, self.to_json()?)?;
+        std::fs::rename(&tmp_path, path)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,469 @@
+//! Fishing grounds: what lives in the water and how much of it is left.
+//!
+//! Every water tile has a depth, a fish density and a pool of species from the
+//! `SpeciesCatalogue`, drawn from the species living in its kind of water. Connected water
+//! (flood-filled through docks, which fish swim under) forms a water body, which keeps one
+//! population per species living in it; a lake reaching into a newly generated chunk absorbs
+//! that chunk's part of it. Uncles fish every water tile within their casting
+//! radius; each catch picks a species from one of those tiles in proportion to how common it
+//! should be, whether it bites at this time of day and how much of its population is left,
+//! then removes one fish from the body.
+//! Uncles anywhere on the same lake therefore compete for the same fish, and an overfished
+//! lake bites less often. Rare fish are caught beyond their natural share wherever rare
+//! bonuses apply (deep water, Japanese uncles), so their small populations run out first.
+//! Populations regrow logistically, at `POPULATION_GROWTH_PER_DAY` per in-game day.
+
+use bevy::prelude::*;
+use rand::Rng;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use crate::components::{FishRarity, TileType};
+use crate::constants::*;
+use crate::grid::{chunk_of, ChunkCoord, TileGrid};
+use crate::species::{Activity, SpeciesCatalogue, SpeciesDef};
+use crate::worldgen::{fbm, hash01, WorldGenConfig};
+
+/// A species of the `SpeciesCatalogue` as the fishing grounds see it: its position in the
+/// catalogue and what decides how often it bites
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct FishSpecies {
+    index: usize,
+    rarity: FishRarity,
+    activity: Activity,
+}
+
+impl FishSpecies {
+    pub fn new(index: usize, def: &SpeciesDef) -> Self {
+        Self { index, rarity: def.rarity, activity: def.activity }
+    }
+
+    /// Position in the catalogue
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn rarity(&self) -> FishRarity {
+        self.rarity
+    }
+
+    pub fn activity(&self) -> Activity {
+        self.activity
+    }
+}
+
+/// Fishing properties of one water tile
+#[derive(Clone, Debug, PartialEq)]
+pub struct WaterTile {
+    pub tile_type: TileType,
+    pub depth: f32,               // 0 at the water line, 1 at the deepest point
+    pub density: f32,             // Scales the fish the tile adds to its body and how often it bites
+    pub body: BodyId,             // The water body the tile's fish live in
+    pub pool: Vec<FishSpecies>,   // Species that live here, commons first
+}
+
+impl WaterTile {
+    pub fn capacity(&self) -> f32 {
+        WATER_TILE_CAPACITY * self.density
+    }
+
+    /// Chance that a catch here is of `rarity` when every population is full, for an uncle
+    /// with the given `UncleDef::rare_bonus`
+    pub fn rarity_chance(&self, rarity: FishRarity, rare_bonus: f32) -> f32 {
+        let mult = self.tile_type.rarity_multiplier();
+        let rare = (RARE_CHANCE + rare_bonus + self.tile_type.rare_bonus() + DEPTH_RARE_BONUS * self.depth) * mult;
+        let uncommon = (UNCOMMON_CHANCE + self.tile_type.uncommon_bonus()) * mult;
+        match rarity {
+            FishRarity::Rare => rare,
+            FishRarity::Uncommon => uncommon,
+            FishRarity::Common => (1.0 - rare - uncommon).max(0.0),
+        }
+    }
+
+    /// Species in this tile's pool sharing one rarity split that rarity's chance
+    fn species_share(&self, species: FishSpecies, chance: f32) -> f32 {
+        let same_rarity = self.pool.iter().filter(|s| s.rarity() == species.rarity()).count();
+        chance / same_rarity as f32
+    }
+}
+
+/// Fish of one species living in a water body
+#[derive(Clone, Debug, PartialEq)]
+pub struct Population {
+    pub species: FishSpecies,
+    pub count: f32,
+    pub capacity: f32,  // Carrying capacity: the count the population regrows towards
+}
+
+impl Population {
+    pub fn fullness(&self) -> f32 {
+        if self.capacity > 0.0 { self.count / self.capacity } else { 0.0 }
+    }
+
+    /// Logistic growth over `days`, using the exact solution so any step size gives the same result
+    fn regrow(&mut self, days: f32) {
+        if self.capacity <= 0.0 {
+            return;
+        }
+        // A fished-out population is restocked by a few strays from elsewhere
+        let n = self.count.max(POPULATION_RESEED.min(self.capacity));
+        let k = self.capacity;
+        self.count = k / (1.0 + (k / n - 1.0) * (-POPULATION_GROWTH_PER_DAY * days).exp());
+    }
+}
+
+/// Connected water and the fish living in it
+#[derive(Clone, Debug, PartialEq)]
+pub struct WaterBody {
+    pub tiles: Vec<(i32, i32)>,          // Every tile of the body, docks included, in row order
+    pub populations: Vec<Population>,    // Sorted by species index
+}
+
+impl WaterBody {
+    pub fn population(&self, species: FishSpecies) -> Option<&Population> {
+        self.populations.iter().find(|p| p.species == species)
+    }
+
+    /// Chunks the body's tiles lie in, in row order
+    pub fn chunks(&self) -> Vec<ChunkCoord> {
+        let chunks: BTreeSet<ChunkCoord> = self.tiles.iter().map(|&(x, y)| chunk_of(x, y)).collect();
+        let mut chunks: Vec<ChunkCoord> = chunks.into_iter().collect();
+        chunks.sort_by_key(|&(cx, cy)| (cy, cx));
+        chunks
+    }
+
+    /// Takes in the tiles and fish of a body it connects with
+    fn absorb(&mut self, other: WaterBody) {
+        self.tiles.extend(other.tiles);
+        self.tiles.sort_by_key(|&(x, y)| (y, x));
+        for population in other.populations {
+            match self.populations.iter_mut().find(|p| p.species == population.species) {
+                Some(existing) => {
+                    existing.count += population.count;
+                    existing.capacity += population.capacity;
+                }
+                None => self.populations.push(population),
+            }
+        }
+        self.populations.sort_by_key(|p| p.species.index());
+    }
+
+    /// Share of all the body's fish still there
+    pub fn fullness(&self) -> f32 {
+        let capacity: f32 = self.populations.iter().map(|p| p.capacity).sum();
+        if capacity > 0.0 {
+            self.populations.iter().map(|p| p.count).sum::<f32>() / capacity
+        } else {
+            0.0
+        }
+    }
+}
+
+/// A water body: a chunk it lies in and its index among the water flood-filled in that chunk.
+/// A body spanning several chunks keeps the lowest id of its parts.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct BodyId {
+    pub chunk: ChunkCoord,
+    pub index: usize,
+}
+
+/// Water tiles by grid position, and the water bodies they belong to.
+/// Each chunk's water is flood-filled on its own, so its tiles never depend on which of its
+/// neighbours have been generated, then joins the bodies of generated neighbours it touches.
+#[derive(Resource, Default, Clone, Debug)]
+pub struct FishingGrounds {
+    pub tiles: HashMap<(i32, i32), WaterTile>,
+    pub bodies: BTreeMap<BodyId, WaterBody>,
+    body_of: HashMap<(i32, i32), BodyId>,  // Every body tile, docks included
+}
+
+impl FishingGrounds {
+    /// Fully stocked grounds for every generated chunk of `grid`
+    pub fn new(seed: u64, config: &WorldGenConfig, catalogue: &SpeciesCatalogue, grid: &TileGrid) -> Self {
+        let mut grounds = Self::default();
+        for chunk in grid.chunks() {
+            grounds.add_chunk(seed, config, catalogue, grid, chunk);
+        }
+        grounds
+    }
+
+    /// Stocks the water of a newly generated chunk and joins it to the bodies it runs on into
+    pub fn add_chunk(&mut self, seed: u64, config: &WorldGenConfig, catalogue: &SpeciesCatalogue, grid: &TileGrid, chunk: ChunkCoord) {
+        let in_chunk = |x: i32, y: i32, tile_type: TileType| chunk_of(x, y) == chunk && holds_fish(tile_type);
+
+        // Flood fill in row order so body indices only depend on the chunk
+        let mut body_of: HashMap<(i32, i32), BodyId> = HashMap::new();
+        let mut bodies = Vec::new();
+        for (x, y, tile_type) in grid.chunk_tiles(chunk) {
+            if body_of.contains_key(&(x, y)) || !holds_fish(tile_type) {
+                continue;
+            }
+            let id = BodyId { chunk, index: bodies.len() };
+            let mut body_tiles = grid.flood_fill((x, y), in_chunk);
+            body_tiles.sort_by_key(|&(x, y)| (y, x));
+            for &pos in &body_tiles {
+                body_of.insert(pos, id);
+            }
+            bodies.push((id, body_tiles));
+        }
+
+        // Each tile adds its capacity to the populations of its pool, split like its catches
+        let mut capacities: HashMap<BodyId, HashMap<FishSpecies, f32>> = HashMap::new();
+        for (x, y, tile_type) in grid.chunk_tiles(chunk).into_iter().filter(|(_, _, t)| t.is_water()) {
+            let tile = water_tile(seed, config, catalogue, x, y, tile_type, body_of[&(x, y)]);
+            let body = capacities.entry(tile.body).or_default();
+            for &species in &tile.pool {
+                let share = tile.species_share(species, base_chance(species.rarity()));
+                *body.entry(species).or_default() += tile.capacity() * share;
+            }
+            self.tiles.insert((x, y), tile);
+        }
+
+        let new_ids: Vec<BodyId> = bodies.iter().map(|&(id, _)| id).collect();
+        for (id, tiles) in bodies {
+            let mut populations: Vec<Population> = capacities
+                .remove(&id)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(species, capacity)| Population { species, count: capacity, capacity })
+                .collect();
+            populations.sort_by_key(|p| p.species.index());
+            self.body_of.extend(tiles.iter().map(|&pos| (pos, id)));
+            self.bodies.insert(id, WaterBody { tiles, populations });
+        }
+
+        // Water running on into generated neighbours is one body, under the lowest id of its parts
+        for id in new_ids {
+            let Some(body) = self.bodies.get(&id) else {
+                continue;
+            };
+            let mut parts: BTreeSet<BodyId> = body
+                .tiles
+                .iter()
+                .flat_map(|&(x, y)| grid.edge_neighbours(x, y))
+                .filter(|&(x, y)| chunk_of(x, y) != chunk)
+                .filter_map(|pos| self.body_of.get(&pos).copied())
+                .collect();
+            parts.insert(id);
+            let mut parts = parts.into_iter();
+            let into = parts.next().expect("the body itself is a part");
+            for from in parts {
+                self.merge(into, from);
+            }
+        }
+    }
+
+    /// Moves the tiles and fish of body `from` into body `into`
+    fn merge(&mut self, into: BodyId, from: BodyId) {
+        let Some(absorbed) = self.bodies.remove(&from) else {
+            return;
+        };
+        for pos in &absorbed.tiles {
+            self.body_of.insert(*pos, into);
+            if let Some(tile) = self.tiles.get_mut(pos) {
+                tile.body = into;
+            }
+        }
+        if let Some(body) = self.bodies.get_mut(&into) {
+            body.absorb(absorbed);
+        }
+    }
+
+    pub fn get(&self, x: i32, y: i32) -> Option<&WaterTile> {
+        self.tiles.get(&(x, y))
+    }
+
+    /// The water body a water tile belongs to
+    pub fn body_at(&self, x: i32, y: i32) -> Option<&WaterBody> {
+        self.get(x, y).map(|tile| &self.bodies[&tile.body])
+    }
+
+    /// Stops a tile being fished, e.g. when a dock is built over it.
+    /// Its fish stay in the water body.
+    pub fn remove(&mut self, x: i32, y: i32) {
+        self.tiles.remove(&(x, y));
+    }
+
+    /// Water tiles within `radius` (in tiles, diagonals included) of (x, y), in a fixed order
+    pub fn reachable(&self, x: i32, y: i32, radius: u32) -> Vec<(i32, i32)> {
+        let r = radius as i32;
+        (-r..=r)
+            .flat_map(|dy| (-r..=r).map(move |dx| (x + dx, y + dy)))
+            .filter(|pos| self.tiles.contains_key(pos))
+            .collect()
+    }
+
+    /// Each species of a tile's pool with its weight for the next catch: its share of the
+    /// catch at full population, scaled by how much of it is left and whether it bites now
+    fn species_weights<'a>(&'a self, tile: &'a WaterTile, rare_bonus: f32, is_day: bool) -> impl Iterator<Item = (FishSpecies, f32)> + 'a {
+        let body = &self.bodies[&tile.body];
+        tile.pool.iter().map(move |&species| {
+            let share = tile.species_share(species, tile.rarity_chance(species.rarity(), rare_bonus));
+            // Less than one whole fish left cannot be caught
+            let fullness = body.population(species).filter(|p| p.count >= 1.0).map_or(0.0, |p| p.fullness());
+            (species, share * fullness * species.activity().bite_multiplier(is_day))
+        })
+    }
+
+    /// How much of the catch a tile could give right now, 0 (fished out or nothing biting)
+    /// to 1 (fully stocked, everything biting)
+    pub fn tile_health(&self, tile: &WaterTile, rare_bonus: f32, is_day: bool) -> f32 {
+        self.species_weights(tile, rare_bonus, is_day).map(|(_, weight)| weight).sum()
+    }
+
+    /// How strongly a tile draws bites: faster water, denser and better stocked tiles bite more
+    fn bite_weight(&self, tile: &WaterTile, rare_bonus: f32, is_day: bool) -> f32 {
+        tile.tile_type.catch_speed() * tile.density * self.tile_health(tile, rare_bonus, is_day)
+    }
+
+    /// Fishing timer speed over `reach`: the average bite weight, so depleted water slows uncles down
+    pub fn bite_rate(&self, reach: &[(i32, i32)], rare_bonus: f32, is_day: bool) -> f32 {
+        if reach.is_empty() {
+            return 0.0;
+        }
+        reach
+            .iter()
+            .filter_map(|pos| self.tiles.get(pos))
+            .map(|tile| self.bite_weight(tile, rare_bonus, is_day))
+            .sum::<f32>() / reach.len() as f32
+    }
+
+    /// Picks the tile and species of the next catch and removes that fish from its water body
+    pub fn take_fish(&mut self, reach: &[(i32, i32)], rare_bonus: f32, is_day: bool, rng: &mut impl Rng) -> Option<FishSpecies> {
+        let tiles: Vec<&WaterTile> = reach.iter().filter_map(|pos| self.tiles.get(pos)).collect();
+        let tile_weights: Vec<f32> = tiles.iter().map(|tile| self.bite_weight(tile, rare_bonus, is_day)).collect();
+        let tile = tiles[weighted_index(&tile_weights, rng)?];
+
+        let (species, weights): (Vec<FishSpecies>, Vec<f32>) = self.species_weights(tile, rare_bonus, is_day).unzip();
+        let species = species[weighted_index(&weights, rng)?];
+
+        let body = tile.body;
+        let population = self.bodies.get_mut(&body)?.populations.iter_mut().find(|p| p.species == species)?;
+        population.count -= 1.0;
+        Some(species)
+    }
+
+    /// Regrows every population over `days` in-game days
+    pub fn regrow(&mut self, days: f32) {
+        for population in self.bodies.values_mut().flat_map(|body| body.populations.iter_mut()) {
+            population.regrow(days);
+        }
+    }
+}
+
+/// Water and docks hold fish; docks join the water on either side of them
+fn holds_fish(tile_type: TileType) -> bool {
+    tile_type.is_water() || tile_type == TileType::Dock
+}
+
+/// Share of catches of each rarity before any bonuses
+fn base_chance(rarity: FishRarity) -> f32 {
+    match rarity {
+        FishRarity::Rare => RARE_CHANCE,
+        FishRarity::Uncommon => UNCOMMON_CHANCE,
+        FishRarity::Common => 1.0 - RARE_CHANCE - UNCOMMON_CHANCE,
+    }
+}
+
+/// Index picked with probability proportional to its weight, or `None` if every weight is zero
+fn weighted_index(weights: &[f32], rng: &mut impl Rng) -> Option<usize> {
+    weighted_pick(weights, rng.gen::<f32>())
+}
+
+/// Index picked by `roll` (0 to 1) with probability proportional to its weight
+fn weighted_pick(weights: &[f32], roll: f32) -> Option<usize> {
+    let total: f32 = weights.iter().sum();
+    if total <= 0.0 {
+        return None;
+    }
+    let mut roll = roll * total;
+    let mut chosen = None;
+    for (i, &weight) in weights.iter().enumerate() {
+        if weight <= 0.0 {
+            continue;
+        }
+        chosen = Some(i);
+        if roll < weight {
+            break;
+        }
+        roll -= weight;
+    }
+    chosen
+}
+
+/// Uncles fishing from land (or a dock) reach the water around them
+#[derive(Clone, Debug, PartialEq)]
+pub struct FishingSpot {
+    pub stand: TileType,
+    pub reach: Vec<(i32, i32)>,
+}
+
+impl FishingSpot {
+    /// The spot for an uncle casting `radius` tiles from (x, y), or `None` if they cannot
+    /// stand there or no water is in reach
+    pub fn at(x: i32, y: i32, radius: u32, stand: Option<TileType>, grounds: &FishingGrounds) -> Option<Self> {
+        let stand = stand.filter(|t| t.can_place_uncle())?;
+        let reach = grounds.reachable(x, y, radius);
+        (!reach.is_empty()).then_some(Self { stand, reach })
+    }
+
+    /// Fishing timer speed multiplier, including the bonus for what the uncle stands on
+    pub fn catch_speed(&self, grounds: &FishingGrounds, rare_bonus: f32, is_day: bool) -> f32 {
+        grounds.bite_rate(&self.reach, rare_bonus, is_day) * self.stand.catch_speed()
+    }
+}
+
+// Mixed into the seed so density and species pools do not follow the terrain noise
+const DENSITY_SEED: u64 = 0x4445_4E53_4954_0003;
+const POOL_SEED: u64 = 0x504F_4F4C_5300_0004;
+
+fn water_tile(
+    seed: u64,
+    config: &WorldGenConfig,
+    catalogue: &SpeciesCatalogue,
+    x: i32,
+    y: i32,
+    tile_type: TileType,
+    body: BodyId,
+) -> WaterTile {
+    let depth = ((config.water_level - config.elevation(seed, x, y)) / config.water_level).clamp(0.0, 1.0);
+    let noise = fbm(seed ^ DENSITY_SEED, x as f32 / config.noise_scale, y as f32 / config.noise_scale, 2, 0.5, 2.0);
+    let density = (0.5 + noise) * tile_type.density();
+
+    WaterTile {
+        tile_type,
+        depth,
+        density,
+        body,
+        pool: species_pool(seed, catalogue, x, y, tile_type),
+    }
+}
+
+/// Species living in a tile: up to three common, two uncommon and one rare species of those
+/// living in its kind of water, picked by rarity weight. Pools are shared by each
+/// `SPECIES_REGION_SIZE` block of the same kind of water, so neighbouring tiles hold the same fish.
+fn species_pool(seed: u64, catalogue: &SpeciesCatalogue, x: i32, y: i32, tile_type: TileType) -> Vec<FishSpecies> {
+    let region_x = x.div_euclid(SPECIES_REGION_SIZE as i32);
+    let region_y = y.div_euclid(SPECIES_REGION_SIZE as i32);
+    let habitat = TileType::ALL.iter().position(|&t| t == tile_type).unwrap_or(0) as u64;
+
+    let mut pool = Vec::new();
+    let mut draw = 0u64;
+    for (rarity, count) in [(FishRarity::Common, 3), (FishRarity::Uncommon, 2), (FishRarity::Rare, 1)] {
+        let mut candidates: Vec<(usize, &SpeciesDef)> = catalogue
+            .iter()
+            .enumerate()
+            .filter(|(_, def)| def.rarity == rarity && def.lives_in(tile_type))
+            .collect();
+        for _ in 0..count {
+            let slot_seed = seed ^ POOL_SEED ^ habitat.wrapping_mul(0x9E37_79B9_7F4A_7C15) ^ draw.wrapping_mul(0xC2B2_AE3D_27D4_EB4F);
+            draw += 1;
+            let weights: Vec<f32> = candidates.iter().map(|(_, def)| def.rarity_weight).collect();
+            let Some(pick) = weighted_pick(&weights, hash01(slot_seed, region_x, region_y)) else {
+                break;
+            };
+            let (index, def) = candidates.remove(pick);
+            pool.push(FishSpecies::new(index, def));
+        }
+    }
+    pool
+}
@@ -0,0 +1,249 @@
+//! The tile grid: every tile's type and entity by grid position.
+//!
+//! The world is unbounded and split into `CHUNK_SIZE` × `CHUNK_SIZE` chunks. Each chunk is
+//! generated from the seed and its coordinates alone, the first time anything needs it (the
+//! camera coming near, or an action touching it), and then kept in `TileGrid` along with the
+//! tiles the player has changed. Tile sprites are only spawned for chunks near the camera;
+//! their entities are recorded here while they exist.
+//!
+//! `TileGrid` is the one place systems look tiles up, so lookups never scan tile entities.
+//! It also converts between grid and world coordinates for hit-testing.
+
+use bevy::prelude::*;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::components::TileType;
+use crate::constants::{CHUNK_SIZE, TILE_SIZE};
+use crate::worldgen::WorldGenConfig;
+
+/// Offsets of the four edge-sharing neighbours
+const EDGE_NEIGHBOURS: [(i32, i32); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+/// Chunk coordinates: chunk (cx, cy) holds tiles `cx * CHUNK_SIZE ..` by `cy * CHUNK_SIZE ..`
+pub type ChunkCoord = (i32, i32);
+
+/// The chunk holding tile (x, y)
+pub fn chunk_of(x: i32, y: i32) -> ChunkCoord {
+    (x.div_euclid(CHUNK_SIZE as i32), y.div_euclid(CHUNK_SIZE as i32))
+}
+
+/// Every chunk overlapping the tile rectangle `min..=max`, row by row
+pub fn chunks_between(min: (i32, i32), max: (i32, i32)) -> Vec<ChunkCoord> {
+    let (c0, c1) = (chunk_of(min.0, min.1), chunk_of(max.0, max.1));
+    (c0.1..=c1.1).flat_map(|cy| (c0.0..=c1.0).map(move |cx| (cx, cy))).collect()
+}
+
+/// Tiles of one chunk in row-major order
+#[derive(Clone, Debug)]
+struct Chunk {
+    tiles: Vec<TileType>,
+    entities: Option<Vec<Entity>>,  // Tile sprites, while the chunk is on screen
+}
+
+/// Generated chunks, and every tile the player has changed
+#[derive(Resource, Clone, Default, Debug)]
+pub struct TileGrid {
+    chunks: HashMap<ChunkCoord, Chunk>,
+    edits: HashMap<(i32, i32), TileType>,  // Docks and cleared forest; survive regenerating a chunk
+}
+
+impl TileGrid {
+    /// A grid whose chunks covering `width` × `height` tiles from the origin are all `tile_type`
+    pub fn filled(width: usize, height: usize, tile_type: TileType) -> Self {
+        let mut grid = Self::default();
+        for chunk in chunks_between((0, 0), (width as i32 - 1, height as i32 - 1)) {
+            grid.insert_chunk(chunk, vec![tile_type; CHUNK_SIZE * CHUNK_SIZE]);
+        }
+        grid
+    }
+
+    /// Adds a chunk of `tiles` (row-major, `CHUNK_SIZE` square), applying any edits inside it
+    pub fn insert_chunk(&mut self, chunk: ChunkCoord, mut tiles: Vec<TileType>) {
+        assert_eq!(tiles.len(), CHUNK_SIZE * CHUNK_SIZE, "chunks are {0}x{0} tiles", CHUNK_SIZE);
+        let (x0, y0) = chunk_origin(chunk);
+        for (&(x, y), &tile_type) in &self.edits {
+            if chunk_of(x, y) == chunk {
+                tiles[(y - y0) as usize * CHUNK_SIZE + (x - x0) as usize] = tile_type;
+            }
+        }
+        self.chunks.insert(chunk, Chunk { tiles, entities: None });
+    }
+
+    /// Generates `chunk` from the seed if it has not been yet. Returns whether it was new.
+    pub fn generate_chunk(&mut self, seed: u64, config: &WorldGenConfig, chunk: ChunkCoord) -> bool {
+        if self.chunks.contains_key(&chunk) {
+            return false;
+        }
+        let (x0, y0) = chunk_origin(chunk);
+        self.insert_chunk(chunk, config.generate_region(seed, x0, y0, CHUNK_SIZE, CHUNK_SIZE));
+        true
+    }
+
+    pub fn has_chunk(&self, chunk: ChunkCoord) -> bool {
+        self.chunks.contains_key(&chunk)
+    }
+
+    /// Generated chunks, row by row
+    pub fn chunks(&self) -> Vec<ChunkCoord> {
+        let mut chunks: Vec<ChunkCoord> = self.chunks.keys().copied().collect();
+        chunks.sort_by_key(|&(cx, cy)| (cy, cx));
+        chunks
+    }
+
+    fn locate(&self, x: i32, y: i32) -> Option<(&Chunk, usize)> {
+        let chunk = self.chunks.get(&chunk_of(x, y))?;
+        Some((chunk, local_index(x, y)))
+    }
+
+    /// The tile at (x, y), or `None` if its chunk has not been generated
+    pub fn get(&self, x: i32, y: i32) -> Option<TileType> {
+        self.locate(x, y).map(|(chunk, i)| chunk.tiles[i])
+    }
+
+    /// The sprite entity of a tile, while its chunk is spawned
+    pub fn entity(&self, x: i32, y: i32) -> Option<Entity> {
+        self.locate(x, y).and_then(|(chunk, i)| Some(chunk.entities.as_ref()?[i]))
+    }
+
+    /// Changes a generated tile and remembers the change. The caller updates the tile entity to match.
+    pub fn set(&mut self, x: i32, y: i32, tile_type: TileType) {
+        let Some(chunk) = self.chunks.get_mut(&chunk_of(x, y)) else {
+            return;
+        };
+        chunk.tiles[local_index(x, y)] = tile_type;
+        self.edits.insert((x, y), tile_type);
+    }
+
+    /// Tiles the player has changed, row by row
+    pub fn edits(&self) -> Vec<(i32, i32, TileType)> {
+        let mut edits: Vec<_> = self.edits.iter().map(|(&(x, y), &t)| (x, y, t)).collect();
+        edits.sort_by_key(|&(x, y, _)| (y, x));
+        edits
+    }
+
+    /// Records an edit without generating its chunk; applied when the chunk is generated
+    pub fn add_edit(&mut self, x: i32, y: i32, tile_type: TileType) {
+        self.edits.insert((x, y), tile_type);
+        if let Some(chunk) = self.chunks.get_mut(&chunk_of(x, y)) {
+            chunk.tiles[local_index(x, y)] = tile_type;
+        }
+    }
+
+    /// Whether a chunk's tile sprites are spawned
+    pub fn is_spawned(&self, chunk: ChunkCoord) -> bool {
+        self.chunks.get(&chunk).is_some_and(|c| c.entities.is_some())
+    }
+
+    /// Records the sprite entities of a chunk, in the order of `chunk_tiles`
+    pub fn set_entities(&mut self, chunk: ChunkCoord, entities: Vec<Entity>) {
+        if let Some(chunk) = self.chunks.get_mut(&chunk) {
+            chunk.entities = Some(entities);
+        }
+    }
+
+    /// Forgets a chunk's sprite entities, returning them for despawning
+    pub fn take_entities(&mut self, chunk: ChunkCoord) -> Vec<Entity> {
+        self.chunks.get_mut(&chunk).and_then(|c| c.entities.take()).unwrap_or_default()
+    }
+
+    /// Chunks with spawned sprites
+    pub fn spawned_chunks(&self) -> Vec<ChunkCoord> {
+        self.chunks().into_iter().filter(|&c| self.is_spawned(c)).collect()
+    }
+
+    /// Every tile of one chunk with its position, row by row
+    pub fn chunk_tiles(&self, chunk: ChunkCoord) -> Vec<(i32, i32, TileType)> {
+        let Some(tiles) = self.chunks.get(&chunk).map(|c| &c.tiles) else {
+            return Vec::new();
+        };
+        let (x0, y0) = chunk_origin(chunk);
+        tiles
+            .iter()
+            .enumerate()
+            .map(|(i, &t)| (x0 + (i % CHUNK_SIZE) as i32, y0 + (i / CHUNK_SIZE) as i32, t))
+            .collect()
+    }
+
+    /// Every generated tile with its position, chunk by chunk
+    pub fn iter(&self) -> impl Iterator<Item = (i32, i32, TileType)> + '_ {
+        self.chunks().into_iter().flat_map(|chunk| self.chunk_tiles(chunk))
+    }
+
+    /// The up to four generated tiles sharing an edge with (x, y)
+    pub fn edge_neighbours(&self, x: i32, y: i32) -> impl Iterator<Item = (i32, i32)> + '_ {
+        EDGE_NEIGHBOURS
+            .iter()
+            .map(move |&(dx, dy)| (x + dx, y + dy))
+            .filter(|&(nx, ny)| self.get(nx, ny).is_some())
+    }
+
+    /// Every generated tile within `radius` of (x, y), diagonals included, except (x, y) itself; row by row
+    pub fn within(&self, x: i32, y: i32, radius: u32) -> impl Iterator<Item = (i32, i32)> + '_ {
+        let r = radius as i32;
+        (-r..=r)
+            .flat_map(move |dy| (-r..=r).map(move |dx| (dx, dy)))
+            .filter(|&offset| offset != (0, 0))
+            .map(move |(dx, dy)| (x + dx, y + dy))
+            .filter(|&(nx, ny)| self.get(nx, ny).is_some())
+    }
+
+    /// Every tile edge-connected to `start` through generated tiles matching `inside`, in the
+    /// order reached. Empty if `start` itself does not match.
+    pub fn flood_fill(&self, start: (i32, i32), inside: impl Fn(i32, i32, TileType) -> bool) -> Vec<(i32, i32)> {
+        let matches = |(x, y): (i32, i32)| self.get(x, y).is_some_and(|t| inside(x, y, t));
+        if !matches(start) {
+            return Vec::new();
+        }
+        let mut filled = vec![start];
+        let mut seen = HashSet::from([start]);
+        let mut queue = VecDeque::from([start]);
+        while let Some((x, y)) = queue.pop_front() {
+            for next in EDGE_NEIGHBOURS.iter().map(|&(dx, dy)| (x + dx, y + dy)) {
+                if matches(next) && seen.insert(next) {
+                    filled.push(next);
+                    queue.push_back(next);
+                }
+            }
+        }
+        filled
+    }
+
+    /// Distance in tiles (diagonals count as one, like casting radii) from (x, y) to the
+    /// nearest generated water tile, or `None` if there is none within `max`
+    pub fn distance_to_water(&self, x: i32, y: i32, max: u32) -> Option<u32> {
+        let is_water = |(x, y): (i32, i32)| self.get(x, y).is_some_and(|t| t.is_water());
+        if is_water((x, y)) {
+            return Some(0);
+        }
+        (1..=max).find(|&r| {
+            let r = r as i32;
+            // Only the ring at distance r; closer rings were checked already
+            (-r..=r)
+                .flat_map(|dy| (-r..=r).map(move |dx| (dx, dy)))
+                .filter(|&(dx, dy)| dx.abs() == r || dy.abs() == r)
+                .any(|(dx, dy)| is_water((x + dx, y + dy)))
+        })
+    }
+
+    /// World-space center of a tile; tile (0, 0) is centered on the origin
+    pub fn tile_to_world(x: i32, y: i32) -> Vec2 {
+        Vec2::new(x as f32 * TILE_SIZE, y as f32 * TILE_SIZE)
+    }
+
+    /// The tile under a world-space point
+    pub fn world_to_tile(world: Vec2) -> (i32, i32) {
+        let tile = (world / TILE_SIZE).round();
+        (tile.x as i32, tile.y as i32)
+    }
+}
+
+/// The first tile of a chunk
+pub fn chunk_origin((cx, cy): ChunkCoord) -> (i32, i32) {
+    (cx * CHUNK_SIZE as i32, cy * CHUNK_SIZE as i32)
+}
+
+/// Index of (x, y) inside its chunk's tiles
+fn local_index(x: i32, y: i32) -> usize {
+    let size = CHUNK_SIZE as i32;
+    (y.rem_euclid(size) * size + x.rem_euclid(size)) as usize
+}
@@ -0,0 +1,93 @@
+// Bevy system signatures routinely trip these two lints
+#![allow(clippy::type_complexity, clippy::too_many_arguments)]
+
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+
+pub mod actions;
+pub mod components;
+pub mod constants;
+pub mod events;
+pub mod fish_sprite;
+pub mod fishdex;
+pub mod fishing;
+pub mod grid;
+pub mod plugins;
+pub mod replay;
+pub mod resources;
+pub mod save;
+pub mod seed;
+pub mod species;
+pub mod states;
+pub mod systems;
+pub mod uncles;
+pub mod worldgen;
+
+use states::AppState;
+
+pub use plugins::{
+    DayNightPlugin, EconomyPlugin, EventLogPlugin, FishingPlugin, ReplayPlugin, RestoreWorldSet, SavePlugin, TickSet, UiPlugin,
+    WorldGenPlugin,
+};
+
+/// The whole game as one plugin.
+/// Turn off `ui` to run the simulation without a window (e.g. under `MinimalPlugins`),
+/// `save` to keep a run from touching the save file, `record` to skip writing a replay,
+/// and `log` to stop printing gameplay events to stdout.
+/// With the UI the app opens on the main menu; without it there is no menu, so it starts in
+/// `AppState::Playing`.
+pub struct RarephishPlugin {
+    pub ui: bool,
+    pub save: bool,
+    pub record: bool,
+    pub log: bool,
+}
+
+impl Default for RarephishPlugin {
+    fn default() -> Self {
+        Self { ui: true, save: true, record: true, log: true }
+    }
+}
+
+impl RarephishPlugin {
+    /// Simulation only: no rendering, input, save file, replay recording or stdout log
+    pub fn headless() -> Self {
+        Self { ui: false, save: false, record: false, log: false }
+    }
+}
+
+impl Plugin for RarephishPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<StatesPlugin>() {
+            app.add_plugins(StatesPlugin);
+        }
+        app.insert_state(if self.ui { AppState::MainMenu } else { AppState::Playing });
+
+        app.insert_resource(Time::<Fixed>::from_hz(constants::SIMULATION_TICK_HZ))
+            .init_resource::<actions::PendingActions>()
+            .init_resource::<resources::SimulationTick>()
+            .add_event::<events::NewWorldRequested>()
+            // The simulation only advances while playing; the menus and pause screen freeze it
+            .configure_sets(FixedUpdate, (TickSet::ApplyActions, TickSet::Simulate).chain().run_if(in_state(AppState::Playing)))
+            .add_systems(FixedUpdate, systems::gameplay::apply_player_actions.in_set(TickSet::ApplyActions))
+            .add_systems(FixedUpdate, systems::replay::count_tick
+                .after(TickSet::Simulate)
+                .run_if(in_state(AppState::Playing)))
+            .add_plugins((WorldGenPlugin, FishingPlugin, EconomyPlugin, DayNightPlugin))
+            // Before any chunks are streamed into the old world's grid
+            .add_systems(Update, systems::tilemap::start_new_world.before(systems::tilemap::stream_chunks));
+
+        if self.save {
+            app.add_plugins(SavePlugin);
+        }
+        if self.record {
+            app.add_plugins(ReplayPlugin::Record);
+        }
+        if self.log {
+            app.add_plugins(EventLogPlugin);
+        }
+        if self.ui {
+            app.add_plugins(UiPlugin);
+        }
+    }
+}
@@ -1,55 +1,80 @@
 use bevy::prelude::*;
+use std::path::Path;
+use rarephish_bevy::replay::ReplayFile;
+use rarephish_bevy::resources::{SaveSlot, WorldSeed};
+use rarephish_bevy::seed::parse_seed;
+use rarephish_bevy::species::{SpeciesCatalogue, SPECIES_CATALOGUE_PATH};
+use rarephish_bevy::states::AppState;
+use rarephish_bevy::uncles::{UncleRoster, UNCLE_ROSTER_PATH};
+use rarephish_bevy::{RarephishPlugin, ReplayPlugin};
 
-mod components;
-mod constants;
-mod resources;
-mod systems;
-
-use resources::*;
-use systems::*;
+const USAGE: &str = "usage: rarephish-bevy [--replay PATH] [--seed CODE|NUMBER|WORDS]";
 
 fn main() {
-    App::new()
-        .add_plugins(DefaultPlugins.set(WindowPlugin {
-            primary_window: Some(Window {
-                title: "🎣 Rare Fish Game".into(),
-                resolution: (1400.0, 900.0).into(),
-                ..default()
-            }),
+    let mut replay = None;
+    let mut seed = None;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--replay" => {
+                let Some(path) = args.next() else {
+                    eprintln!("{}", USAGE);
+                    std::process::exit(2);
+                };
+                match ReplayFile::read(path.as_ref()) {
+                    Ok(file) => replay = Some(file),
+                    Err(err) => {
+                        eprintln!("could not read replay {}: {}", path, err);
+                        std::process::exit(1);
+                    }
+                }
+            }
+            "--seed" => match args.next().as_deref().and_then(parse_seed) {
+                Some(parsed) => seed = Some(parsed),
+                None => {
+                    eprintln!("{}", USAGE);
+                    std::process::exit(2);
+                }
+            },
+            _ => {
+                eprintln!("{}", USAGE);
+                std::process::exit(2);
+            }
+        }
+    }
+
+    let mut app = App::new();
+    app.add_plugins(DefaultPlugins.set(WindowPlugin {
+        primary_window: Some(Window {
+            title: "🎣 Rare Fish Game".into(),
+            resolution: (1400.0, 900.0).into(),
             ..default()
-        }))
-        // Resources
-        .init_resource::<GameState>()
-        .init_resource::<WorldSeed>()
-        .init_resource::<SelectedUncle>()
-        .init_resource::<DayNightCycle>()  // NEW: Day/Night cycle
-        // Startup systems
-        .add_systems(Startup, (setup::setup_camera, setup::setup_ui))
-        .add_systems(Startup, tilemap::generate_tilemap)
-        // Update systems - Gameplay
-        .add_systems(Update, (
-            gameplay::handle_uncle_placement,  // Handles both placement AND selection
-            gameplay::uncle_fishing_system,
-            gameplay::fish_escape_system,
-            gameplay::remove_fish_from_basket,  // NEW: R key to remove fish
-            gameplay::cash_out_selected_uncle,
-            gameplay::cash_out_all_uncles,
-            gameplay::cooldown_update_system,
-            day_night::day_night_cycle_system,   // NEW: Time progression
-        ))
-        // Update systems - UI
-        .add_systems(Update, (
-            ui::update_ui_system,
-            ui::update_basket_display,
-            ui::update_basket_value_display,
-            ui::handle_uncle_selection,
-            ui::handle_cash_out_button,
-            ui::handle_cash_out_all_button,
-            ui::cash_out_button_visual,
-            ui::cash_out_all_button_visual,
-            ui::uncle_button_visual,
-            ui::handle_new_world,
-            day_night::update_day_night_ui,     // NEW: Day/Night UI updates
-        ))
-        .run();
+        }),
+        ..default()
+    }));
+
+    // Uncles and fish come from the data files next to the game, so they can be modded without rebuilding
+    app.insert_resource(UncleRoster::read_or_default(Path::new(UNCLE_ROSTER_PATH)))
+        .insert_resource(SpeciesCatalogue::read_or_default(Path::new(SPECIES_CATALOGUE_PATH)));
+
+    // A chosen seed starts a fresh world instead of resuming the save
+    if let Some(seed) = seed {
+        app.insert_resource(WorldSeed::from_seed(seed))
+            .insert_resource(SaveSlot { resume: false, ..default() });
+    }
+
+    let skip_menu = replay.is_some() || seed.is_some();
+    match replay {
+        // Watching a replay must not overwrite the player's save or recording
+        Some(replay) => app
+            .add_plugins(RarephishPlugin { ui: true, save: false, record: false, log: true })
+            .add_plugins(ReplayPlugin::Play(Box::new(replay))),
+        None => app.add_plugins(RarephishPlugin::default()),
+    };
+    // Replays and chosen seeds start playing without the main menu
+    if skip_menu {
+        app.insert_state(AppState::Playing);
+    }
+
+    app.run();
 }
@@ -0,0 +1,264 @@
+use bevy::prelude::*;
+
+use crate::components::{MainMenuScreen, PauseScreen, RunSummaryScreen};
+use crate::events::*;
+use crate::fish_sprite::FishSprites;
+use crate::fishdex::Fishdex;
+use crate::fishing::FishingGrounds;
+use crate::grid::TileGrid;
+use crate::replay::ReplayFile;
+use crate::species::SpeciesCatalogue;
+use crate::uncles::UncleRoster;
+use crate::worldgen::WorldGenConfig;
+use crate::resources::*;
+use crate::states::AppState;
+use crate::systems::*;
+use crate::systems::replay::{ReplayPlayer, ReplayRecorder};
+
+/// Ordering inside each fixed tick: queued player actions first, then the simulation
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TickSet {
+    ApplyActions,
+    Simulate,
+}
+
+/// Startup systems that rebuild an existing world (save or replay); generation runs after them
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RestoreWorldSet;
+
+/// Seeded world: the `WorldSeed` resource, generator settings, tilemap generation and the `TileGrid`
+pub struct WorldGenPlugin;
+
+impl Plugin for WorldGenPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WorldSeed>()
+            .init_resource::<WorldGenConfig>()
+            .init_resource::<TileGrid>()
+            .add_systems(Startup, tilemap::generate_tilemap
+                .after(RestoreWorldSet)
+                .run_if(not(resource_exists::<RestoredWorld>)));
+    }
+}
+
+/// The uncle roster and species catalogue, uncle fishing timers, the fish populations they draw
+/// from, and fish escapes. Uses the built-in `UncleRoster` and `SpeciesCatalogue` unless others
+/// were inserted before this plugin.
+pub struct FishingPlugin;
+
+impl Plugin for FishingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<UncleRoster>()
+            .init_resource::<SpeciesCatalogue>()
+            .init_resource::<SelectedUncle>()
+            .init_resource::<FishingStats>()
+            .init_resource::<SpeciesRecords>()
+            .init_resource::<Fishdex>()
+            .init_resource::<FishingGrounds>()
+            .add_event::<FishCaught>()
+            .add_event::<RecordBroken>()
+            .add_event::<FishDiscovered>()
+            .add_event::<FishEscaped>()
+            .add_event::<FishDiscarded>()
+            .add_event::<UnclePlaced>()
+            .add_event::<DockBuilt>()
+            // Chained: fishing and escapes change the same baskets, so their order must never vary
+            .add_systems(FixedUpdate, (
+                gameplay::regrow_fishing_grounds,
+                gameplay::uncle_fishing_system,
+                gameplay::fish_escape_system,
+                gameplay::record_fishing_stats,
+                gameplay::track_species_records,
+                gameplay::record_fishdex,
+            ).chain().in_set(TickSet::Simulate))
+            .add_systems(FixedUpdate, offline::send_offline_events
+                .run_if(resource_exists::<PendingOfflineEvents>)
+                .in_set(TickSet::ApplyActions));
+    }
+}
+
+/// Gold, multiplier, the cash-out cooldown, and the one system that sells baskets
+pub struct EconomyPlugin;
+
+impl Plugin for EconomyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameState>()
+            .add_event::<CashOutRequest>()
+            .add_event::<CashedOut>()
+            .add_event::<CashOutDenied>()
+            .add_systems(FixedUpdate, economy::handle_cash_out_requests
+                .in_set(TickSet::ApplyActions)
+                .after(gameplay::apply_player_actions))
+            .add_systems(FixedUpdate, economy::cooldown_update_system.in_set(TickSet::Simulate));
+    }
+}
+
+/// Day/night clock and the daily cash-out allowance
+pub struct DayNightPlugin;
+
+impl Plugin for DayNightPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DayNightCycle>()
+            .add_event::<DayStarted>()
+            .add_event::<DaylightChanged>()
+            .add_systems(FixedUpdate, day_night::day_night_cycle_system.in_set(TickSet::Simulate));
+    }
+}
+
+/// Prints gameplay events (new days, dawn/dusk, cash-outs, discarded fish, docks) to stdout
+pub struct EventLogPlugin;
+
+impl Plugin for EventLogPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, logging::log_gameplay_events);
+    }
+}
+
+/// Resume on startup, autosave, and save on exit. The Fishdex is loaded and saved with the run.
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SaveSlot>()
+            .add_systems(Startup, (persistence::load_game, persistence::load_fishdex).in_set(RestoreWorldSet))
+            // An ended run is not saved again, so it cannot be resumed
+            .add_systems(OnEnter(AppState::RunSummary), persistence::discard_ended_run)
+            .add_systems(Update, persistence::autosave_system.run_if(not(in_state(AppState::RunSummary))))
+            .add_systems(Last, persistence::save_on_exit.run_if(not(in_state(AppState::RunSummary))));
+    }
+}
+
+/// Records every applied action to a replay file, or plays one back
+pub enum ReplayPlugin {
+    Record,
+    Play(Box<ReplayFile>),
+}
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        match self {
+            ReplayPlugin::Record => {
+                app.init_resource::<ReplayRecorder>()
+                    .add_systems(FixedUpdate, (
+                        replay::begin_recording,
+                        replay::record_actions,
+                    ).chain().in_set(TickSet::ApplyActions).before(gameplay::apply_player_actions))
+                    .add_systems(Update, replay::save_replay_hotkey)
+                    .add_systems(Last, replay::save_replay_on_exit);
+            }
+            ReplayPlugin::Play(replay) => {
+                app.insert_resource(ReplayPlayer {
+                    replay: (**replay).clone(),
+                    next_action: 0,
+                    finished: false,
+                })
+                .add_systems(Startup, replay::start_replay.in_set(RestoreWorldSet))
+                .add_systems(FixedUpdate, replay::feed_replay_actions
+                    .in_set(TickSet::ApplyActions)
+                    .before(gameplay::apply_player_actions))
+                .add_systems(FixedUpdate, replay::check_replay_finished.after(replay::count_tick));
+            }
+        }
+    }
+}
+
+/// Camera (panning, zooming and the chunks streamed in around it), HUD, the main menu, pause
+/// and run summary screens, the Fishdex panel, catch animations, and all player input (mouse placement, keyboard and button cash-outs).
+/// Input systems only queue `PlayerAction`s; they are applied on the next fixed tick.
+/// Leave this out to run the simulation headless.
+pub struct UiPlugin;
+
+impl Plugin for UiPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<NotificationLog>()
+            .init_resource::<FishOverlay>()
+            .init_resource::<SeedEntry>()
+            .init_resource::<BasketSort>()
+            .init_resource::<FishSprites>()
+            .add_systems(Startup, (setup::setup_camera, setup::setup_ui, setup::setup_toasts))
+            // Player input, only while playing
+            .add_systems(Update, (
+                gameplay::handle_uncle_placement  // Handles both placement AND selection
+                    .run_if(not(resource_exists::<OfflineReport>))
+                    .run_if(not(resource_exists::<NewWorldDialog>))
+                    .run_if(not(resource_exists::<FishdexPanel>)),
+                (
+                    gameplay::handle_gameplay_keys,  // R / Space / C
+                    overlay::toggle_fish_overlay,    // O
+                ).run_if(ui::seed_entry_closed),
+            ).run_if(in_state(AppState::Playing)))
+            // Seed code and the New World dialog
+            .add_systems(Update, (
+                ui::copy_seed_code,
+                (
+                    ui::open_new_world_dialog,
+                    ui::show_new_world_dialog.run_if(resource_added::<NewWorldDialog>),
+                    ui::type_seed_input,
+                    ui::update_seed_input_text,
+                    ui::handle_new_world.run_if(resource_exists::<NewWorldDialog>),
+                ).chain(),
+            ))
+            // Camera and the chunks around it
+            .add_systems(Update, (
+                (
+                    camera::pan_camera.run_if(ui::seed_entry_closed),  // WASD / arrows / screen edge
+                    camera::drag_camera,                               // Right or middle mouse
+                    camera::zoom_camera,                               // Mouse wheel
+                ).run_if(in_state(AppState::Playing)),
+                tilemap::stream_chunks,
+            ).chain())
+            // Main menu, pause (Esc) and run summary screens
+            .add_systems(OnEnter(AppState::MainMenu), menus::spawn_main_menu)
+            .add_systems(OnExit(AppState::MainMenu), menus::despawn_screen::<MainMenuScreen>)
+            .add_systems(OnEnter(AppState::Paused), menus::spawn_pause_menu)
+            .add_systems(OnExit(AppState::Paused), menus::despawn_screen::<PauseScreen>)
+            .add_systems(OnEnter(AppState::RunSummary), menus::spawn_run_summary)
+            .add_systems(OnExit(AppState::RunSummary), menus::despawn_screen::<RunSummaryScreen>)
+            .add_systems(Update, (
+                // Before the dialog handler, so the Esc that closes the dialog does not also pause
+                menus::toggle_pause
+                    .run_if(ui::seed_entry_closed)
+                    .before(ui::handle_new_world),
+                menus::handle_menu_buttons,
+                menus::update_main_menu_run_text.run_if(in_state(AppState::MainMenu)),
+            ))
+            // Fishdex panel (F, or the FISHDEX menu button)
+            .add_systems(Update, (
+                fishdex::toggle_fishdex.run_if(ui::seed_entry_closed),
+                fishdex::handle_fishdex_close,
+                fishdex::update_fishdex_panel,
+                fishdex::scroll_fishdex,
+            ).chain())
+            // HUD
+            .add_systems(Update, (
+                ui::update_ui_system,
+                ui::update_basket_display,
+                ui::cycle_basket_sort,
+                ui::update_basket_value_display,
+                ui::handle_uncle_selection,
+                ui::handle_cash_out_button,
+                ui::handle_cash_out_all_button,
+                ui::cash_out_button_visual,
+                ui::cash_out_all_button_visual,
+                ui::uncle_button_visual,
+                day_night::update_day_night_ui,
+                ui::show_offline_report.run_if(resource_added::<OfflineReport>),
+                ui::dismiss_offline_report,
+            ))
+            // Toasts and event log
+            .add_systems(Update, (
+                (
+                    notifications::collect_notifications,
+                    notifications::show_notifications,
+                    notifications::fade_toasts,
+                ).chain(),
+                notifications::scroll_notification_history.run_if(not(resource_exists::<FishdexPanel>)),
+            ))
+            // Caught fish leaping over their uncles
+            .add_systems(Update, (
+                catch_animation::spawn_catch_animations,
+                catch_animation::animate_catches,
+            ).chain().run_if(in_state(AppState::Playing)))
+            // Fish population overlay
+            .add_systems(Update, overlay::update_fish_overlay.after(overlay::toggle_fish_overlay));
+    }
+}
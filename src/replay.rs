@@ -0,0 +1,86 @@
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::actions::PlayerAction;
+use crate::constants::SIMULATION_TICK_HZ;
+use crate::plugins::ReplayPlugin;
+use crate::resources::{GameState, SimulationTick};
+use crate::save::{SaveError, SaveFile};
+use crate::RarephishPlugin;
+
+/// Current replay-file version. Also bumped when simulation rules change, since older
+/// recordings would no longer reproduce their final state.
+pub const REPLAY_VERSION: u32 = 9;
+
+/// A player action and the fixed tick it was applied on
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RecordedAction {
+    pub tick: u64,
+    pub action: PlayerAction,
+}
+
+/// A recorded session: the world as it was on the first tick, every action
+/// with its tick, and the `GameState` the recording ended with.
+/// Feeding the actions back from `start` must reproduce `final_state` exactly.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ReplayFile {
+    pub version: u32,
+    pub start: SaveFile,
+    pub actions: Vec<RecordedAction>,
+    pub end_tick: u64,
+    pub final_state: GameState,
+}
+
+impl ReplayFile {
+    pub fn from_json(text: &str) -> Result<Self, SaveError> {
+        let replay: ReplayFile = serde_json::from_str(text)?;
+        if replay.version != REPLAY_VERSION {
+            return Err(SaveError::UnsupportedVersion(replay.version));
+        }
+        Ok(replay)
+    }
+
+    pub fn read(path: &Path) -> Result<Self, SaveError> {
+        let text = std::fs::read_to_string(path)?;
+        Self::from_json(&text)
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), SaveError> {
+        let tmp_path = path.with_extension("tmp");
+        std::fs::write(&tmp_path, serde_json::to_string(self)?)?;
+        std::fs::rename(&tmp_path, path)?;
+        Ok(())
+    }
+}
+
+/// Builds a windowless app that advances exactly one fixed tick per `update()`
+pub fn headless_app() -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(
+            std::time::Duration::from_secs_f64(1.0 / SIMULATION_TICK_HZ),
+        ))
+        .add_plugins(RarephishPlugin::headless());
+    app
+}
+
+/// Advances a headless app until `tick` fixed ticks have run
+pub fn run_until_tick(app: &mut App, tick: u64) {
+    while app.world().resource::<SimulationTick>().0 < tick {
+        app.update();
+    }
+}
+
+/// Plays a replay back without a window and returns the resulting `GameState`
+pub fn play_headless(replay: ReplayFile) -> GameState {
+    let end_tick = replay.end_tick;
+    let mut app = headless_app();
+    app.add_plugins(ReplayPlugin::Play(Box::new(replay)));
+    app.finish();
+    app.cleanup();
+
+    run_until_tick(&mut app, end_tick);
+    app.world().resource::<GameState>().clone()
+}
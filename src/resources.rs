@@ -1,11 +1,18 @@
 use bevy::prelude::*;
 use rand::SeedableRng;
 use rand_chacha::ChaCha8Rng;
-use crate::components::UncleType;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::path::PathBuf;
+use crate::components::{Fish, FishRarity};
 use crate::constants::*;
+use crate::events::{FishCaught, FishEscaped};
+use crate::seed::{random_seed, seed_code};
+use crate::species::SpeciesId;
+use crate::uncles::{UncleRoster, UncleType};
 
 /// Global game state resource
-#[derive(Resource)]
+#[derive(Resource, Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub struct GameState {
     pub fish_count: u32,
     pub gold: u32,
@@ -24,8 +31,17 @@ impl Default for GameState {
     }
 }
 
+impl GameState {
+    /// Counts the cash-out cooldown down, clamping at zero
+    pub fn tick_cooldown(&mut self, delta: f32) {
+        if self.cash_out_cooldown > 0.0 {
+            self.cash_out_cooldown = (self.cash_out_cooldown - delta).max(0.0);
+        }
+    }
+}
+
 /// Day/Night cycle tracker
-#[derive(Resource)]
+#[derive(Resource, Clone, Serialize, Deserialize)]
 pub struct DayNightCycle {
     pub time_elapsed: f32,         // Seconds elapsed in current cycle
     pub day_progress: f32,         // 0.0 to 1.0 (0 = midnight, 0.5 = noon)
@@ -89,6 +105,22 @@ impl DayNightCycle {
         }
     }
 
+    /// Advances the clock by `delta` seconds, returning true if a new day began
+    pub fn advance(&mut self, delta: f32) -> bool {
+        self.time_elapsed += delta;
+
+        // Calculate progress through day (0.0 to 1.0)
+        self.day_progress = (self.time_elapsed / DAY_LENGTH_SECONDS) % 1.0;
+
+        // Check if new day started
+        let new_day = self.time_elapsed >= DAY_LENGTH_SECONDS;
+        if new_day {
+            self.time_elapsed = 0.0;
+            self.new_day();
+        }
+        new_day
+    }
+
     /// Resets cash-outs for new day
     pub fn new_day(&mut self) {
         self.day_number += 1;
@@ -96,33 +128,84 @@ impl DayNightCycle {
     }
 }
 
-/// World seed resource for procedural generation
+/// An independent random stream derived from the world seed
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize)]
+pub enum RngStream {
+    Fishing { x: i32, y: i32 },  // One per uncle, by the tile it fishes from
+    Escapes,                     // Every escape roll of every basket
+    Events,                      // Random world events
+}
+
+impl RngStream {
+    /// The stream's first state: a ChaCha8 key made of the seed and the stream itself,
+    /// so no two streams of any seeds ever overlap
+    fn start(self, seed: u64) -> ChaCha8Rng {
+        let (kind, x, y) = match self {
+            RngStream::Fishing { x, y } => (1, x, y),
+            RngStream::Escapes => (2, 0, 0),
+            RngStream::Events => (3, 0, 0),
+        };
+        let mut key = [0u8; 32];
+        key[..8].copy_from_slice(&seed.to_le_bytes());
+        key[8] = kind;
+        key[12..16].copy_from_slice(&x.to_le_bytes());
+        key[16..20].copy_from_slice(&y.to_le_bytes());
+        ChaCha8Rng::from_seed(key)
+    }
+}
+
+/// World seed resource for procedural generation, and the random streams derived from it.
+/// Map generation draws no random numbers: it hashes the seed with tile coordinates, so the
+/// same seed always gives the same map. Everything else draws from its own `RngStream`, so
+/// extra draws in one (say, more escape rolls) never shift another (the fish an uncle catches).
 #[derive(Resource)]
 pub struct WorldSeed {
     pub seed: u64,
-    pub rng: ChaCha8Rng,
+    streams: BTreeMap<RngStream, ChaCha8Rng>,  // Created on first use
 }
 
 impl Default for WorldSeed {
     fn default() -> Self {
-        let seed = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
+        Self::from_seed(random_seed())
+    }
+}
+
+impl WorldSeed {
+    /// Starts a world from a chosen seed instead of a random one
+    pub fn from_seed(seed: u64) -> Self {
         Self {
             seed,
-            rng: ChaCha8Rng::seed_from_u64(seed),
+            streams: BTreeMap::new(),
         }
     }
-}
 
-impl WorldSeed {
-    pub fn new_seed(&mut self) {
-        self.seed = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
-        self.rng = ChaCha8Rng::seed_from_u64(self.seed);
+    /// Rebuilds the streams from a seed, fast-forwarding each to a saved position
+    pub fn restore(seed: u64, positions: impl IntoIterator<Item = (RngStream, u128)>) -> Self {
+        let mut world_seed = Self::from_seed(seed);
+        for (stream, word_pos) in positions {
+            world_seed.rng(stream).set_word_pos(word_pos);
+        }
+        world_seed
+    }
+
+    /// One random stream, starting it if nothing has drawn from it yet
+    pub fn rng(&mut self, stream: RngStream) -> &mut ChaCha8Rng {
+        let seed = self.seed;
+        self.streams.entry(stream).or_insert_with(|| stream.start(seed))
+    }
+
+    /// How far each stream that has been drawn from has advanced, in stream order
+    pub fn positions(&self) -> Vec<(RngStream, u128)> {
+        self.streams
+            .iter()
+            .map(|(&stream, rng)| (stream, rng.get_word_pos()))
+            .filter(|&(_, word_pos)| word_pos > 0)
+            .collect()
+    }
+
+    /// The seed as a shareable code, e.g. `"0K3F-7QZ2-M9V4A"`
+    pub fn code(&self) -> String {
+        seed_code(self.seed)
     }
 }
 
@@ -132,10 +215,223 @@ pub struct SelectedUncle {
     pub uncle_type: UncleType,
 }
 
-impl Default for SelectedUncle {
+/// Starts on the first uncle of the `UncleRoster`
+impl FromWorld for SelectedUncle {
+    fn from_world(world: &mut World) -> Self {
+        let uncle_type = world.get_resource_or_insert_with(UncleRoster::default).first().id.clone();
+        Self { uncle_type }
+    }
+}
+
+/// Text typed into the New World dialog's seed field, and whether it has keyboard focus
+#[derive(Resource, Default)]
+pub struct SeedEntry {
+    pub text: String,
+    pub focused: bool,
+}
+
+/// Present while the New World confirmation dialog is open. Insert it to open the dialog.
+#[derive(Resource, Default)]
+pub struct NewWorldDialog;
+
+/// Present while the Fishdex panel is open. Insert it to open the panel.
+#[derive(Resource, Default)]
+pub struct FishdexPanel {
+    pub offset: f32,  // How far the entry list is scrolled down
+}
+
+/// Where the run and the Fishdex are saved and when they are next autosaved
+#[derive(Resource)]
+pub struct SaveSlot {
+    pub path: PathBuf,
+    pub fishdex_path: PathBuf,
+    pub autosave_timer: Timer,
+    pub resume: bool,  // Resume the saved run on startup; off when a seed was chosen to play instead
+}
+
+impl Default for SaveSlot {
     fn default() -> Self {
         Self {
-            uncle_type: UncleType::Mongolian,
+            path: PathBuf::from(SAVE_FILE_PATH),
+            fishdex_path: PathBuf::from(FISHDEX_FILE_PATH),
+            autosave_timer: Timer::from_seconds(AUTOSAVE_INTERVAL_SECONDS, TimerMode::Repeating),
+            resume: true,
         }
     }
 }
+
+/// Marker inserted when Startup rebuilt the world from a save or replay instead of generating one
+#[derive(Resource)]
+pub struct RestoredWorld;
+
+/// "While you were away" results of the offline catch-up, shown once after loading
+#[derive(Resource, Default)]
+pub struct OfflineReport {
+    pub seconds_away: f32,
+    pub caught: Vec<OfflineFish>,
+    pub escaped: Vec<OfflineFish>,
+    pub days_passed: u32,
+}
+
+/// A fish caught or lost while the game was closed
+#[derive(Clone, Debug)]
+pub struct OfflineFish {
+    pub uncle: usize,  // Index of its uncle in the uncles that were simulated
+    pub fish: Fish,
+}
+
+/// `FishCaught` and `FishEscaped` events of the offline catch-up, held back until the first
+/// simulated tick so the systems that only run while playing see them too
+#[derive(Resource, Default)]
+pub struct PendingOfflineEvents {
+    pub caught: Vec<FishCaught>,
+    pub escaped: Vec<FishEscaped>,
+}
+
+/// Whether water tiles are tinted by how much fish is left in them (toggled with O)
+#[derive(Resource, Default)]
+pub struct FishOverlay {
+    pub visible: bool,
+}
+
+/// Order of the fish in the basket panel; its SORT button cycles through these
+#[derive(Resource, Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BasketSort {
+    #[default]
+    Caught,   // Oldest catch first
+    Value,
+    Rarity,
+    Size,
+    Color,
+    Pattern,
+    Shape,
+}
+
+impl BasketSort {
+    pub const ALL: [BasketSort; 7] = [
+        BasketSort::Caught, BasketSort::Value, BasketSort::Rarity, BasketSort::Size,
+        BasketSort::Color, BasketSort::Pattern, BasketSort::Shape,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            BasketSort::Caught => "Caught",
+            BasketSort::Value => "Value",
+            BasketSort::Rarity => "Rarity",
+            BasketSort::Size => "Size",
+            BasketSort::Color => "Color",
+            BasketSort::Pattern => "Pattern",
+            BasketSort::Shape => "Shape",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        let i = Self::ALL.iter().position(|s| s == self).unwrap_or(0);
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+
+    /// Sorts fish in place; rarity, size (by weight) and value put the best first, and fish
+    /// that tie are ordered by value
+    pub fn sort(&self, fish: &mut [&Fish]) {
+        let by_value = |a: &&Fish, b: &&Fish| b.value.cmp(&a.value);
+        match self {
+            BasketSort::Caught => {}
+            BasketSort::Value => fish.sort_by(by_value),
+            BasketSort::Rarity => fish.sort_by(|a, b| b.rarity.cmp(&a.rarity).then(by_value(a, b))),
+            BasketSort::Size => fish.sort_by(|a, b| b.weight_kg.total_cmp(&a.weight_kg).then(by_value(a, b))),
+            BasketSort::Color => fish.sort_by(|a, b| a.traits.color.cmp(&b.traits.color).then(by_value(a, b))),
+            BasketSort::Pattern => fish.sort_by(|a, b| a.traits.pattern.cmp(&b.traits.pattern).then(by_value(a, b))),
+            BasketSort::Shape => fish.sort_by(|a, b| a.traits.shape.cmp(&b.traits.shape).then(by_value(a, b))),
+        }
+    }
+}
+
+/// One line in the on-screen event log
+#[derive(Clone)]
+pub struct Notification {
+    pub text: String,
+    pub color: Color,
+    pub timestamp: String,  // In-game day and time, e.g. "Day 3 04:30 PM"
+    pub toast: bool,        // Also pop up as a toast, not just in the history
+}
+
+/// Recent notifications, newest last, capped at `NOTIFICATION_HISTORY_LEN`
+#[derive(Resource, Default)]
+pub struct NotificationLog {
+    pub entries: VecDeque<Notification>,
+    pub unshown: usize,  // Entries at the back not yet added to the UI
+}
+
+impl NotificationLog {
+    pub fn push(&mut self, notification: Notification) {
+        self.entries.push_back(notification);
+        self.unshown = (self.unshown + 1).min(NOTIFICATION_HISTORY_LEN);
+        if self.entries.len() > NOTIFICATION_HISTORY_LEN {
+            self.entries.pop_front();
+        }
+    }
+}
+
+/// Running totals of fish caught and escaped, by rarity and by uncle type
+#[derive(Resource, Default, Clone, Serialize, Deserialize)]
+pub struct FishingStats {
+    pub caught_by_rarity: BTreeMap<FishRarity, u32>,
+    pub escaped_by_rarity: BTreeMap<FishRarity, u32>,
+    pub caught_by_uncle: BTreeMap<UncleType, u32>,
+    pub escaped_by_uncle: BTreeMap<UncleType, u32>,
+}
+
+impl FishingStats {
+    pub fn record_catch(&mut self, fish: &Fish) {
+        *self.caught_by_rarity.entry(fish.rarity).or_default() += 1;
+        *self.caught_by_uncle.entry(fish.caught_by_uncle.clone()).or_default() += 1;
+    }
+
+    pub fn record_escape(&mut self, fish: &Fish) {
+        *self.escaped_by_rarity.entry(fish.rarity).or_default() += 1;
+        *self.escaped_by_uncle.entry(fish.caught_by_uncle.clone()).or_default() += 1;
+    }
+}
+
+/// The heaviest fish of one species landed this run
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SpeciesRecord {
+    pub species: SpeciesId,
+    pub weight_kg: f32,
+    pub length_cm: f32,
+    pub caught_by_uncle: UncleType,
+    pub day: u32,  // In-game day it was caught
+}
+
+/// Record weights of every species caught this run
+#[derive(Resource, Default, Clone)]
+pub struct SpeciesRecords {
+    pub records: BTreeMap<SpeciesId, SpeciesRecord>,
+}
+
+impl SpeciesRecords {
+    /// Keeps `fish` as the record of its species if it is the heaviest so far. Returns the
+    /// weight of the record it broke; the first fish of a species sets a record without
+    /// breaking one. Fish that were never measured are ignored.
+    pub fn offer(&mut self, fish: &Fish, day: u32) -> Option<f32> {
+        if fish.weight_kg <= 0.0 {
+            return None;
+        }
+        let previous = self.records.get(&fish.species).map(|record| record.weight_kg);
+        if previous.is_some_and(|weight| weight >= fish.weight_kg) {
+            return None;
+        }
+        self.records.insert(fish.species.clone(), SpeciesRecord {
+            species: fish.species.clone(),
+            weight_kg: fish.weight_kg,
+            length_cm: fish.length_cm,
+            caught_by_uncle: fish.caught_by_uncle.clone(),
+            day,
+        });
+        previous
+    }
+}
+
+/// Number of fixed simulation ticks run so far this session
+#[derive(Resource, Default)]
+pub struct SimulationTick(pub u64);
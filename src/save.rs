@@ -0,0 +1,281 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+use std::path::Path;
+
+use crate::components::{TileType, UncleBasket};
+use crate::resources::{DayNightCycle, FishingStats, GameState, RngStream, SpeciesRecord};
+use crate::species::SpeciesId;
+use crate::uncles::UncleType;
+
+/// Current save-file schema version.
+/// Bump this and append a step to `MIGRATIONS` whenever a saved type changes shape.
+pub const SAVE_VERSION: u32 = 7;
+
+/// Upgrade steps applied to raw JSON, indexed by the version they upgrade *from* minus one.
+/// `MIGRATIONS[0]` turns a v1 save into v2, `MIGRATIONS[1]` turns v2 into v3, and so on.
+const MIGRATIONS: &[fn(Value) -> Value] = &[
+    migrate_v1_flop_escapes,
+    migrate_v2_chunked_world,
+    migrate_v3_rng_streams,
+    migrate_v4_species_catalogue,
+    migrate_v5_fishing_stats,
+    migrate_v6_lake_bodies,
+];
+
+/// Everything needed to rebuild a run exactly as it was left
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SaveFile {
+    pub version: u32,
+    pub saved_at: u64,  // Unix seconds when the save was written
+    pub game_state: GameState,
+    pub day_night: DayNightCycle,
+    pub world_seed: WorldSeedSave,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tiles: Option<TileGridSave>,  // Only in v1-v2 saves, which stored the whole fixed-size map
+    #[serde(default)]
+    pub tile_edits: Vec<TileEditSave>,  // Tiles changed by the player; the rest is regenerated from the seed
+    pub uncles: Vec<UncleSave>,
+    #[serde(default)]
+    pub selected_uncle_type: Option<UncleType>,  // Uncle type picked for the next placement
+    #[serde(default)]
+    pub populations: Vec<PopulationSave>,  // Fished populations; missing ones start fully stocked
+    #[serde(default)]
+    pub records: Vec<SpeciesRecord>,  // Heaviest fish of each species caught this run
+    pub stats: FishingStats,  // Catch and escape totals for the run summary
+}
+
+/// Seed plus the ChaCha8 stream position, so the RNG resumes where it left off
+#[derive(Clone, Serialize, Deserialize)]
+pub struct WorldSeedSave {
+    pub seed: u64,
+    #[serde(default)]
+    pub streams: Vec<RngStreamSave>,  // Only streams that have been drawn from
+}
+
+/// How far one random stream has advanced
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RngStreamSave {
+    pub stream: RngStream,
+    pub word_pos: u128,
+}
+
+/// Row-major tile types of a fixed-size map starting at (0, 0), as older versions saved it
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TileGridSave {
+    pub width: usize,
+    pub height: usize,
+    pub tiles: Vec<TileType>,
+}
+
+/// A tile the player changed (a dock or cleared forest)
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TileEditSave {
+    pub x: i32,
+    pub y: i32,
+    pub tile_type: TileType,
+}
+
+/// A placed uncle with its timer progress and basket contents
+#[derive(Clone, Serialize, Deserialize)]
+pub struct UncleSave {
+    pub uncle_type: UncleType,
+    pub x: i32,
+    pub y: i32,
+    pub timer_elapsed: f32,
+    pub basket: UncleBasket,
+    #[serde(default)]
+    pub selected: bool,  // Whether this uncle's basket was open in the UI
+}
+
+/// Fish of one species left in one water body; everything else about the water is
+/// regenerated from the seed and tile grid
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PopulationSave {
+    pub chunk: (i32, i32),  // With `body`, the `BodyId` of the water body
+    pub body: usize,
+    pub chunks: Vec<(i32, i32)>,  // Every chunk the body spanned, generated before the count is restored
+    pub species: SpeciesId,
+    pub count: f32,
+}
+
+#[derive(Debug)]
+pub enum SaveError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    MissingVersion,
+    UnsupportedVersion(u32),
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveError::Io(err) => write!(f, "I/O error: {}", err),
+            SaveError::Json(err) => write!(f, "malformed save: {}", err),
+            SaveError::MissingVersion => write!(f, "save has no schema version"),
+            SaveError::UnsupportedVersion(v) => {
+                write!(f, "save version {} is not supported (current is {})", v, SAVE_VERSION)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SaveError {}
+
+impl From<std::io::Error> for SaveError {
+    fn from(err: std::io::Error) -> Self {
+        SaveError::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for SaveError {
+    fn from(err: serde_json::Error) -> Self {
+        SaveError::Json(err)
+    }
+}
+
+impl SaveFile {
+    /// Parses a save, migrating older schema versions up to `SAVE_VERSION`
+    pub fn from_json(text: &str) -> Result<Self, SaveError> {
+        let mut value: Value = serde_json::from_str(text)?;
+
+        let version = value
+            .get("version")
+            .and_then(Value::as_u64)
+            .ok_or(SaveError::MissingVersion)? as u32;
+
+        if version == 0 || version > SAVE_VERSION {
+            return Err(SaveError::UnsupportedVersion(version));
+        }
+
+        for migrate in &MIGRATIONS[(version - 1) as usize..] {
+            value = migrate(value);
+        }
+        value["version"] = Value::from(SAVE_VERSION);
+
+        Ok(serde_json::from_value(value)?)
+    }
+
+    pub fn to_json(&self) -> Result<String, SaveError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn read(path: &Path) -> Result<Self, SaveError> {
+        let text = std::fs::read_to_string(path)?;
+        Self::from_json(&text)
+    }
+
+    /// Writes to a temporary file first so a crash mid-write never corrupts the last good save
+    pub fn write(&self, path: &Path) -> Result<(), SaveError> {
+        let tmp_path = path.with_extension("tmp");
+        std::fs::write(&tmp_path, self.to_json()?)?;
+        std::fs::rename(&tmp_path, path)?;
+        Ok(())
+    }
+}
+
+/// v1 counted a failed escape attempt every frame. v2 counts flops and schedules the next one,
+/// so basket fish get the flop count a fish of their age would have and flop on the next tick.
+fn migrate_v1_flop_escapes(mut value: Value) -> Value {
+    use crate::components::MetabolicPhase;
+    use crate::constants::{BURST_PHASE_DURATION, STOCHASTIC_PHASE_DURATION};
+
+    let flops_by_age = |age: f32| {
+        let burst = age.min(BURST_PHASE_DURATION);
+        let stochastic = (age - BURST_PHASE_DURATION).clamp(0.0, STOCHASTIC_PHASE_DURATION);
+        let fatigue = (age - BURST_PHASE_DURATION - STOCHASTIC_PHASE_DURATION).max(0.0);
+        burst * MetabolicPhase::Burst.flop_hz()
+            + stochastic * MetabolicPhase::Stochastic.flop_hz()
+            + fatigue * MetabolicPhase::Fatigue.flop_hz()
+    };
+
+    let Some(uncles) = value.get_mut("uncles").and_then(Value::as_array_mut) else {
+        return value;
+    };
+    for uncle in uncles {
+        let Some(fish) = uncle.pointer_mut("/basket/fish").and_then(Value::as_array_mut) else {
+            continue;
+        };
+        for fish in fish {
+            let age = fish.get("time_alive").and_then(Value::as_f64).unwrap_or(0.0) as f32;
+            fish["failed_escape_attempts"] = Value::from(flops_by_age(age) as u32);
+            fish["next_flop_at"] = Value::from(age);
+        }
+    }
+    value
+}
+
+/// v2 numbered water bodies across the whole fixed-size map; v3 numbers them per chunk.
+/// The old numbering cannot be matched up, so v2 populations are dropped and their water
+/// restocks. The old tile grid is kept and turned into tile edits on load.
+fn migrate_v2_chunked_world(mut value: Value) -> Value {
+    if let Some(save) = value.as_object_mut() {
+        save.remove("populations");
+    }
+    value
+}
+
+/// v3 kept one random stream for everything; v4 derives one per purpose. The old stream's
+/// position means nothing to the new ones, so they start from the beginning.
+fn migrate_v3_rng_streams(mut value: Value) -> Value {
+    if let Some(world_seed) = value.get_mut("world_seed").and_then(Value::as_object_mut) {
+        world_seed.remove("word_pos");
+    }
+    value
+}
+
+/// v4 species were combinations of the old name lists, with no catalogue entry. Their
+/// populations are dropped so the water restocks with catalogue species, and basket fish get
+/// the color, pattern and shape their name spelled out ("Blue Striped Slimfish").
+fn migrate_v4_species_catalogue(mut value: Value) -> Value {
+    use crate::species::{FishColor, FishPattern, FishShape, FishTraits};
+
+    if let Some(save) = value.as_object_mut() {
+        save.remove("populations");
+    }
+    let Some(uncles) = value.get_mut("uncles").and_then(Value::as_array_mut) else {
+        return value;
+    };
+    for uncle in uncles {
+        let Some(fish) = uncle.pointer_mut("/basket/fish").and_then(Value::as_array_mut) else {
+            continue;
+        };
+        for fish in fish {
+            let name = fish.get("name").and_then(Value::as_str).unwrap_or_default().to_string();
+            let words: Vec<&str> = name.split_whitespace().collect();
+            let mut traits = FishTraits::default();
+            if let [color, pattern, shape] = words[..] {
+                traits.color = FishColor::ALL.into_iter().find(|c| c.name() == color).unwrap_or_default();
+                traits.pattern = FishPattern::ALL.into_iter().find(|p| p.name() == pattern).unwrap_or_default();
+                let shape = shape.trim_end_matches("fish");
+                traits.shape = FishShape::ALL.into_iter().find(|s| s.name() == shape).unwrap_or_default();
+            }
+            fish["traits"] = serde_json::to_value(traits).unwrap_or_default();
+        }
+    }
+    value
+}
+
+/// v5 did not save the run's catch and escape totals. They cannot be recovered, so they
+/// start from zero and only count what happens after the upgrade.
+fn migrate_v5_fishing_stats(mut value: Value) -> Value {
+    value["stats"] = serde_json::to_value(FishingStats::default()).unwrap_or_default();
+    value
+}
+
+/// v6 water bodies ended at chunk borders; v7 joins a lake across them. A v6 body may now be
+/// part of a larger one, so v6 populations are dropped and their water restocks.
+fn migrate_v6_lake_bodies(mut value: Value) -> Value {
+    if let Some(save) = value.as_object_mut() {
+        save.remove("populations");
+    }
+    value
+}
+
+/// Current Unix time in seconds
+pub fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
@@ -0,0 +1,81 @@
+//! Seed codes: how players read, share and type world seeds.
+//!
+//! A seed is shown as a 13-character Crockford base32 code in three groups
+//! (`"0K3F-7QZ2-M9V4A"`), which holds any `u64` and avoids letters that are easy to mix up.
+//! Players can type a code back in (any case, dashes optional), a plain decimal seed,
+//! or any other text such as `"blue lagoon"`, which is hashed to a seed.
+
+/// Crockford base32: digits and letters without I, L, O and U
+const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// Characters in a code; 13 × 5 bits covers all 64 bits of a seed
+const CODE_LEN: usize = 13;
+
+/// A fresh seed from the OS entropy source, so worlds started in the same second still differ
+pub fn random_seed() -> u64 {
+    rand::random()
+}
+
+/// The shareable code for a seed, e.g. `"0K3F-7QZ2-M9V4A"`
+pub fn seed_code(seed: u64) -> String {
+    let mut code = String::with_capacity(CODE_LEN + 2);
+    for i in 0..CODE_LEN {
+        if i == 4 || i == 8 {
+            code.push('-');
+        }
+        let shift = 5 * (CODE_LEN - 1 - i);
+        code.push(ALPHABET[((seed >> shift) & 31) as usize] as char);
+    }
+    code
+}
+
+/// Reads a seed typed by the player: a seed code, a decimal seed, or any other text,
+/// which is hashed (ignoring case and extra spaces). `None` if there is no text.
+pub fn parse_seed(text: &str) -> Option<u64> {
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+    if text.bytes().all(|b| b.is_ascii_digit()) {
+        if let Ok(seed) = text.parse() {
+            return Some(seed);
+        }
+    }
+    decode_code(text).or_else(|| Some(hash_words(text)))
+}
+
+/// Decodes a seed code, forgiving case, dashes, spaces and the look-alikes O, I and L
+fn decode_code(text: &str) -> Option<u64> {
+    let compact: Vec<char> = text.chars().filter(|c| *c != '-' && !c.is_whitespace()).collect();
+    if compact.len() != CODE_LEN {
+        return None;
+    }
+    let mut seed: u64 = 0;
+    for (i, c) in compact.into_iter().enumerate() {
+        let c = match c.to_ascii_uppercase() {
+            'O' => '0',
+            'I' | 'L' => '1',
+            c => c,
+        };
+        let value = ALPHABET.iter().position(|&a| a as char == c)? as u64;
+        // The first character only carries the top 4 bits
+        if i == 0 && value > 15 {
+            return None;
+        }
+        seed = (seed << 5) | value;
+    }
+    Some(seed)
+}
+
+/// FNV-1a over the normalised words, then a SplitMix64 finaliser to spread similar phrases apart
+fn hash_words(text: &str) -> u64 {
+    let normalised = text.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in normalised.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    hash = (hash ^ (hash >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    hash = (hash ^ (hash >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    hash ^ (hash >> 31)
+}
@@ -0,0 +1,446 @@
+//! The species catalogue: every kind of fish that lives in the water, loaded from data.
+//!
+//! Species definitions (habitat, time of day they bite, how common they are, what they are
+//! worth and how hard they fight) live in `assets/species.json`. Like the uncle roster, the
+//! file is read on startup and also built into the binary as the default catalogue. Every
+//! caught `Fish` records its species id, its rolled traits (color, pattern, shape and
+//! size) and its measured length and weight, so value, escapes and the UI can work with
+//! them directly.
+
+use bevy::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt;
+use std::path::Path;
+
+use crate::components::{FishRarity, TileType};
+use crate::constants::{FISH_SIZE_LENGTH_RATIOS, OFF_HOURS_BITE_MULTIPLIER};
+
+/// Where the game looks for the catalogue, relative to the working directory
+pub const SPECIES_CATALOGUE_PATH: &str = "assets/species.json";
+
+/// The catalogue the game ships with
+const BUILT_IN_CATALOGUE: &str = include_str!("../assets/species.json");
+
+/// Id of a species definition in the `SpeciesCatalogue`, e.g. `"koi"`.
+/// Empty for fish caught before there was a catalogue.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SpeciesId(String);
+
+impl SpeciesId {
+    pub fn new(id: &str) -> Self {
+        Self(id.to_string())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for SpeciesId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default, Serialize, Deserialize)]
+pub enum FishColor {
+    #[default]
+    Blue,
+    Red,
+    Green,
+    Yellow,
+    Purple,
+    Orange,
+    Pink,
+    Teal,
+}
+
+impl FishColor {
+    pub const ALL: [FishColor; 8] = [
+        FishColor::Blue, FishColor::Red, FishColor::Green, FishColor::Yellow,
+        FishColor::Purple, FishColor::Orange, FishColor::Pink, FishColor::Teal,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            FishColor::Blue => "Blue",
+            FishColor::Red => "Red",
+            FishColor::Green => "Green",
+            FishColor::Yellow => "Yellow",
+            FishColor::Purple => "Purple",
+            FishColor::Orange => "Orange",
+            FishColor::Pink => "Pink",
+            FishColor::Teal => "Teal",
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            FishColor::Blue => Color::srgb(0.231, 0.510, 0.965),
+            FishColor::Red => Color::srgb(0.937, 0.267, 0.267),
+            FishColor::Green => Color::srgb(0.133, 0.773, 0.369),
+            FishColor::Yellow => Color::srgb(0.984, 0.749, 0.141),
+            FishColor::Purple => Color::srgb(0.659, 0.333, 0.969),
+            FishColor::Orange => Color::srgb(0.976, 0.451, 0.086),
+            FishColor::Pink => Color::srgb(0.925, 0.282, 0.600),
+            FishColor::Teal => Color::srgb(0.078, 0.722, 0.651),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default, Serialize, Deserialize)]
+pub enum FishPattern {
+    Striped,
+    Spotted,
+    #[default]
+    Solid,
+    Marbled,
+    Gradient,
+}
+
+impl FishPattern {
+    pub const ALL: [FishPattern; 5] = [
+        FishPattern::Striped, FishPattern::Spotted, FishPattern::Solid, FishPattern::Marbled, FishPattern::Gradient,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            FishPattern::Striped => "Striped",
+            FishPattern::Spotted => "Spotted",
+            FishPattern::Solid => "Solid",
+            FishPattern::Marbled => "Marbled",
+            FishPattern::Gradient => "Gradient",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default, Serialize, Deserialize)]
+pub enum FishShape {
+    Slim,
+    #[default]
+    Round,
+    Flat,
+    Long,
+    Bulky,
+}
+
+impl FishShape {
+    pub const ALL: [FishShape; 5] = [FishShape::Slim, FishShape::Round, FishShape::Flat, FishShape::Long, FishShape::Bulky];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            FishShape::Slim => "Slim",
+            FishShape::Round => "Round",
+            FishShape::Flat => "Flat",
+            FishShape::Long => "Long",
+            FishShape::Bulky => "Bulky",
+        }
+    }
+
+    /// Multiplies the species' escape strength
+    pub fn escape_multiplier(&self) -> f32 {
+        match self {
+            FishShape::Slim => 1.15,   // Slips through fingers
+            FishShape::Long => 1.05,
+            FishShape::Bulky => 1.0,
+            FishShape::Flat => 0.95,
+            FishShape::Round => 0.9,   // Easy to keep hold of
+        }
+    }
+}
+
+/// Size class of a fish, from how its length compares to the species median. Ordered smallest to largest.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default, Serialize, Deserialize)]
+pub enum FishSize {
+    Tiny,
+    Small,
+    #[default]
+    Medium,
+    Large,
+    Huge,
+}
+
+impl FishSize {
+    pub const ALL: [FishSize; 5] = [FishSize::Tiny, FishSize::Small, FishSize::Medium, FishSize::Large, FishSize::Huge];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            FishSize::Tiny => "Tiny",
+            FishSize::Small => "Small",
+            FishSize::Medium => "Medium",
+            FishSize::Large => "Large",
+            FishSize::Huge => "Huge",
+        }
+    }
+
+    /// Size class of a fish `ratio` times the median length of its species
+    pub fn from_length_ratio(ratio: f32) -> Self {
+        let larger = FISH_SIZE_LENGTH_RATIOS.iter().filter(|&&start| ratio >= start).count();
+        FishSize::ALL[larger]
+    }
+
+    /// Multiplies the chance of every flop: trophy fish fight hardest
+    pub fn escape_multiplier(&self) -> f32 {
+        match self {
+            FishSize::Tiny => 0.8,
+            FishSize::Small => 0.9,
+            FishSize::Medium => 1.0,
+            FishSize::Large => 1.15,
+            FishSize::Huge => 1.35,
+        }
+    }
+}
+
+/// What a caught fish looks like. Pattern and shape come from its species; color is rolled
+/// for every fish and size follows from its length.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, Serialize, Deserialize)]
+pub struct FishTraits {
+    pub color: FishColor,
+    pub pattern: FishPattern,
+    pub shape: FishShape,
+    pub size: FishSize,
+}
+
+impl FishTraits {
+    /// E.g. "Large Orange Marbled Round"
+    pub fn describe(&self) -> String {
+        format!("{} {} {} {}", self.size.name(), self.color.name(), self.pattern.name(), self.shape.name())
+    }
+}
+
+/// When a species bites. Outside its hours it bites at `OFF_HOURS_BITE_MULTIPLIER` of its usual rate.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub enum Activity {
+    Day,
+    Night,
+    Always,
+}
+
+impl Activity {
+    pub fn bite_multiplier(&self, is_day: bool) -> f32 {
+        match (self, is_day) {
+            (Activity::Day, false) | (Activity::Night, true) => OFF_HOURS_BITE_MULTIPLIER,
+            _ => 1.0,
+        }
+    }
+}
+
+/// Gold a fish of the species' median weight sells for; value scales with weight
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct ValueCurve {
+    pub min: u32,
+    pub max: u32,
+}
+
+/// How big the species grows. Lengths are log-normal around the median; weight grows with
+/// the cube of length.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct SizeCurve {
+    pub median_cm: f32,
+    pub median_kg: f32,     // Weight of a fish of the median length
+    pub spread: f32,        // Standard deviation of the log of the length
+}
+
+/// One kind of fish: where it lives, when it bites and what it is worth
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SpeciesDef {
+    pub id: SpeciesId,
+    pub name: String,
+    pub rarity: FishRarity,
+    #[serde(default = "one")]
+    pub rarity_weight: f32,          // How often the species shows up in pools, against others of its rarity
+    pub habitats: Vec<TileType>,     // Water tile types it lives in
+    pub activity: Activity,
+    pub shape: FishShape,
+    pub pattern: FishPattern,
+    pub colors: Vec<FishColor>,      // Each fish rolls one of these
+    pub value: ValueCurve,
+    pub size: SizeCurve,
+    #[serde(default = "one")]
+    pub escape_strength: f32,        // Multiplies the chance of every flop, before the shape's multiplier
+    #[serde(default)]
+    pub description: String,
+}
+
+fn one() -> f32 {
+    1.0
+}
+
+/// A standard normal sample (Box-Muller)
+fn standard_normal(rng: &mut impl Rng) -> f32 {
+    let u1: f32 = rng.gen_range(f32::EPSILON..1.0);
+    let u2: f32 = rng.gen();
+    (-2.0 * u1.ln()).sqrt() * (std::f32::consts::TAU * u2).cos()
+}
+
+impl SpeciesDef {
+    pub fn lives_in(&self, tile_type: TileType) -> bool {
+        self.habitats.contains(&tile_type)
+    }
+
+    /// Rolls the length of a newly caught fish from the species' log-normal distribution
+    pub fn roll_length(&self, rng: &mut impl Rng) -> f32 {
+        self.size.median_cm * (self.size.spread * standard_normal(rng)).exp()
+    }
+
+    /// Weight of a fish of this species `length_cm` long
+    pub fn weight_at(&self, length_cm: f32) -> f32 {
+        self.size.median_kg * (length_cm / self.size.median_cm).powi(3)
+    }
+
+    /// Rolls the color of a newly caught fish `length_cm` long
+    pub fn roll_traits(&self, length_cm: f32, rng: &mut impl Rng) -> FishTraits {
+        let color = self.colors[rng.gen_range(0..self.colors.len())];
+        let size = FishSize::from_length_ratio(length_cm / self.size.median_cm);
+        FishTraits { color, pattern: self.pattern, shape: self.shape, size }
+    }
+
+    /// Rolls the value of a fish of this species weighing `weight_kg`, at least 1 gold
+    pub fn roll_value(&self, weight_kg: f32, rng: &mut impl Rng) -> u32 {
+        let base = rng.gen_range(self.value.min..=self.value.max) as f32;
+        ((base * weight_kg / self.size.median_kg).round() as u32).max(1)
+    }
+
+    /// Escape strength of a fish of this species with the given traits
+    pub fn escape_strength(&self, traits: &FishTraits) -> f32 {
+        self.escape_strength * traits.shape.escape_multiplier()
+    }
+}
+
+#[derive(Debug)]
+pub enum CatalogueError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    Invalid(String),
+}
+
+impl fmt::Display for CatalogueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CatalogueError::Io(err) => write!(f, "I/O error: {}", err),
+            CatalogueError::Json(err) => write!(f, "malformed catalogue: {}", err),
+            CatalogueError::Invalid(reason) => write!(f, "invalid catalogue: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for CatalogueError {}
+
+impl From<std::io::Error> for CatalogueError {
+    fn from(err: std::io::Error) -> Self {
+        CatalogueError::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for CatalogueError {
+    fn from(err: serde_json::Error) -> Self {
+        CatalogueError::Json(err)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct CatalogueFile {
+    species: Vec<SpeciesDef>,
+}
+
+/// Every species of fish, in catalogue order
+#[derive(Resource, Clone, Debug)]
+pub struct SpeciesCatalogue {
+    species: Vec<SpeciesDef>,
+}
+
+impl Default for SpeciesCatalogue {
+    fn default() -> Self {
+        Self::from_json(BUILT_IN_CATALOGUE).expect("built-in species catalogue is valid")
+    }
+}
+
+impl SpeciesCatalogue {
+    /// Checks that there is at least one species, ids are unique and every species lives
+    /// in water, has a color, a value, a size and a positive rarity weight
+    pub fn new(species: Vec<SpeciesDef>) -> Result<Self, CatalogueError> {
+        if species.is_empty() {
+            return Err(CatalogueError::Invalid("no species".to_string()));
+        }
+        let mut ids = HashSet::new();
+        for def in &species {
+            let invalid = |reason: &str| Err(CatalogueError::Invalid(format!("species {} {}", def.id, reason)));
+            if !ids.insert(&def.id) {
+                return invalid("is defined twice");
+            }
+            if def.habitats.is_empty() || def.habitats.iter().any(|t| !t.is_water()) {
+                return invalid("needs water habitats");
+            }
+            if def.colors.is_empty() {
+                return invalid("needs at least one color");
+            }
+            if def.value.max == 0 || def.value.min > def.value.max {
+                return invalid("has an empty value range");
+            }
+            if def.size.median_cm <= 0.0 || def.size.median_kg <= 0.0 || def.size.spread < 0.0 {
+                return invalid("needs a positive median length and weight");
+            }
+            if def.rarity_weight <= 0.0 {
+                return invalid("needs a positive rarity weight");
+            }
+            if def.escape_strength < 0.0 {
+                return invalid("has a negative escape strength");
+            }
+        }
+        Ok(Self { species })
+    }
+
+    pub fn from_json(text: &str) -> Result<Self, CatalogueError> {
+        let file: CatalogueFile = serde_json::from_str(text)?;
+        Self::new(file.species)
+    }
+
+    pub fn read(path: &Path) -> Result<Self, CatalogueError> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+
+    /// The catalogue at `path`, or the built-in one if the file is missing or broken
+    pub fn read_or_default(path: &Path) -> Self {
+        match Self::read(path) {
+            Ok(catalogue) => {
+                println!("🐟 Loaded {} species from {}", catalogue.species.len(), path.display());
+                catalogue
+            }
+            Err(CatalogueError::Io(err)) if err.kind() == std::io::ErrorKind::NotFound => Self::default(),
+            Err(err) => {
+                eprintln!("⚠️ Could not load {}: {}; using the built-in species", path.display(), err);
+                Self::default()
+            }
+        }
+    }
+
+    pub fn get(&self, id: &SpeciesId) -> Option<&SpeciesDef> {
+        self.species.iter().find(|def| def.id == *id)
+    }
+
+    /// Position of a species in the catalogue
+    pub fn index_of(&self, id: &SpeciesId) -> Option<usize> {
+        self.species.iter().position(|def| def.id == *id)
+    }
+
+    /// The species at `index`, as numbered by `iter`
+    pub fn at(&self, index: usize) -> &SpeciesDef {
+        &self.species[index]
+    }
+
+    /// Every definition, in catalogue order
+    pub fn iter(&self) -> std::slice::Iter<'_, SpeciesDef> {
+        self.species.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.species.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.species.is_empty()
+    }
+}
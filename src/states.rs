@@ -0,0 +1,16 @@
+use bevy::prelude::*;
+
+/// Which screen the app is on. Gameplay (the fixed-tick simulation, world input and the camera)
+/// only runs while `Playing`; the run itself lives in the `GameState` resource and survives
+/// moving between screens.
+///
+/// With the UI the app opens on the main menu; headless apps (tests, the simulator) and
+/// replays start straight in `Playing`.
+#[derive(States, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum AppState {
+    #[default]
+    MainMenu,    // New Run / Continue / Load seed over the world loaded behind it
+    Playing,
+    Paused,      // Esc: simulation stopped, pause overlay shown
+    RunSummary,  // The run was ended from the pause menu; totals shown until a new run starts
+}
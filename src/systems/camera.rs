@@ -0,0 +1,86 @@
+use bevy::input::mouse::{MouseMotion, MouseScrollUnit, MouseWheel};
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use crate::constants::*;
+
+/// Pans the camera with WASD / the arrow keys, or when the cursor touches the window edge.
+/// Speed is in screen pixels, so panning feels the same at any zoom.
+pub fn pan_camera(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut camera_q: Query<(&mut Transform, &OrthographicProjection), With<Camera>>,
+    time: Res<Time>,
+) {
+    let Ok((mut transform, projection)) = camera_q.get_single_mut() else {
+        return;
+    };
+
+    let mut direction = Vec2::ZERO;
+    for (keys, step) in [
+        ([KeyCode::KeyW, KeyCode::ArrowUp], Vec2::Y),
+        ([KeyCode::KeyS, KeyCode::ArrowDown], Vec2::NEG_Y),
+        ([KeyCode::KeyA, KeyCode::ArrowLeft], Vec2::NEG_X),
+        ([KeyCode::KeyD, KeyCode::ArrowRight], Vec2::X),
+    ] {
+        if keyboard.any_pressed(keys) {
+            direction += step;
+        }
+    }
+
+    if let Some((window, cursor)) = windows.get_single().ok().and_then(|w| Some((w, w.cursor_position()?))) {
+        // Window coordinates grow downwards, world coordinates upwards
+        if cursor.x < CAMERA_EDGE_SCROLL_MARGIN {
+            direction.x -= 1.0;
+        } else if cursor.x > window.width() - CAMERA_EDGE_SCROLL_MARGIN {
+            direction.x += 1.0;
+        }
+        if cursor.y < CAMERA_EDGE_SCROLL_MARGIN {
+            direction.y += 1.0;
+        } else if cursor.y > window.height() - CAMERA_EDGE_SCROLL_MARGIN {
+            direction.y -= 1.0;
+        }
+    }
+
+    let pan = direction.normalize_or_zero() * CAMERA_PAN_SPEED * projection.scale * time.delta_seconds();
+    transform.translation += pan.extend(0.0);
+}
+
+/// Drags the map with the right or middle mouse button held (left click places uncles)
+pub fn drag_camera(
+    mouse_button: Res<ButtonInput<MouseButton>>,
+    mut motion: EventReader<MouseMotion>,
+    mut camera_q: Query<(&mut Transform, &OrthographicProjection), With<Camera>>,
+) {
+    let dragging = mouse_button.any_pressed([MouseButton::Right, MouseButton::Middle]);
+    let delta: Vec2 = motion.read().map(|event| event.delta).sum();
+    let Ok((mut transform, projection)) = camera_q.get_single_mut() else {
+        return;
+    };
+    if dragging {
+        transform.translation.x -= delta.x * projection.scale;
+        transform.translation.y += delta.y * projection.scale;
+    }
+}
+
+/// Zooms with the mouse wheel, unless the cursor is over the UI (the event log scrolls instead)
+pub fn zoom_camera(
+    mut wheel: EventReader<MouseWheel>,
+    ui_q: Query<&Interaction>,
+    mut projection_q: Query<&mut OrthographicProjection, With<Camera>>,
+) {
+    let lines: f32 = wheel
+        .read()
+        .map(|event| match event.unit {
+            MouseScrollUnit::Line => event.y,
+            MouseScrollUnit::Pixel => event.y / HISTORY_SCROLL_SPEED,
+        })
+        .sum();
+    if lines == 0.0 || ui_q.iter().any(|interaction| *interaction != Interaction::None) {
+        return;
+    }
+    let Ok(mut projection) = projection_q.get_single_mut() else {
+        return;
+    };
+    // Scrolling up zooms in
+    projection.scale = (projection.scale * CAMERA_ZOOM_STEP.powf(-lines)).clamp(CAMERA_MIN_ZOOM, CAMERA_MAX_ZOOM);
+}
@@ -0,0 +1,64 @@
+use bevy::prelude::*;
+use crate::components::*;
+use crate::constants::{CATCH_ANIMATION_HEIGHT, CATCH_ANIMATION_SECONDS, FISH_SPRITE_HEIGHT, FISH_SPRITE_WIDTH};
+use crate::events::FishCaught;
+use crate::fish_sprite::{FishLook, FishSprites};
+use crate::species::FishSize;
+
+/// Every caught fish leaps out of the water over the uncle that caught it, drawn at its size
+pub fn spawn_catch_animations(
+    mut commands: Commands,
+    mut caught: EventReader<FishCaught>,
+    uncles_q: Query<&Transform, With<Uncle>>,
+    mut sprites: ResMut<FishSprites>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    for event in caught.read() {
+        let Ok(uncle_transform) = uncles_q.get(event.uncle) else {
+            continue;
+        };
+        let scale = match event.fish.traits.size {
+            FishSize::Tiny => 0.5,
+            FishSize::Small => 0.65,
+            FishSize::Medium => 0.8,
+            FishSize::Large => 1.0,
+            FishSize::Huge => 1.25,
+        };
+        // Above uncles and tiles
+        let origin = uncle_transform.translation.truncate().extend(5.0);
+        commands.spawn((
+            SpriteBundle {
+                texture: sprites.get(FishLook::of(&event.fish), &mut images),
+                sprite: Sprite {
+                    custom_size: Some(Vec2::new(FISH_SPRITE_WIDTH as f32, FISH_SPRITE_HEIGHT as f32) * scale),
+                    ..default()
+                },
+                transform: Transform::from_translation(origin),
+                ..default()
+            },
+            CatchAnimation { origin, elapsed: 0.0 },
+        ));
+    }
+}
+
+/// Moves catch animations along their arc, fading them out as they land
+pub fn animate_catches(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut animations_q: Query<(Entity, &mut CatchAnimation, &mut Transform, &mut Sprite)>,
+) {
+    for (entity, mut animation, mut transform, mut sprite) in animations_q.iter_mut() {
+        animation.elapsed += time.delta_seconds();
+        let t = animation.elapsed / CATCH_ANIMATION_SECONDS;
+        if t >= 1.0 {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        // Up and over from left to right, nose first
+        let arc = 4.0 * t * (1.0 - t);
+        transform.translation = animation.origin + Vec3::new((t - 0.5) * 24.0, arc * CATCH_ANIMATION_HEIGHT, 0.0);
+        transform.rotation = Quat::from_rotation_z((1.0 - 2.0 * t) * 0.6 + (t * 30.0).sin() * 0.08);
+        sprite.color = Color::WHITE.with_alpha(((1.0 - t) / 0.3).min(1.0));
+    }
+}
@@ -1,23 +1,20 @@
 use bevy::prelude::*;
+use crate::events::{DayStarted, DaylightChanged};
 use crate::resources::DayNightCycle;
 use crate::components::{DayNumberText, TimeOfDayText, CashoutsRemainingText};
-use crate::constants::*;
 
 /// Updates the day/night cycle progression
 pub fn day_night_cycle_system(
     mut day_night: ResMut<DayNightCycle>,
+    mut day_started: EventWriter<DayStarted>,
+    mut daylight_changed: EventWriter<DaylightChanged>,
     time: Res<Time>,
 ) {
-    day_night.time_elapsed += time.delta_seconds();
-
-    // Calculate progress through day (0.0 to 1.0)
-    day_night.day_progress = (day_night.time_elapsed / DAY_LENGTH_SECONDS) % 1.0;
-
-    // Check if new day started
-    if day_night.time_elapsed >= DAY_LENGTH_SECONDS {
-        day_night.time_elapsed = 0.0;
-        day_night.new_day();
-        println!("☀️ Day {} begins! Cash-outs refreshed: {}", day_night.day_number, day_night.cashouts_remaining);
+    if day_night.advance(time.delta_seconds()) {
+        day_started.send(DayStarted {
+            day_number: day_night.day_number,
+            cashouts_remaining: day_night.cashouts_remaining,
+        });
     }
 
     // Update day/night state
@@ -25,10 +22,8 @@ pub fn day_night_cycle_system(
     day_night.is_day = day_night.is_daytime();
 
     // Transition events
-    if !was_day && day_night.is_day {
-        println!("🌅 Dawn - daytime begins");
-    } else if was_day && !day_night.is_day {
-        println!("🌆 Dusk - nighttime begins");
+    if was_day != day_night.is_day {
+        daylight_changed.send(DaylightChanged { is_day: day_night.is_day });
     }
 }
 
@@ -0,0 +1,93 @@
+use bevy::prelude::*;
+use crate::components::{SelectedUncleMarker, Uncle};
+use crate::constants::*;
+use crate::events::{CashOutDenial, CashOutDenied, CashOutRequest, CashOutScope, CashedOut};
+use crate::resources::{DayNightCycle, GameState};
+
+/// Whether a cash-out would currently pass the cooldown and daily limit
+pub fn cash_out_available(game_state: &GameState, day_night: &DayNightCycle) -> bool {
+    game_state.cash_out_cooldown <= 0.0 && day_night.cashouts_remaining > 0
+}
+
+/// The only place baskets are sold: handles every `CashOutRequest` queued this tick
+pub fn handle_cash_out_requests(
+    mut requests: EventReader<CashOutRequest>,
+    mut uncles_q: Query<(&mut Uncle, Has<SelectedUncleMarker>)>,
+    mut game_state: ResMut<GameState>,
+    mut day_night: ResMut<DayNightCycle>,
+    mut cashed_out: EventWriter<CashedOut>,
+    mut denied: EventWriter<CashOutDenied>,
+) {
+    for request in requests.read() {
+        let uncles = uncles_q
+            .iter_mut()
+            .filter(|(_, selected)| request.scope == CashOutScope::All || *selected)
+            .map(|(uncle, _)| uncle.into_inner());
+
+        match cash_out_uncles(uncles, &mut game_state, &mut day_night) {
+            Ok((fish_sold, gold_earned, multiplier)) => {
+                cashed_out.send(CashedOut {
+                    scope: request.scope,
+                    fish_sold,
+                    gold_earned,
+                    multiplier,
+                    cashouts_remaining: day_night.cashouts_remaining,
+                });
+            }
+            Err(reason) => {
+                denied.send(CashOutDenied { scope: request.scope, reason });
+            }
+        }
+    }
+}
+
+/// Cashes out every non-empty basket in `uncles` as one cash-out.
+/// Enforces the cooldown and daily limit; returns (fish sold, gold earned, multiplier used) on success.
+pub fn cash_out_uncles<'a>(
+    uncles: impl IntoIterator<Item = &'a mut Uncle>,
+    game_state: &mut GameState,
+    day_night: &mut DayNightCycle,
+) -> Result<(u32, u32, f32), CashOutDenial> {
+    // Check cooldown
+    if game_state.cash_out_cooldown > 0.0 {
+        return Err(CashOutDenial::Cooldown);
+    }
+
+    // Check daily limit
+    if day_night.cashouts_remaining == 0 {
+        return Err(CashOutDenial::NoCashoutsRemaining);
+    }
+
+    let mut total_value = 0;
+    let mut total_fish = 0;
+
+    for uncle in uncles {
+        if !uncle.basket.fish.is_empty() {
+            total_value += uncle.basket.total_value();
+            total_fish += uncle.basket.fish.len() as u32;
+            uncle.basket.cash_out();
+        }
+    }
+
+    if total_fish == 0 {
+        return Err(CashOutDenial::NothingToSell);
+    }
+
+    let multiplier = game_state.multiplier;
+    let gold_earned = (total_value as f32 * multiplier) as u32;
+    game_state.gold += gold_earned;
+    game_state.fish_count += total_fish;
+    game_state.multiplier = (multiplier + MULTIPLIER_INCREMENT).min(MAX_MULTIPLIER);
+    game_state.cash_out_cooldown = CASH_OUT_COOLDOWN;
+    day_night.cashouts_remaining -= 1;
+
+    Ok((total_fish, gold_earned, multiplier))
+}
+
+/// Updates the cash out cooldown timer
+pub fn cooldown_update_system(
+    mut game_state: ResMut<GameState>,
+    time: Res<Time>,
+) {
+    game_state.tick_cooldown(time.delta_seconds());
+}
@@ -0,0 +1,329 @@
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+use crate::components::*;
+use crate::constants::{FISH_SPRITE_HEIGHT, FISH_SPRITE_WIDTH, HISTORY_SCROLL_SPEED};
+use crate::fish_sprite::{FishLook, FishSprites};
+use crate::fishdex::{Fishdex, FishdexEntry};
+use crate::resources::FishdexPanel;
+use crate::species::{FishColor, SpeciesCatalogue, SpeciesDef};
+use crate::uncles::{UncleRoster, UncleType};
+
+const TEXT_COLOR: Color = Color::srgb(0.945, 0.961, 0.973);
+const MUTED_COLOR: Color = Color::srgb(0.6, 0.65, 0.7);
+const SILHOUETTE_COLOR: Color = Color::srgb(0.05, 0.07, 0.1);
+
+/// F opens and closes the Fishdex
+pub fn toggle_fishdex(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    panel: Option<Res<FishdexPanel>>,
+    mut commands: Commands,
+) {
+    if keyboard.just_pressed(KeyCode::KeyF) {
+        if panel.is_some() {
+            commands.remove_resource::<FishdexPanel>();
+        } else {
+            commands.init_resource::<FishdexPanel>();
+        }
+    }
+}
+
+/// The Fishdex panel's CLOSE button
+pub fn handle_fishdex_close(
+    interaction_q: Query<&Interaction, (Changed<Interaction>, With<FishdexCloseButton>)>,
+    mut commands: Commands,
+) {
+    if interaction_q.iter().any(|interaction| *interaction == Interaction::Pressed) {
+        commands.remove_resource::<FishdexPanel>();
+    }
+}
+
+/// Builds the Fishdex panel when it opens, rebuilds it when the Fishdex changes while it is
+/// open, and removes it once `FishdexPanel` is gone
+pub fn update_fishdex_panel(
+    mut commands: Commands,
+    panel: Option<Res<FishdexPanel>>,
+    fishdex: Res<Fishdex>,
+    catalogue: Res<SpeciesCatalogue>,
+    roster: Res<UncleRoster>,
+    mut sprites: ResMut<FishSprites>,
+    mut images: ResMut<Assets<Image>>,
+    screen_q: Query<Entity, With<FishdexScreen>>,
+) {
+    if let Some(panel) = &panel {
+        if !panel.is_added() && !fishdex.is_changed() && !screen_q.is_empty() {
+            return;
+        }
+    }
+    for entity in &screen_q {
+        commands.entity(entity).despawn_recursive();
+    }
+    if let Some(panel) = panel {
+        let mut sprite_of = |def: &SpeciesDef, color: FishColor| sprites.get(FishLook::of_species(def, color), &mut images);
+        spawn_fishdex(&mut commands, &panel, &fishdex, &catalogue, &roster, &mut sprite_of);
+    }
+}
+
+/// Scrolls the entry list with the mouse wheel
+pub fn scroll_fishdex(
+    mut wheel: EventReader<MouseWheel>,
+    panel: Option<ResMut<FishdexPanel>>,
+    mut list_q: Query<(&mut Style, &Parent, &Node), With<FishdexList>>,
+    viewport_q: Query<&Node>,
+) {
+    let Some(mut panel) = panel else {
+        wheel.clear();
+        return;
+    };
+    for event in wheel.read() {
+        let dy = match event.unit {
+            MouseScrollUnit::Line => event.y * HISTORY_SCROLL_SPEED,
+            MouseScrollUnit::Pixel => event.y,
+        };
+
+        for (mut style, parent, list_node) in list_q.iter_mut() {
+            let Ok(viewport) = viewport_q.get(parent.get()) else {
+                continue;
+            };
+            let max_offset = (list_node.size().y - viewport.size().y).max(0.0);
+            panel.offset = (panel.offset - dy).clamp(0.0, max_offset);
+            style.top = Val::Px(-panel.offset);
+        }
+    }
+}
+
+fn spawn_fishdex(
+    commands: &mut Commands,
+    panel: &FishdexPanel,
+    fishdex: &Fishdex,
+    catalogue: &SpeciesCatalogue,
+    roster: &UncleRoster,
+    sprite_of: &mut impl FnMut(&SpeciesDef, FishColor) -> Handle<Image>,
+) {
+    let uncle_name = |uncle_type: &UncleType| roster.get(uncle_type).map_or(uncle_type.to_string(), |def| def.name.clone());
+
+    // Over the menu screens; `Interaction` keeps the mouse wheel from zooming the camera
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            background_color: Color::srgba(0.0, 0.0, 0.0, 0.6).into(),
+            focus_policy: FocusPolicy::Block,
+            z_index: ZIndex::Global(25),
+            ..default()
+        },
+        Interaction::default(),
+        FishdexScreen,
+    ))
+    .with_children(|overlay| {
+        overlay.spawn(NodeBundle {
+            style: Style {
+                width: Val::Px(620.0),
+                height: Val::Percent(80.0),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(20.0)),
+                row_gap: Val::Px(10.0),
+                ..default()
+            },
+            background_color: Color::srgba(0.118, 0.161, 0.231, 0.98).into(),
+            ..default()
+        })
+        .with_children(|panel_node| {
+            // Title and CLOSE
+            panel_node.spawn(NodeBundle {
+                style: Style {
+                    justify_content: JustifyContent::SpaceBetween,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                ..default()
+            })
+            .with_children(|header| {
+                header.spawn(TextBundle::from_section(
+                    format!("📖 FISHDEX  ({} found)", fishdex.len()),
+                    TextStyle {
+                        font_size: 22.0,
+                        color: TEXT_COLOR,
+                        ..default()
+                    },
+                ));
+                header.spawn((
+                    ButtonBundle {
+                        style: Style {
+                            padding: UiRect::axes(Val::Px(12.0), Val::Px(6.0)),
+                            ..default()
+                        },
+                        background_color: Color::srgb(0.392, 0.455, 0.545).into(),
+                        ..default()
+                    },
+                    FishdexCloseButton,
+                ))
+                .with_children(|button| {
+                    button.spawn(TextBundle::from_section(
+                        "CLOSE (F)",
+                        TextStyle {
+                            font_size: 12.0,
+                            color: TEXT_COLOR,
+                            ..default()
+                        },
+                    ));
+                });
+            });
+
+            // Completion by rarity
+            panel_node.spawn(NodeBundle {
+                style: Style {
+                    column_gap: Val::Px(16.0),
+                    ..default()
+                },
+                ..default()
+            })
+            .with_children(|row| {
+                for rarity in FishRarity::ALL {
+                    let (found, total) = fishdex.completion(catalogue, rarity);
+                    let percent = if total == 0 { 100.0 } else { found as f32 / total as f32 * 100.0 };
+                    row.spawn(TextBundle::from_section(
+                        format!("{}: {}/{} ({:.0}%)", rarity.name(), found, total, percent),
+                        TextStyle {
+                            font_size: 13.0,
+                            color: rarity.color(),
+                            ..default()
+                        },
+                    ));
+                }
+            });
+
+            // Scroll viewport with one row per species and color
+            panel_node.spawn(NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    flex_grow: 1.0,
+                    flex_direction: FlexDirection::Column,
+                    overflow: Overflow::clip_y(),
+                    ..default()
+                },
+                background_color: Color::srgba(0.059, 0.090, 0.165, 0.5).into(),
+                ..default()
+            })
+            .with_children(|viewport| {
+                viewport.spawn((
+                    NodeBundle {
+                        style: Style {
+                            width: Val::Percent(100.0),
+                            flex_direction: FlexDirection::Column,
+                            row_gap: Val::Px(4.0),
+                            padding: UiRect::all(Val::Px(6.0)),
+                            top: Val::Px(-panel.offset),
+                            ..default()
+                        },
+                        ..default()
+                    },
+                    FishdexList,
+                ))
+                .with_children(|list| {
+                    for def in catalogue.iter() {
+                        for &color in &def.colors {
+                            let sprite = sprite_of(def, color);
+                            spawn_entry_row(list, def, color, fishdex.get(&def.id, color), sprite, &uncle_name);
+                        }
+                    }
+                });
+            });
+        });
+    });
+}
+
+fn spawn_entry_row(
+    list: &mut ChildBuilder,
+    def: &SpeciesDef,
+    color: FishColor,
+    entry: Option<&FishdexEntry>,
+    sprite: Handle<Image>,
+    uncle_name: &impl Fn(&UncleType) -> String,
+) {
+    list.spawn(NodeBundle {
+        style: Style {
+            align_items: AlignItems::Center,
+            column_gap: Val::Px(12.0),
+            padding: UiRect::all(Val::Px(6.0)),
+            ..default()
+        },
+        background_color: Color::srgba(0.118, 0.161, 0.231, 0.6).into(),
+        ..default()
+    })
+    .with_children(|row| {
+        // Undiscovered entries only show their outline
+        let image = UiImage::new(sprite);
+        row.spawn(ImageBundle {
+            style: Style {
+                width: Val::Px(FISH_SPRITE_WIDTH as f32 * 1.25),
+                height: Val::Px(FISH_SPRITE_HEIGHT as f32 * 1.25),
+                flex_shrink: 0.0,
+                ..default()
+            },
+            image: if entry.is_some() { image } else { image.with_color(SILHOUETTE_COLOR) },
+            ..default()
+        });
+
+        row.spawn(NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|text| {
+            let Some(entry) = entry else {
+                text.spawn(TextBundle::from_section(
+                    format!("???  ({} {})", def.rarity.name(), def.shape.name()),
+                    TextStyle {
+                        font_size: 13.0,
+                        color: MUTED_COLOR,
+                        ..default()
+                    },
+                ));
+                return;
+            };
+            text.spawn(TextBundle::from_section(
+                format!("{} {}", color.name(), def.name),
+                TextStyle {
+                    font_size: 13.0,
+                    color: def.rarity.color(),
+                    ..default()
+                },
+            ));
+            text.spawn(TextBundle::from_section(
+                format!(
+                    "Caught {} • Escaped {} • First on day {} by {}",
+                    entry.caught, entry.escaped, entry.first_caught_day, uncle_name(&entry.first_caught_by)
+                ),
+                TextStyle {
+                    font_size: 10.0,
+                    color: MUTED_COLOR,
+                    ..default()
+                },
+            ));
+            text.spawn(TextBundle::from_section(
+                format!(
+                    "Largest {} ({:.0} cm, {}) • Best {}g ({})",
+                    format_weight(entry.largest.weight_kg),
+                    entry.largest.length_cm,
+                    uncle_name(&entry.largest.caught_by_uncle),
+                    entry.most_valuable.value,
+                    uncle_name(&entry.most_valuable.caught_by_uncle)
+                ),
+                TextStyle {
+                    font_size: 10.0,
+                    color: TEXT_COLOR,
+                    ..default()
+                },
+            ));
+        });
+    });
+}
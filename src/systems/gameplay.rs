@@ -1,59 +1,31 @@
 use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
 use rand::Rng;
-use crate::components::{Tile, TileType, Uncle, UncleType, FishRarity, Fish, UncleBasket, SelectedUncleMarker};
+use std::time::Duration;
+use crate::actions::{PendingActions, PlayerAction};
+use crate::components::{Tile, TileType, Uncle, Fish, SelectedUncleMarker};
 use crate::constants::*;
-use crate::resources::{GameState, WorldSeed, SelectedUncle, DayNightCycle};
-
-/// Helper function to check if a tile position is near water
-fn is_tile_near_water(x: usize, y: usize, tiles_q: &Query<(Entity, &Tile, &Transform)>) -> bool {
-    let directions = [
-        (-1i32, -1i32), (0, -1), (1, -1),
-        (-1, 0),                 (1, 0),
-        (-1, 1),        (0, 1),  (1, 1),
-    ];
-
-    for (dx, dy) in directions {
-        let check_x = x as i32 + dx;
-        let check_y = y as i32 + dy;
-
-        if check_x >= 0 && check_x < TILE_WIDTH as i32 && check_y >= 0 && check_y < TILE_HEIGHT as i32 {
-            for (_entity, tile, _transform) in tiles_q.iter() {
-                if tile.x == check_x as usize && tile.y == check_y as usize && tile.tile_type == TileType::Water {
-                    return true;
-                }
-            }
-        }
-    }
-
-    false
-}
-
-/// Spawns an uncle entity at a given position with sprite support
-fn spawn_uncle(
+use crate::events::{CashOutRequest, CashOutScope, DockBuilt, FishCaught, FishDiscarded, FishDiscovered, FishEscaped, RecordBroken, UnclePlaced};
+use crate::fishdex::Fishdex;
+use crate::fishing::{FishingGrounds, FishingSpot};
+use crate::resources::{DayNightCycle, GameState, RngStream, WorldSeed, SelectedUncle, FishingStats, SpeciesRecords};
+use crate::grid::TileGrid;
+use crate::species::SpeciesCatalogue;
+use crate::systems::tilemap::{load_chunks_around, set_tile};
+use crate::uncles::{UncleDef, UncleRoster};
+use crate::worldgen::WorldGenConfig;
+
+/// Spawns an uncle entity at a given position, drawn with its definition's sprite.
+/// Without a sprite or an `AssetServer` (headless runs) the colored letter fallback is used.
+pub fn spawn_uncle(
     commands: &mut Commands,
-    asset_server: &Res<AssetServer>,
-    uncle_type: UncleType,
+    asset_server: Option<&AssetServer>,
+    uncle: Uncle,
+    def: &UncleDef,
     world_x: f32,
     world_y: f32,
-    tile_x: usize,
-    tile_y: usize,
-) {
-    let speed_ms = uncle_type.speed_ms();
-    let basket_capacity = uncle_type.basket_capacity();
-    
-    let uncle = Uncle {
-        uncle_type,
-        x: tile_x,
-        y: tile_y,
-        fishing_timer: Timer::from_seconds(
-            speed_ms as f32 / 1000.0,
-            TimerMode::Repeating,
-        ),
-        basket: UncleBasket::new(basket_capacity),
-    };
-
-    if let Some(asset_path) = uncle_type.asset_path() {
+) -> Entity {
+    if let (Some(asset_path), Some(asset_server)) = (def.sprite.clone(), asset_server) {
         commands.spawn((
             uncle,
             SpriteBundle {
@@ -65,13 +37,13 @@ fn spawn_uncle(
                 },
                 ..default()
             },
-        ));
+        )).id()
     } else {
         let uncle_entity = commands.spawn((
             uncle,
             SpriteBundle {
                 sprite: Sprite {
-                    color: uncle_type.color(),
+                    color: def.color(),
                     custom_size: Some(Vec2::new(UNCLE_SPRITE_SIZE, UNCLE_SPRITE_SIZE)),
                     ..default()
                 },
@@ -83,7 +55,7 @@ fn spawn_uncle(
         commands.entity(uncle_entity).with_children(|parent| {
             parent.spawn(Text2dBundle {
                 text: Text::from_section(
-                    uncle_type.letter(),
+                    def.letter.clone(),
                     TextStyle {
                         font_size: 24.0,
                         color: Color::srgb(0.1, 0.1, 0.15),
@@ -94,21 +66,20 @@ fn spawn_uncle(
                 ..default()
             });
         });
+
+        uncle_entity
     }
 }
 
-/// Handles mouse clicks for placing uncles OR selecting placed uncles
+/// Handles mouse clicks for placing uncles OR selecting placed uncles.
+/// Clicking water builds a dock there instead.
 pub fn handle_uncle_placement(
     mouse_button: Res<ButtonInput<MouseButton>>,
     windows: Query<&Window, With<PrimaryWindow>>,
     camera_q: Query<(&Camera, &GlobalTransform)>,
-    tiles_q: Query<(Entity, &Tile, &Transform)>,
-    uncles_q: Query<(Entity, &Uncle, &Transform), Without<Tile>>,
-    mut commands: Commands,
-    asset_server: Res<AssetServer>,
-    mut game_state: ResMut<GameState>,
-    selected_uncle: Res<SelectedUncle>,
-    selected_marker_q: Query<Entity, With<SelectedUncleMarker>>,
+    grid: Res<TileGrid>,
+    uncles_q: Query<(&Uncle, &Transform)>,
+    mut actions: ResMut<PendingActions>,
 ) {
     if !mouse_button.just_pressed(MouseButton::Left) {
         return;
@@ -120,7 +91,7 @@ pub fn handle_uncle_placement(
     if let Some(cursor_position) = window.cursor_position() {
         if let Some(world_pos) = camera.viewport_to_world_2d(camera_transform, cursor_position) {
             // First check if clicking an existing uncle
-            for (uncle_entity, _uncle, uncle_transform) in uncles_q.iter() {
+            for (uncle, uncle_transform) in uncles_q.iter() {
                 let uncle_pos = uncle_transform.translation.truncate();
                 let half_size = UNCLE_SPRITE_SIZE / 2.0;
 
@@ -130,285 +101,357 @@ pub fn handle_uncle_placement(
                     && world_pos.y <= uncle_pos.y + half_size
                 {
                     // Clicked an uncle! Select it
-                    // Remove previous selection marker
-                    for entity in selected_marker_q.iter() {
-                        commands.entity(entity).remove::<SelectedUncleMarker>();
-                    }
-                    // Add marker to this uncle
-                    commands.entity(uncle_entity).insert(SelectedUncleMarker);
+                    actions.push(PlayerAction::SelectPlacedUncle { x: uncle.x, y: uncle.y });
                     return;
                 }
             }
 
             // Not clicking uncle, try to place new one
-            for (_tile_entity, tile, tile_transform) in tiles_q.iter() {
-                let tile_pos = tile_transform.translation.truncate();
-                let half_size = TILE_SIZE / 2.0;
-
-                if world_pos.x >= tile_pos.x - half_size
-                    && world_pos.x <= tile_pos.x + half_size
-                    && world_pos.y >= tile_pos.y - half_size
-                    && world_pos.y <= tile_pos.y + half_size
-                {
-                    if tile.tile_type != TileType::Land {
-                        return;
-                    }
+            let (x, y) = TileGrid::world_to_tile(world_pos);
+            if grid.get(x, y).is_some_and(|t| t.is_water()) {
+                actions.push(PlayerAction::BuildDock { x, y });
+            } else {
+                actions.push(PlayerAction::PlaceUncle { x, y });
+            }
+        }
+    }
+}
 
-                    let is_occupied = uncles_q.iter().any(|(_, u, _)| u.x == tile.x && u.y == tile.y);
-                    if is_occupied {
-                        return;
-                    }
+/// Queues keyboard actions: R removes a fish, Space cashes out the selected uncle, C cashes out all
+pub fn handle_gameplay_keys(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut actions: ResMut<PendingActions>,
+) {
+    if keyboard.just_pressed(KeyCode::KeyR) {
+        actions.push(PlayerAction::RemoveLowestFish);
+    }
+    if keyboard.just_pressed(KeyCode::Space) {
+        actions.push(PlayerAction::CashOutSelected);
+    }
+    if keyboard.just_pressed(KeyCode::KeyC) {
+        actions.push(PlayerAction::CashOutAll);
+    }
+}
 
-                    if !is_tile_near_water(tile.x, tile.y, &tiles_q) {
-                        return;
-                    }
+/// Applies queued player actions at the start of a fixed tick
+pub fn apply_player_actions(
+    mut actions: ResMut<PendingActions>,
+    mut commands: Commands,
+    asset_server: Option<Res<AssetServer>>,
+    mut tiles_q: Query<(&mut Tile, &mut Sprite)>,
+    mut uncles_q: Query<(Entity, &mut Uncle)>,
+    selected_marker_q: Query<Entity, With<SelectedUncleMarker>>,
+    mut game_state: ResMut<GameState>,
+    mut grid: ResMut<TileGrid>,
+    mut grounds: ResMut<FishingGrounds>,
+    world_seed: Res<WorldSeed>,
+    // What newly reached chunks are generated from
+    (config, catalogue): (Res<WorldGenConfig>, Res<SpeciesCatalogue>),
+    // The hiring choice and what can be hired
+    (mut selected_uncle, roster): (ResMut<SelectedUncle>, Res<UncleRoster>),
+    mut cash_out_requests: EventWriter<CashOutRequest>,
+    mut placed: EventWriter<UnclePlaced>,
+    mut docks_built: EventWriter<DockBuilt>,
+    mut discarded: EventWriter<FishDiscarded>,
+) {
+    // Uncles hired this tick are not in the query yet
+    let mut placed_this_tick: Vec<(i32, i32)> = Vec::new();
+    // Selection changes are deferred too, so track the latest one here
+    let mut selected: Option<Entity> = selected_marker_q.iter().next();
+
+    for action in actions.queue.drain(..) {
+        match action {
+            PlayerAction::SelectUncleType(uncle_type) => {
+                // Uncles missing from the roster (e.g. a replay from a modded game) cannot be hired
+                if roster.get(&uncle_type).is_some() {
+                    selected_uncle.uncle_type = uncle_type;
+                }
+            }
+            PlayerAction::PlaceUncle { x, y } => {
+                let Some(def) = roster.get(&selected_uncle.uncle_type) else {
+                    continue;
+                };
+                // Rocks, water and tiles with no water in casting range are rejected here
+                let radius = def.casting_radius;
+                load_chunks_around(&mut grid, &mut grounds, world_seed.seed, &config, &catalogue, (x, y), radius);
+                let Some(spot) = FishingSpot::at(x, y, radius, grid.get(x, y), &grounds) else {
+                    continue;
+                };
+
+                let is_occupied = uncles_q.iter().any(|(_, u)| u.x == x && u.y == y)
+                    || placed_this_tick.contains(&(x, y));
+                if is_occupied {
+                    continue;
+                }
 
-                    let cost = selected_uncle.uncle_type.cost();
-                    if game_state.gold < cost {
-                        return;
-                    }
+                let cost = def.cost + spot.stand.placement_cost();
+                if game_state.gold < cost {
+                    continue;
+                }
 
-                    game_state.gold -= cost;
-                    spawn_uncle(
-                        &mut commands,
-                        &asset_server,
-                        selected_uncle.uncle_type,
-                        tile_transform.translation.x,
-                        tile_transform.translation.y,
-                        tile.x,
-                        tile.y,
-                    );
-                    return;
+                game_state.gold -= cost;
+                if spot.stand == TileType::Forest {
+                    set_tile(&mut grid, &mut tiles_q, x, y, TileType::Land);
+                }
+                let world_pos = TileGrid::tile_to_world(x, y);
+                let uncle = spawn_uncle(
+                    &mut commands,
+                    asset_server.as_deref(),
+                    Uncle::new(def, x, y),
+                    def,
+                    world_pos.x,
+                    world_pos.y,
+                );
+                placed.send(UnclePlaced { uncle, uncle_type: def.id.clone(), x, y, cost });
+                placed_this_tick.push((x, y));
+            }
+            PlayerAction::BuildDock { x, y } => {
+                load_chunks_around(&mut grid, &mut grounds, world_seed.seed, &config, &catalogue, (x, y), 1);
+                if !grid.get(x, y).is_some_and(|t| t.can_build_dock()) {
+                    continue;
+                }
+                // Docks grow out from the shore (or another dock), never diagonally
+                let on_shore = grid
+                    .edge_neighbours(x, y)
+                    .any(|(nx, ny)| grid.get(nx, ny).is_some_and(|t| t.can_place_uncle()));
+                if !on_shore || game_state.gold < DOCK_COST {
+                    continue;
+                }
+
+                game_state.gold -= DOCK_COST;
+                set_tile(&mut grid, &mut tiles_q, x, y, TileType::Dock);
+                grounds.remove(x, y);
+                docks_built.send(DockBuilt { x, y, cost: DOCK_COST });
+            }
+            PlayerAction::SelectPlacedUncle { x, y } => {
+                let Some((uncle_entity, _)) = uncles_q.iter().find(|(_, u)| u.x == x && u.y == y) else {
+                    continue;
+                };
+                // Remove previous selection marker
+                for entity in selected_marker_q.iter() {
+                    commands.entity(entity).remove::<SelectedUncleMarker>();
+                }
+                // Add marker to this uncle
+                commands.entity(uncle_entity).insert(SelectedUncleMarker);
+                selected = Some(uncle_entity);
+            }
+            PlayerAction::RemoveLowestFish => {
+                if let Some((entity, mut uncle)) = selected.and_then(|e| uncles_q.get_mut(e).ok()) {
+                    if let Some(fish) = remove_lowest_fish(&mut uncle) {
+                        discarded.send(FishDiscarded { uncle: entity, fish });
+                    }
                 }
             }
+            // Sold by `economy::handle_cash_out_requests` right after this system
+            PlayerAction::CashOutSelected => {
+                cash_out_requests.send(CashOutRequest { scope: CashOutScope::Selected });
+            }
+            PlayerAction::CashOutAll => {
+                cash_out_requests.send(CashOutRequest { scope: CashOutScope::All });
+            }
         }
     }
 }
 
 /// Updates fishing timers and adds fish to individual uncle baskets
 pub fn uncle_fishing_system(
-    mut uncles_q: Query<&mut Uncle>,
+    mut uncles_q: Query<(Entity, &mut Uncle)>,
+    grid: Res<TileGrid>,
+    mut grounds: ResMut<FishingGrounds>,
     mut world_seed: ResMut<WorldSeed>,
+    roster: Res<UncleRoster>,
+    catalogue: Res<SpeciesCatalogue>,
+    day_night: Res<DayNightCycle>,
+    mut caught: EventWriter<FishCaught>,
     time: Res<Time>,
 ) {
-    for mut uncle in uncles_q.iter_mut() {
-        // Skip if basket is full
-        if uncle.basket.is_full() {
+    for (entity, mut uncle) in uncles_q.iter_mut() {
+        let Some(def) = roster.get(&uncle.uncle_type) else {
             continue;
-        }
+        };
+        let Some(spot) = FishingSpot::at(uncle.x, uncle.y, def.casting_radius, grid.get(uncle.x, uncle.y), &grounds) else {
+            continue;
+        };
+        caught.send_batch(
+            advance_fishing(&mut uncle, def, time.delta(), day_night.is_day, &mut world_seed, &mut grounds, &catalogue, &spot)
+                .into_iter()
+                .map(|fish| FishCaught { uncle: entity, uncle_type: def.id.clone(), fish }),
+        );
+    }
+}
+
+/// Ticks one uncle's fishing timer, returning the fish that landed in the basket.
+/// The timer runs faster or slower depending on the water in reach, how much fish is left in it
+/// and how much of it bites at this time of day.
+pub fn advance_fishing(
+    uncle: &mut Uncle,
+    def: &UncleDef,
+    delta: Duration,
+    is_day: bool,
+    world_seed: &mut WorldSeed,
+    grounds: &mut FishingGrounds,
+    catalogue: &SpeciesCatalogue,
+    spot: &FishingSpot,
+) -> Vec<Fish> {
+    let mut caught = Vec::new();
+
+    // Skip if basket is full
+    if uncle.basket.is_full() {
+        return caught;
+    }
 
-        uncle.fishing_timer.tick(time.delta());
+    uncle.fishing_timer.tick(delta.mul_f32(spot.catch_speed(grounds, def.rare_bonus, is_day)));
 
-        if uncle.fishing_timer.just_finished() {
-            // Generate a fish and add to uncle's basket
-            let fish = generate_fish(&mut world_seed, uncle.uncle_type);
-            uncle.basket.add_fish(fish);
+    // Each uncle draws from its own stream, so nothing else going on changes its catches
+    let rng = world_seed.rng(RngStream::Fishing { x: uncle.x, y: uncle.y });
+    for _ in 0..uncle.fishing_timer.times_finished_this_tick() {
+        // Stop before taking a fish from the water that would not fit
+        if uncle.basket.is_full() {
+            break;
+        }
+        // Generate a fish and add to uncle's basket
+        let Some(fish) = generate_fish(rng, def, is_day, grounds, catalogue, spot) else {
+            break;
+        };
+        if uncle.basket.add_fish(fish.clone()) {
+            caught.push(fish);
         }
     }
+    caught
 }
 
-/// Generates a fish with random attributes
+/// Takes a fish from the water in reach of `spot` and rolls its length, traits and value,
+/// or returns `None` if nothing there is biting
 fn generate_fish(
-    world_seed: &mut WorldSeed,
-    uncle_type: UncleType,
-) -> Fish {
-    let rng = &mut world_seed.rng;
-
-    let rare_threshold = RARE_CHANCE + uncle_type.rare_bonus();
-    let uncommon_threshold = rare_threshold + UNCOMMON_CHANCE;
-
-    let roll = rng.gen::<f32>();
-    let rarity = if roll < rare_threshold {
-        FishRarity::Rare
-    } else if roll < uncommon_threshold {
-        FishRarity::Uncommon
-    } else {
-        FishRarity::Common
-    };
-
-    let color = FISH_COLORS[rng.gen_range(0..FISH_COLORS.len())];
-    let pattern = FISH_PATTERNS[rng.gen_range(0..FISH_PATTERNS.len())];
-    let shape = FISH_SHAPES[rng.gen_range(0..FISH_SHAPES.len())];
-    let name = format!("{} {} {}fish", color, pattern, shape);
-
-    let value = match rarity {
-        FishRarity::Common => rng.gen_range(COMMON_VALUE_MIN..=COMMON_VALUE_MAX),
-        FishRarity::Uncommon => rng.gen_range(UNCOMMON_VALUE_MIN..=UNCOMMON_VALUE_MAX),
-        FishRarity::Rare => rng.gen_range(RARE_VALUE_MIN..=RARE_VALUE_MAX),
-    };
-
-    Fish {
-        name,
-        rarity,
-        value,
-        time_alive: 0.0,
-        failed_escape_attempts: 0,
-        caught_by_uncle: uncle_type,
-    }
+    rng: &mut impl Rng,
+    def: &UncleDef,
+    is_day: bool,
+    grounds: &mut FishingGrounds,
+    catalogue: &SpeciesCatalogue,
+    spot: &FishingSpot,
+) -> Option<Fish> {
+    let species = catalogue.at(grounds.take_fish(&spot.reach, def.rare_bonus, is_day, rng)?.index());
+    let length_cm = species.roll_length(rng);
+    let traits = species.roll_traits(length_cm, rng);
+    let value = species.roll_value(species.weight_at(length_cm), rng);
+
+    Some(Fish::of_species(species, traits, length_cm, value, def.id.clone()))
+}
+
+/// Fish populations regrow over in-game days
+pub fn regrow_fishing_grounds(
+    mut grounds: ResMut<FishingGrounds>,
+    time: Res<Time>,
+) {
+    grounds.regrow(time.delta_seconds() / DAY_LENGTH_SECONDS);
 }
 
 /// Fish escape system now works on individual uncle baskets
 pub fn fish_escape_system(
-    mut uncles_q: Query<&mut Uncle>,
+    mut uncles_q: Query<(Entity, &mut Uncle)>,
     mut world_seed: ResMut<WorldSeed>,
+    roster: Res<UncleRoster>,
+    mut escaped: EventWriter<FishEscaped>,
     time: Res<Time>,
 ) {
-    let rng = &mut world_seed.rng;
     let delta = time.delta_seconds();
 
-    for mut uncle in uncles_q.iter_mut() {
-        let mut escaped_indices = Vec::new();
-
-        // Check each fish in this uncle's basket
-        for (i, fish) in uncle.basket.fish.iter_mut().enumerate() {
-            fish.time_alive += delta;
-            let escape_chance_per_second = fish.calculate_escape_chance();
-            let escape_chance_this_frame = escape_chance_per_second * delta;
-
-            if rng.gen::<f32>() < escape_chance_this_frame {
-                escaped_indices.push(i);
-            } else {
-                fish.failed_escape_attempts += 1;
-            }
-        }
-
-        // Remove escaped fish
-        for &i in escaped_indices.iter().rev() {
-            uncle.basket.fish.remove(i);
-        }
+    for (entity, mut uncle) in uncles_q.iter_mut() {
+        let Some(def) = roster.get(&uncle.uncle_type) else {
+            continue;
+        };
+        escaped.send_batch(
+            advance_escapes(&mut uncle, def, delta, &mut world_seed)
+                .into_iter()
+                .map(|fish| FishEscaped { uncle: entity, uncle_type: def.id.clone(), fish }),
+        );
     }
 }
 
-/// Remove lowest value fish from selected uncle's basket (R key)
-/// Strategic use: free up space for potentially better fish
-pub fn remove_fish_from_basket(
-    keyboard: Res<ButtonInput<KeyCode>>,
-    mut uncles_q: Query<&mut Uncle, With<SelectedUncleMarker>>,
+/// Keeps `FishingStats` in step with catch and escape events
+pub fn record_fishing_stats(
+    mut stats: ResMut<FishingStats>,
+    mut caught: EventReader<FishCaught>,
+    mut escaped: EventReader<FishEscaped>,
 ) {
-    if !keyboard.just_pressed(KeyCode::KeyR) {
-        return;
+    for event in caught.read() {
+        stats.record_catch(&event.fish);
     }
-
-    for mut uncle in uncles_q.iter_mut() {
-        if uncle.basket.fish.is_empty() {
-            continue;
-        }
-
-        // Find lowest value fish
-        let lowest_idx = uncle.basket.fish
-            .iter()
-            .enumerate()
-            .min_by_key(|(_, fish)| fish.value)
-            .map(|(idx, _)| idx);
-
-        if let Some(idx) = lowest_idx {
-            let removed = uncle.basket.fish.remove(idx);
-            println!("🗑️ Removed {} ({}g) to make space", removed.name, removed.value);
-        }
+    for event in escaped.read() {
+        stats.record_escape(&event.fish);
     }
 }
 
-/// Cash out selected uncle's basket
-pub fn cash_out_selected_uncle(
-    keyboard: Res<ButtonInput<KeyCode>>,
-    mut uncles_q: Query<&mut Uncle, With<SelectedUncleMarker>>,
-    mut game_state: ResMut<GameState>,
-    mut day_night: ResMut<DayNightCycle>,
+/// Keeps `SpeciesRecords` up to date with every catch and announces broken records
+pub fn track_species_records(
+    mut records: ResMut<SpeciesRecords>,
+    day_night: Res<DayNightCycle>,
+    mut caught: EventReader<FishCaught>,
+    mut broken: EventWriter<RecordBroken>,
 ) {
-    if !keyboard.just_pressed(KeyCode::Space) {
-        return;
-    }
-
-    // Check cooldown
-    if game_state.cash_out_cooldown > 0.0 {
-        return;
-    }
-
-    // Check daily limit
-    if day_night.cashouts_remaining == 0 {
-        println!("❌ No cash-outs remaining! Wait for day {} ({})", day_night.day_number + 1, day_night.time_string());
-        return;
-    }
-
-    // Cash out the selected uncle
-    for mut uncle in uncles_q.iter_mut() {
-        if uncle.basket.fish.is_empty() {
-            continue;
+    for event in caught.read() {
+        if let Some(previous_kg) = records.offer(&event.fish, day_night.day_number) {
+            broken.send(RecordBroken {
+                uncle: event.uncle,
+                uncle_type: event.uncle_type.clone(),
+                fish: event.fish.clone(),
+                previous_kg,
+            });
         }
-
-        let total_value = uncle.basket.total_value();
-        let gold_earned = (total_value as f32 * game_state.multiplier) as u32;
-        let fish_count = uncle.basket.fish.len() as u32;
-
-        game_state.gold += gold_earned;
-        game_state.fish_count += fish_count;
-        uncle.basket.cash_out();
-
-        game_state.multiplier = (game_state.multiplier + MULTIPLIER_INCREMENT).min(MAX_MULTIPLIER);
-        game_state.cash_out_cooldown = CASH_OUT_COOLDOWN;
-        day_night.cashouts_remaining -= 1;
-        
-        println!("💰 Cashed out {} fish for {}g! Remaining: {}/{}", 
-                 fish_count, gold_earned, day_night.cashouts_remaining, day_night.max_cashouts_per_day);
-        break;
     }
 }
 
-/// Cash out ALL uncles at once
-pub fn cash_out_all_uncles(
-    keyboard: Res<ButtonInput<KeyCode>>,
-    mut uncles_q: Query<&mut Uncle>,
-    mut game_state: ResMut<GameState>,
-    mut day_night: ResMut<DayNightCycle>,
+/// Adds every catch and escape to the `Fishdex` and announces new entries
+pub fn record_fishdex(
+    mut fishdex: ResMut<Fishdex>,
+    day_night: Res<DayNightCycle>,
+    mut caught: EventReader<FishCaught>,
+    mut escaped: EventReader<FishEscaped>,
+    mut discovered: EventWriter<FishDiscovered>,
 ) {
-    // Must press A key to trigger
-    if !keyboard.just_pressed(KeyCode::KeyA) {
-        return;
-    }
-
-    // Check cooldown
-    if game_state.cash_out_cooldown > 0.0 {
-        return;
+    for event in caught.read() {
+        if fishdex.record_catch(&event.fish, day_night.day_number) {
+            discovered.send(FishDiscovered {
+                uncle: event.uncle,
+                uncle_type: event.uncle_type.clone(),
+                fish: event.fish.clone(),
+            });
+        }
     }
-
-    // Check daily limit
-    if day_night.cashouts_remaining == 0 {
-        println!("❌ No cash-outs remaining! Wait for day {} ({})", day_night.day_number + 1, day_night.time_string());
-        return;
+    for event in escaped.read() {
+        fishdex.record_escape(&event.fish);
     }
+}
 
-    let mut total_gold = 0;
-    let mut total_fish = 0;
+/// Ages every fish in one basket and resolves its flops, returning the fish that got away
+pub fn advance_escapes(uncle: &mut Uncle, def: &UncleDef, delta: f32, world_seed: &mut WorldSeed) -> Vec<Fish> {
+    let rng = world_seed.rng(RngStream::Escapes);
+    let mut escaped_indices = Vec::new();
 
-    for mut uncle in uncles_q.iter_mut() {
-        if !uncle.basket.fish.is_empty() {
-            let value = uncle.basket.total_value();
-            total_gold += value;
-            total_fish += uncle.basket.fish.len() as u32;
-            uncle.basket.cash_out();
+    // Check each fish in this uncle's basket
+    for (i, fish) in uncle.basket.fish.iter_mut().enumerate() {
+        if fish.advance(delta, def.retention, rng) {
+            escaped_indices.push(i);
         }
     }
 
-    if total_fish > 0 {
-        let gold_earned = (total_gold as f32 * game_state.multiplier) as u32;
-        game_state.gold += gold_earned;
-        game_state.fish_count += total_fish;
-        game_state.multiplier = (game_state.multiplier + MULTIPLIER_INCREMENT).min(MAX_MULTIPLIER);
-        game_state.cash_out_cooldown = CASH_OUT_COOLDOWN;
-        day_night.cashouts_remaining -= 1;
-        
-        println!("💰 Cashed out ALL: {} fish for {}g! Remaining: {}/{}", 
-                 total_fish, gold_earned, day_night.cashouts_remaining, day_night.max_cashouts_per_day);
-    }
+    // Remove escaped fish
+    escaped_indices
+        .into_iter()
+        .rev()
+        .map(|i| uncle.basket.fish.remove(i))
+        .collect()
 }
 
-/// Updates the cash out cooldown timer
-pub fn cooldown_update_system(
-    mut game_state: ResMut<GameState>,
-    time: Res<Time>,
-) {
-    if game_state.cash_out_cooldown > 0.0 {
-        game_state.cash_out_cooldown -= time.delta_seconds();
-        game_state.cash_out_cooldown = game_state.cash_out_cooldown.max(0.0);
-    }
+/// Remove lowest value fish from an uncle's basket (R key)
+/// Strategic use: free up space for potentially better fish
+pub fn remove_lowest_fish(uncle: &mut Uncle) -> Option<Fish> {
+    // Find lowest value fish
+    let lowest_idx = uncle.basket.fish
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, fish)| fish.value)
+        .map(|(idx, _)| idx)?;
+
+    uncle.basket.remove_fish(lowest_idx)
 }
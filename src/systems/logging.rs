@@ -0,0 +1,60 @@
+use bevy::prelude::*;
+use crate::components::format_weight;
+use crate::events::*;
+use crate::resources::DayNightCycle;
+
+/// Prints notable gameplay events to stdout
+pub fn log_gameplay_events(
+    day_night: Res<DayNightCycle>,
+    mut day_started: EventReader<DayStarted>,
+    mut daylight_changed: EventReader<DaylightChanged>,
+    mut discarded: EventReader<FishDiscarded>,
+    mut discovered: EventReader<FishDiscovered>,
+    mut records: EventReader<RecordBroken>,
+    mut docks_built: EventReader<DockBuilt>,
+    mut cashed_out: EventReader<CashedOut>,
+    mut denied: EventReader<CashOutDenied>,
+) {
+    for event in day_started.read() {
+        println!("☀️ Day {} begins! Cash-outs refreshed: {}", event.day_number, event.cashouts_remaining);
+    }
+
+    for event in daylight_changed.read() {
+        if event.is_day {
+            println!("🌅 Dawn - daytime begins");
+        } else {
+            println!("🌆 Dusk - nighttime begins");
+        }
+    }
+
+    for event in discarded.read() {
+        println!("🗑️ Removed {} ({}g) to make space", event.fish.name, event.fish.value);
+    }
+
+    for event in discovered.read() {
+        println!("📖 New Fishdex entry: {} {}", event.fish.traits.color.name(), event.fish.name);
+    }
+
+    for event in records.read() {
+        println!("🏆 New {} record: {} (was {})", event.fish.name, format_weight(event.fish.weight_kg), format_weight(event.previous_kg));
+    }
+
+    for event in docks_built.read() {
+        println!("⚓ Built a dock at ({}, {}) for {}g", event.x, event.y, event.cost);
+    }
+
+    for event in cashed_out.read() {
+        let label = match event.scope {
+            CashOutScope::Selected => "",
+            CashOutScope::All => " ALL:",
+        };
+        println!("💰 Cashed out{} {} fish for {}g! Remaining: {}/{}",
+                 label, event.fish_sold, event.gold_earned, event.cashouts_remaining, day_night.max_cashouts_per_day);
+    }
+
+    for event in denied.read() {
+        if event.reason == CashOutDenial::NoCashoutsRemaining {
+            println!("❌ No cash-outs remaining! Wait for day {} ({})", day_night.day_number + 1, day_night.time_string());
+        }
+    }
+}
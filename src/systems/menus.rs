@@ -0,0 +1,238 @@
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+use crate::components::*;
+use crate::events::NewWorldRequested;
+use crate::resources::{DayNightCycle, FishdexPanel, FishingStats, GameState, NewWorldDialog, WorldSeed};
+use crate::seed::random_seed;
+use crate::states::AppState;
+
+/// Spawns a full-screen screen root that dims the world and keeps clicks from reaching it,
+/// with a centred column for the screen's contents
+fn spawn_screen(commands: &mut Commands, marker: impl Bundle, dim: f32, contents: impl FnOnce(&mut ChildBuilder)) {
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            background_color: Color::srgba(0.0, 0.0, 0.0, dim).into(),
+            focus_policy: FocusPolicy::Block,
+            z_index: ZIndex::Global(20),
+            ..default()
+        },
+        marker,
+    ))
+    .with_children(|overlay| {
+        overlay.spawn(NodeBundle {
+            style: Style {
+                width: Val::Px(340.0),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(20.0)),
+                row_gap: Val::Px(10.0),
+                ..default()
+            },
+            background_color: Color::srgba(0.118, 0.161, 0.231, 0.98).into(),
+            ..default()
+        })
+        .with_children(contents);
+    });
+}
+
+fn spawn_title(panel: &mut ChildBuilder, title: &str) {
+    panel.spawn(TextBundle::from_section(
+        title,
+        TextStyle {
+            font_size: 22.0,
+            color: Color::srgb(0.945, 0.961, 0.973),
+            ..default()
+        },
+    ));
+}
+
+fn spawn_menu_button(panel: &mut ChildBuilder, label: &str, color: Color, button: MenuButton) {
+    panel.spawn((
+        ButtonBundle {
+            style: Style {
+                width: Val::Percent(100.0),
+                height: Val::Px(40.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            background_color: color.into(),
+            ..default()
+        },
+        button,
+    ))
+    .with_children(|button| {
+        button.spawn(TextBundle::from_section(
+            label,
+            TextStyle {
+                font_size: 14.0,
+                color: Color::srgb(0.945, 0.961, 0.973),
+                ..default()
+            },
+        ));
+    });
+}
+
+const GREEN: Color = Color::srgb(0.13, 0.77, 0.37);
+const BLUE: Color = Color::srgb(0.247, 0.596, 0.757);
+const GREY: Color = Color::srgb(0.392, 0.455, 0.545);
+
+/// Despawns a screen when its state is left
+pub fn despawn_screen<T: Component>(mut commands: Commands, screen_q: Query<Entity, With<T>>) {
+    for entity in &screen_q {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// The main menu, over the world that was resumed or generated at startup
+pub fn spawn_main_menu(mut commands: Commands) {
+    spawn_screen(&mut commands, MainMenuScreen, 0.6, |panel| {
+        spawn_title(panel, "🎣 Rare Fish");
+        panel.spawn((
+            TextBundle::from_section(
+                "",
+                TextStyle {
+                    font_size: 14.0,
+                    color: Color::srgb(0.796, 0.835, 0.882),
+                    ..default()
+                },
+            ),
+            MainMenuRunText,
+        ));
+        spawn_menu_button(panel, "CONTINUE", GREEN, MenuButton::Continue);
+        spawn_menu_button(panel, "NEW RUN", BLUE, MenuButton::NewRun);
+        spawn_menu_button(panel, "LOAD SEED", GREY, MenuButton::LoadSeed);
+        spawn_menu_button(panel, "FISHDEX", GREY, MenuButton::Fishdex);
+    });
+}
+
+/// Describes the run CONTINUE goes back to
+pub fn update_main_menu_run_text(
+    game_state: Res<GameState>,
+    day_night: Res<DayNightCycle>,
+    world_seed: Res<WorldSeed>,
+    mut text_q: Query<&mut Text, With<MainMenuRunText>>,
+) {
+    for mut text in &mut text_q {
+        text.sections[0].value = format!(
+            "Current run: day {}, {} gold\nSeed {}",
+            day_night.day_number, game_state.gold, world_seed.code()
+        );
+    }
+}
+
+/// Esc pauses and resumes the game
+pub fn toggle_pause(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    state: Res<State<AppState>>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if !keyboard.just_pressed(KeyCode::Escape) {
+        return;
+    }
+    match state.get() {
+        AppState::Playing => next_state.set(AppState::Paused),
+        AppState::Paused => next_state.set(AppState::Playing),
+        _ => {}
+    }
+}
+
+/// The pause overlay: back to the run, end it, or go to the main menu
+pub fn spawn_pause_menu(mut commands: Commands) {
+    spawn_screen(&mut commands, PauseScreen, 0.4, |panel| {
+        spawn_title(panel, "Paused");
+        spawn_menu_button(panel, "RESUME", GREEN, MenuButton::Resume);
+        spawn_menu_button(panel, "END RUN", BLUE, MenuButton::EndRun);
+        spawn_menu_button(panel, "FISHDEX", GREY, MenuButton::Fishdex);
+        spawn_menu_button(panel, "MAIN MENU", GREY, MenuButton::MainMenu);
+    });
+}
+
+/// Totals for the run that was just ended, by rarity
+pub fn spawn_run_summary(
+    mut commands: Commands,
+    game_state: Res<GameState>,
+    day_night: Res<DayNightCycle>,
+    stats: Res<FishingStats>,
+    world_seed: Res<WorldSeed>,
+) {
+    let caught: u32 = stats.caught_by_rarity.values().sum();
+    let escaped: u32 = stats.escaped_by_rarity.values().sum();
+    let by_rarity: Vec<String> = FishRarity::ALL
+        .iter()
+        .map(|rarity| {
+            format!(
+                "{}: {} caught, {} escaped",
+                rarity.name(),
+                stats.caught_by_rarity.get(rarity).copied().unwrap_or(0),
+                stats.escaped_by_rarity.get(rarity).copied().unwrap_or(0)
+            )
+        })
+        .collect();
+
+    spawn_screen(&mut commands, RunSummaryScreen, 0.6, |panel| {
+        spawn_title(panel, "Run over");
+        panel.spawn(TextBundle::from_section(
+            format!("Seed {}\nLasted {} days", world_seed.code(), day_night.day_number),
+            TextStyle {
+                font_size: 14.0,
+                color: Color::srgb(0.796, 0.835, 0.882),
+                ..default()
+            },
+        ));
+        panel.spawn(TextBundle::from_section(
+            format!(
+                "Gold: {}\nFish sold: {}\nFish caught: {}\nFish escaped: {}",
+                game_state.gold, game_state.fish_count, caught, escaped
+            ),
+            TextStyle {
+                font_size: 16.0,
+                color: Color::srgb(0.984, 0.749, 0.141),
+                ..default()
+            },
+        ));
+        panel.spawn(TextBundle::from_section(
+            by_rarity.join("\n"),
+            TextStyle {
+                font_size: 14.0,
+                color: Color::srgb(0.796, 0.835, 0.882),
+                ..default()
+            },
+        ));
+        spawn_menu_button(panel, "NEW RUN", GREEN, MenuButton::NewRun);
+        spawn_menu_button(panel, "LOAD SEED", GREY, MenuButton::LoadSeed);
+    });
+}
+
+/// Menu screen buttons. NEW RUN starts a random world; LOAD SEED opens the New World dialog,
+/// which starts playing once a world is chosen. FISHDEX opens the Fishdex panel over the menu.
+pub fn handle_menu_buttons(
+    interaction_q: Query<(&Interaction, &MenuButton), Changed<Interaction>>,
+    mut next_state: ResMut<NextState<AppState>>,
+    mut new_world: EventWriter<NewWorldRequested>,
+    mut commands: Commands,
+) {
+    for (interaction, button) in interaction_q.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match button {
+            MenuButton::NewRun => {
+                new_world.send(NewWorldRequested { seed: random_seed() });
+                next_state.set(AppState::Playing);
+            }
+            MenuButton::Continue | MenuButton::Resume => next_state.set(AppState::Playing),
+            MenuButton::LoadSeed => commands.init_resource::<NewWorldDialog>(),
+            MenuButton::EndRun => next_state.set(AppState::RunSummary),
+            MenuButton::MainMenu => next_state.set(AppState::MainMenu),
+            MenuButton::Fishdex => commands.init_resource::<FishdexPanel>(),
+        }
+    }
+}
@@ -1,5 +1,16 @@
 pub mod setup;
+pub mod camera;
 pub mod tilemap;
 pub mod gameplay;
+pub mod economy;
+pub mod logging;
+pub mod notifications;
 pub mod ui;
+pub mod menus;
+pub mod fishdex;
+pub mod overlay;
+pub mod catch_animation;
 pub mod day_night;
+pub mod persistence;
+pub mod offline;
+pub mod replay;
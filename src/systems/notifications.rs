@@ -0,0 +1,298 @@
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
+use bevy::prelude::*;
+use crate::components::*;
+use crate::constants::*;
+use crate::events::*;
+use crate::resources::{DayNightCycle, GameState, Notification, NotificationLog};
+use crate::seed::seed_code;
+use crate::uncles::{UncleRoster, UncleType};
+
+const GOLD_COLOR: Color = Color::srgb(0.984, 0.749, 0.141);
+const MUTED_COLOR: Color = Color::srgb(0.6, 0.65, 0.7);
+const WARNING_COLOR: Color = Color::srgb(0.9, 0.4, 0.4);
+const DAY_COLOR: Color = Color::srgb(1.0, 0.8, 0.4);
+
+/// Turns gameplay events into notifications for the toast feed and event log
+pub fn collect_notifications(
+    mut log: ResMut<NotificationLog>,
+    day_night: Res<DayNightCycle>,
+    game_state: Res<GameState>,
+    mut caught: EventReader<FishCaught>,
+    mut escaped: EventReader<FishEscaped>,
+    mut discovered: EventReader<FishDiscovered>,
+    mut records: EventReader<RecordBroken>,
+    mut discarded: EventReader<FishDiscarded>,
+    mut placed: EventReader<UnclePlaced>,
+    mut docks_built: EventReader<DockBuilt>,
+    mut cashed_out: EventReader<CashedOut>,
+    mut denied: EventReader<CashOutDenied>,
+    mut day_started: EventReader<DayStarted>,
+    mut daylight_changed: EventReader<DaylightChanged>,
+    mut new_world: EventReader<NewWorldRequested>,
+    roster: Res<UncleRoster>,
+) {
+    let uncle_name = |uncle_type: &UncleType| roster.get(uncle_type).map_or(uncle_type.to_string(), |def| def.name.clone());
+
+    let timestamp = format!("Day {} {}", day_night.day_number, day_night.time_string());
+    let mut notify = |text: String, color: Color, toast: bool| {
+        log.push(Notification { text, color, timestamp: timestamp.clone(), toast });
+    };
+
+    for event in new_world.read() {
+        notify(format!("New world from seed {}", seed_code(event.seed)), DAY_COLOR, true);
+    }
+
+    for event in day_started.read() {
+        notify(
+            format!("Day {} begins! Cash-outs refreshed: {}", event.day_number, event.cashouts_remaining),
+            DAY_COLOR,
+            true,
+        );
+    }
+
+    for event in daylight_changed.read() {
+        let text = if event.is_day { "Dawn - daytime begins" } else { "Dusk - nighttime begins" };
+        notify(text.to_string(), day_night.time_of_day_color(), true);
+    }
+
+    for event in placed.read() {
+        notify(
+            format!("Hired {} for {}g", uncle_name(&event.uncle_type), event.cost),
+            roster.get(&event.uncle_type).map_or(MUTED_COLOR, |def| def.color()),
+            true,
+        );
+    }
+
+    for event in docks_built.read() {
+        notify(format!("Built a dock for {}g", event.cost), TileType::Dock.color(), true);
+    }
+
+    // Common catches and escapes only go to the history; anything rarer pops up
+    for event in caught.read() {
+        let weight = if event.fish.weight_kg > 0.0 { format!(", {}", format_weight(event.fish.weight_kg)) } else { String::new() };
+        notify(
+            format!("{} caught a {} {}{} ({}g)", uncle_name(&event.uncle_type), event.fish.rarity.name(), event.fish.name, weight, event.fish.value),
+            event.fish.rarity.color(),
+            event.fish.rarity != FishRarity::Common,
+        );
+    }
+
+    for event in escaped.read() {
+        notify(
+            format!("{} {} ({}g) escaped from {}", event.fish.rarity.name(), event.fish.name, event.fish.value, uncle_name(&event.uncle_type)),
+            event.fish.rarity.color(),
+            event.fish.rarity != FishRarity::Common,
+        );
+    }
+
+    for event in discovered.read() {
+        notify(
+            format!("New Fishdex entry: {} {}", event.fish.traits.color.name(), event.fish.name),
+            event.fish.traits.color.color(),
+            true,
+        );
+    }
+
+    for event in records.read() {
+        notify(
+            format!(
+                "Record {}! {} landed {} (old record {})",
+                event.fish.name, uncle_name(&event.uncle_type), format_weight(event.fish.weight_kg), format_weight(event.previous_kg)
+            ),
+            GOLD_COLOR,
+            true,
+        );
+    }
+
+    for event in discarded.read() {
+        notify(format!("Removed {} ({}g) to make space", event.fish.name, event.fish.value), MUTED_COLOR, true);
+    }
+
+    for event in cashed_out.read() {
+        notify(
+            format!(
+                "Cashed out {} fish for {}g at {:.1}x! Remaining: {}/{}",
+                event.fish_sold, event.gold_earned, event.multiplier, event.cashouts_remaining, day_night.max_cashouts_per_day
+            ),
+            GOLD_COLOR,
+            true,
+        );
+    }
+
+    for event in denied.read() {
+        let text = match (event.reason, event.scope) {
+            (CashOutDenial::Cooldown, _) => {
+                format!("Cash-out ready in {:.0}s", game_state.cash_out_cooldown.ceil())
+            }
+            (CashOutDenial::NoCashoutsRemaining, _) => {
+                format!("No cash-outs remaining! Wait for day {}", day_night.day_number + 1)
+            }
+            (CashOutDenial::NothingToSell, CashOutScope::Selected) => {
+                "Select an uncle with fish to cash out".to_string()
+            }
+            (CashOutDenial::NothingToSell, CashOutScope::All) => "No fish to cash out".to_string(),
+        };
+        notify(text, WARNING_COLOR, true);
+    }
+}
+
+/// Adds new notifications to the event log panel and pops up toasts for them
+pub fn show_notifications(
+    mut commands: Commands,
+    mut log: ResMut<NotificationLog>,
+    toast_container_q: Query<(Entity, Option<&Children>), With<ToastContainer>>,
+    history_q: Query<(Entity, Option<&Children>), With<NotificationHistoryList>>,
+) {
+    if log.unshown == 0 {
+        return;
+    }
+    let new_entries = log.entries.len() - log.unshown;
+    log.unshown = 0;
+
+    let mut toasts = Vec::new();
+    let mut history = Vec::new();
+
+    for notification in log.entries.range(new_entries..) {
+        if notification.toast {
+            toasts.push(spawn_toast(&mut commands, notification));
+        }
+        history.push(spawn_history_entry(&mut commands, notification));
+    }
+
+    if let Ok((container, children)) = toast_container_q.get_single() {
+        commands.entity(container).push_children(&toasts);
+        // Newest at the bottom; drop the oldest once too many are stacked
+        let existing = children.map(|c| c.to_vec()).unwrap_or_default();
+        let overflow = (existing.len() + toasts.len()).saturating_sub(MAX_VISIBLE_TOASTS);
+        for &toast in existing.iter().chain(&toasts).take(overflow) {
+            commands.entity(toast).despawn_recursive();
+        }
+    } else {
+        for toast in toasts {
+            commands.entity(toast).despawn_recursive();
+        }
+    }
+
+    if let Ok((list, children)) = history_q.get_single() {
+        // Newest on top
+        history.reverse();
+        commands.entity(list).insert_children(0, &history);
+        let existing = children.map(|c| c.to_vec()).unwrap_or_default();
+        let keep = NOTIFICATION_HISTORY_LEN.saturating_sub(history.len());
+        for &entry in existing.iter().skip(keep) {
+            commands.entity(entry).despawn_recursive();
+        }
+    } else {
+        for entry in history {
+            commands.entity(entry).despawn_recursive();
+        }
+    }
+}
+
+fn spawn_toast(commands: &mut Commands, notification: &Notification) -> Entity {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    padding: UiRect::axes(Val::Px(14.0), Val::Px(8.0)),
+                    border: UiRect::left(Val::Px(4.0)),
+                    ..default()
+                },
+                background_color: Color::srgba(0.118, 0.161, 0.231, 0.9).into(),
+                border_color: notification.color.into(),
+                ..default()
+            },
+            Toast { age: 0.0 },
+        ))
+        .with_children(|toast| {
+            toast.spawn(TextBundle::from_section(
+                notification.text.clone(),
+                TextStyle {
+                    font_size: 15.0,
+                    color: notification.color,
+                    ..default()
+                },
+            ));
+        })
+        .id()
+}
+
+fn spawn_history_entry(commands: &mut Commands, notification: &Notification) -> Entity {
+    commands
+        .spawn((
+            TextBundle::from_sections([
+                TextSection::new(
+                    format!("{}  ", notification.timestamp),
+                    TextStyle {
+                        font_size: 10.0,
+                        color: MUTED_COLOR,
+                        ..default()
+                    },
+                ),
+                TextSection::new(
+                    notification.text.clone(),
+                    TextStyle {
+                        font_size: 11.0,
+                        color: notification.color,
+                        ..default()
+                    },
+                ),
+            ]),
+            NotificationHistoryEntry,
+        ))
+        .id()
+}
+
+/// Ages toasts, fading them out over their last `TOAST_FADE_SECONDS`
+pub fn fade_toasts(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut toasts_q: Query<(Entity, &mut Toast, &mut BackgroundColor, &mut BorderColor, &Children)>,
+    mut text_q: Query<&mut Text>,
+) {
+    for (entity, mut toast, mut background, mut border, children) in toasts_q.iter_mut() {
+        toast.age += time.delta_seconds();
+        if toast.age >= TOAST_LIFETIME_SECONDS {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        let alpha = ((TOAST_LIFETIME_SECONDS - toast.age) / TOAST_FADE_SECONDS).min(1.0);
+        background.0.set_alpha(0.9 * alpha);
+        border.0.set_alpha(alpha);
+        for &child in children.iter() {
+            if let Ok(mut text) = text_q.get_mut(child) {
+                for section in text.sections.iter_mut() {
+                    section.style.color.set_alpha(alpha);
+                }
+            }
+        }
+    }
+}
+
+/// Scrolls the event log with the mouse wheel while the cursor is over it
+pub fn scroll_notification_history(
+    mut wheel: EventReader<MouseWheel>,
+    mut list_q: Query<(&mut NotificationHistoryList, &mut Style, &Parent, &Node)>,
+    panel_q: Query<(&Node, &Interaction)>,
+) {
+    for event in wheel.read() {
+        let dy = match event.unit {
+            MouseScrollUnit::Line => event.y * HISTORY_SCROLL_SPEED,
+            MouseScrollUnit::Pixel => event.y,
+        };
+
+        for (mut list, mut style, parent, list_node) in list_q.iter_mut() {
+            let Ok((panel_node, interaction)) = panel_q.get(parent.get()) else {
+                continue;
+            };
+            if *interaction == Interaction::None {
+                continue;
+            }
+
+            let max_offset = (list_node.size().y - panel_node.size().y).max(0.0);
+            list.offset = (list.offset - dy).clamp(0.0, max_offset);
+            style.top = Val::Px(-list.offset);
+        }
+    }
+}
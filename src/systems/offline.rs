@@ -0,0 +1,83 @@
+use bevy::prelude::*;
+use std::time::Duration;
+use crate::components::{TileType, Uncle};
+use crate::constants::*;
+use crate::events::{FishCaught, FishEscaped};
+use crate::fishing::{FishingGrounds, FishingSpot};
+use crate::resources::{DayNightCycle, GameState, OfflineFish, OfflineReport, PendingOfflineEvents, WorldSeed};
+use crate::species::SpeciesCatalogue;
+use crate::systems::gameplay::{advance_escapes, advance_fishing};
+use crate::uncles::UncleRoster;
+
+/// Simulates the time the game was closed using the same fishing, escape and
+/// day/night rules as the live systems, in fixed one-second steps.
+/// Uncles draw from and deplete `grounds` just as they do live; `tile_at` looks up what they stand on.
+/// Every uncle must be in `roster`. The report lists each fish caught and escaped with the
+/// index of its uncle in `uncles`.
+/// Runtime is bounded by `MAX_OFFLINE_SECONDS / OFFLINE_STEP_SECONDS` steps.
+pub fn simulate_offline(
+    seconds_away: f32,
+    uncles: &mut [Uncle],
+    game_state: &mut GameState,
+    day_night: &mut DayNightCycle,
+    world_seed: &mut WorldSeed,
+    grounds: &mut FishingGrounds,
+    catalogue: &SpeciesCatalogue,
+    roster: &UncleRoster,
+    tile_at: impl Fn(i32, i32) -> Option<TileType>,
+) -> OfflineReport {
+    let total = seconds_away.clamp(0.0, MAX_OFFLINE_SECONDS);
+    let mut report = OfflineReport {
+        seconds_away: total,
+        ..Default::default()
+    };
+
+    let defs: Vec<_> = uncles
+        .iter()
+        .map(|uncle| roster.get(&uncle.uncle_type).expect("offline uncles are in the roster"))
+        .collect();
+    // The map cannot change while the game is closed
+    let spots: Vec<Option<FishingSpot>> = uncles
+        .iter()
+        .zip(&defs)
+        .map(|(uncle, def)| FishingSpot::at(uncle.x, uncle.y, def.casting_radius, tile_at(uncle.x, uncle.y), grounds))
+        .collect();
+
+    let mut remaining = total;
+    while remaining > 0.0 {
+        let step = remaining.min(OFFLINE_STEP_SECONDS);
+        remaining -= step;
+
+        grounds.regrow(step / DAY_LENGTH_SECONDS);
+        let is_day = day_night.is_daytime();
+        for (index, ((uncle, def), spot)) in uncles.iter_mut().zip(&defs).zip(&spots).enumerate() {
+            let of_uncle = |fish| OfflineFish { uncle: index, fish };
+            if let Some(spot) = spot {
+                let delta = Duration::from_secs_f32(step);
+                report.caught.extend(advance_fishing(uncle, def, delta, is_day, world_seed, grounds, catalogue, spot).into_iter().map(of_uncle));
+            }
+            report.escaped.extend(advance_escapes(uncle, def, step, world_seed).into_iter().map(of_uncle));
+        }
+
+        game_state.tick_cooldown(step);
+        if day_night.advance(step) {
+            report.days_passed += 1;
+        }
+    }
+    day_night.is_day = day_night.is_daytime();
+
+    report
+}
+
+/// Sends the offline catch-up's events on the first simulated tick, before the fishing
+/// systems that turn catches and escapes into stats, records and Fishdex entries
+pub fn send_offline_events(
+    mut commands: Commands,
+    mut pending: ResMut<PendingOfflineEvents>,
+    mut caught: EventWriter<FishCaught>,
+    mut escaped: EventWriter<FishEscaped>,
+) {
+    caught.send_batch(pending.caught.drain(..));
+    escaped.send_batch(pending.escaped.drain(..));
+    commands.remove_resource::<PendingOfflineEvents>();
+}
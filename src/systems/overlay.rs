@@ -0,0 +1,95 @@
+use bevy::prelude::*;
+use crate::components::{FishOverlayLabel, FishRarity, Tile};
+use crate::fishing::{BodyId, FishingGrounds, WaterBody};
+use crate::grid::TileGrid;
+use crate::resources::FishOverlay;
+
+/// Toggles the fish population overlay with O
+pub fn toggle_fish_overlay(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut overlay: ResMut<FishOverlay>,
+) {
+    if keyboard.just_pressed(KeyCode::KeyO) {
+        overlay.visible = !overlay.visible;
+        println!("🗺️ Fish overlay {}", if overlay.visible { "on" } else { "off" });
+    }
+}
+
+/// Tints every water tile from red (fished out) to green (fully stocked) and labels each
+/// water body with how full it is. Turning the overlay off restores the terrain colors.
+pub fn update_fish_overlay(
+    mut commands: Commands,
+    overlay: Res<FishOverlay>,
+    grounds: Res<FishingGrounds>,
+    mut tiles_q: Query<(&Tile, &mut Sprite)>,
+    mut labels_q: Query<(Entity, &FishOverlayLabel, &mut Text)>,
+) {
+    let refresh = overlay.is_changed() || (overlay.visible && grounds.is_changed());
+    if !refresh {
+        return;
+    }
+
+    for (tile, mut sprite) in tiles_q.iter_mut() {
+        sprite.color = match grounds.body_at(tile.x, tile.y) {
+            Some(body) if overlay.visible => fullness_color(body.fullness()).mix(&tile.tile_type.color(), 0.35),
+            _ => tile.tile_type.color(),
+        };
+    }
+
+    // Labels are rebuilt whenever bodies are added or merged (new chunks or a new map) or the overlay is toggled
+    let stale = overlay.is_changed()
+        || labels_q.iter().count() != grounds.bodies.len()
+        || labels_q.iter().any(|(_, label, _)| !grounds.bodies.contains_key(&label.body));
+    if stale {
+        for (entity, _, _) in labels_q.iter() {
+            commands.entity(entity).despawn();
+        }
+        if overlay.visible {
+            for (&id, body) in &grounds.bodies {
+                spawn_body_label(&mut commands, id, body);
+            }
+        }
+        return;
+    }
+
+    for (_, label, mut text) in labels_q.iter_mut() {
+        text.sections[0].value = body_summary(&grounds.bodies[&label.body]);
+    }
+}
+
+fn spawn_body_label(commands: &mut Commands, id: BodyId, body: &WaterBody) {
+    // Label the middle tile of the body in row order, which always lies in the body
+    let (x, y) = body.tiles[body.tiles.len() / 2];
+    let pos = TileGrid::tile_to_world(x, y);
+    commands.spawn((
+        Text2dBundle {
+            text: Text::from_section(
+                body_summary(body),
+                TextStyle {
+                    font_size: 14.0,
+                    color: Color::WHITE,
+                    ..default()
+                },
+            ),
+            transform: Transform::from_xyz(pos.x, pos.y, 3.0),
+            ..default()
+        },
+        FishOverlayLabel { body: id },
+    ));
+}
+
+/// e.g. "82% (rare 40%)"
+fn body_summary(body: &WaterBody) -> String {
+    let rare: Vec<_> = body.populations.iter().filter(|p| p.species.rarity() == FishRarity::Rare).collect();
+    let rare_count: f32 = rare.iter().map(|p| p.count).sum();
+    let rare_capacity: f32 = rare.iter().map(|p| p.capacity).sum();
+    if rare_capacity > 0.0 {
+        format!("{:.0}% (rare {:.0}%)", body.fullness() * 100.0, rare_count / rare_capacity * 100.0)
+    } else {
+        format!("{:.0}%", body.fullness() * 100.0)
+    }
+}
+
+fn fullness_color(fullness: f32) -> Color {
+    Color::srgb(0.85, 0.2, 0.2).mix(&Color::srgb(0.2, 0.8, 0.3), fullness.clamp(0.0, 1.0))
+}
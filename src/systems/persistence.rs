@@ -0,0 +1,328 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use std::time::Duration;
+use crate::components::{SelectedUncleMarker, Uncle};
+use crate::constants::*;
+use crate::fishdex::Fishdex;
+use crate::events::{FishCaught, FishEscaped};
+use crate::resources::{DayNightCycle, FishingStats, GameState, PendingOfflineEvents, RestoredWorld, SaveSlot, SelectedUncle, SpeciesRecords, WorldSeed};
+use crate::fishing::{BodyId, FishingGrounds};
+use crate::grid::TileGrid;
+use crate::save::{unix_now, PopulationSave, RngStreamSave, SaveFile, TileEditSave, UncleSave, WorldSeedSave, SAVE_VERSION};
+use crate::species::SpeciesCatalogue;
+use crate::systems::gameplay::spawn_uncle;
+use crate::systems::offline::simulate_offline;
+use crate::systems::tilemap::{home_chunks, load_chunk, load_chunks_around, spawn_chunk};
+use crate::uncles::UncleRoster;
+use crate::worldgen::WorldGenConfig;
+
+/// Read-only view of everything that goes into a save file
+#[derive(SystemParam)]
+pub struct RunSnapshot<'w, 's> {
+    game_state: Res<'w, GameState>,
+    day_night: Res<'w, DayNightCycle>,
+    world_seed: Res<'w, WorldSeed>,
+    grounds: Res<'w, FishingGrounds>,
+    catalogue: Res<'w, SpeciesCatalogue>,
+    grid: Res<'w, TileGrid>,
+    selected_uncle: Res<'w, SelectedUncle>,
+    records: Res<'w, SpeciesRecords>,
+    stats: Res<'w, FishingStats>,
+    uncles_q: Query<'w, 's, (&'static Uncle, Has<SelectedUncleMarker>)>,
+}
+
+impl RunSnapshot<'_, '_> {
+    pub fn capture(&self) -> SaveFile {
+        let uncles = self.uncles_q
+            .iter()
+            .map(|(uncle, selected)| UncleSave {
+                uncle_type: uncle.uncle_type.clone(),
+                x: uncle.x,
+                y: uncle.y,
+                timer_elapsed: uncle.fishing_timer.elapsed_secs(),
+                basket: uncle.basket.clone(),
+                selected,
+            })
+            .collect();
+
+        // Untouched water is regenerated fully stocked, so only fished populations are saved
+        let populations = self.grounds.bodies
+            .iter()
+            .flat_map(|(id, body)| body.populations.iter().map(move |population| (id, population)))
+            .filter(|(_, population)| population.count < population.capacity)
+            .map(|(id, population)| PopulationSave {
+                chunk: id.chunk,
+                body: id.index,
+                chunks: self.grounds.bodies[id].chunks(),
+                species: self.catalogue.at(population.species.index()).id.clone(),
+                count: population.count,
+            })
+            .collect();
+
+        let tile_edits = self.grid
+            .edits()
+            .into_iter()
+            .map(|(x, y, tile_type)| TileEditSave { x, y, tile_type })
+            .collect();
+
+        SaveFile {
+            version: SAVE_VERSION,
+            saved_at: unix_now(),
+            game_state: self.game_state.clone(),
+            day_night: self.day_night.clone(),
+            world_seed: WorldSeedSave {
+                seed: self.world_seed.seed,
+                streams: self.world_seed
+                    .positions()
+                    .into_iter()
+                    .map(|(stream, word_pos)| RngStreamSave { stream, word_pos })
+                    .collect(),
+            },
+            tiles: None,
+            tile_edits,
+            uncles,
+            selected_uncle_type: Some(self.selected_uncle.uncle_type.clone()),
+            populations,
+            records: self.records.records.values().cloned().collect(),
+            stats: self.stats.clone(),
+        }
+    }
+}
+
+fn write_snapshot(snapshot: &RunSnapshot, fishdex: &Fishdex, slot: &SaveSlot) {
+    match snapshot.capture().write(&slot.path) {
+        Ok(()) => println!("💾 Saved run to {}", slot.path.display()),
+        Err(err) => println!("❌ Failed to save run: {}", err),
+    }
+    if let Err(err) = fishdex.write(&slot.fishdex_path) {
+        println!("❌ Failed to save the Fishdex: {}", err);
+    }
+}
+
+/// Mutable access to everything a save file restores
+#[derive(SystemParam)]
+pub struct RunRestorer<'w, 's> {
+    commands: Commands<'w, 's>,
+    asset_server: Option<Res<'w, AssetServer>>,
+    game_state: ResMut<'w, GameState>,
+    day_night: ResMut<'w, DayNightCycle>,
+    world_seed: ResMut<'w, WorldSeed>,
+    config: Res<'w, WorldGenConfig>,
+    catalogue: Res<'w, SpeciesCatalogue>,
+    roster: Res<'w, UncleRoster>,
+    selected_uncle: ResMut<'w, SelectedUncle>,
+    records: ResMut<'w, SpeciesRecords>,
+    stats: ResMut<'w, FishingStats>,
+}
+
+impl RunRestorer<'_, '_> {
+    /// Rebuilds the saved world: resources, tiles, fish populations and uncles.
+    /// Uncles whose definition is no longer in the `UncleRoster` are dropped.
+    /// With `catch_up`, the time since the save was written is simulated first, and its catches
+    /// and escapes are sent as `FishCaught` and `FishEscaped` on the first simulated tick.
+    /// Returns false (and changes nothing) if the save's tile grid is malformed.
+    pub fn restore(&mut self, save: SaveFile, catch_up: bool) -> bool {
+        if let Some(legacy) = &save.tiles {
+            if legacy.tiles.len() != legacy.width * legacy.height {
+                println!("❌ Save holds {} tiles for a {}x{} map", legacy.tiles.len(), legacy.width, legacy.height);
+                return false;
+            }
+        }
+
+        *self.game_state = save.game_state;
+        *self.day_night = save.day_night;
+        *self.world_seed = WorldSeed::restore(
+            save.world_seed.seed,
+            save.world_seed.streams.iter().map(|saved| (saved.stream, saved.word_pos)),
+        );
+        self.records.records = save.records.into_iter().map(|record| (record.species.clone(), record)).collect();
+        *self.stats = save.stats;
+        if let Some(uncle_type) = save.selected_uncle_type.filter(|id| self.roster.get(id).is_some()) {
+            self.selected_uncle.uncle_type = uncle_type;
+        }
+        let (saved_uncles, unknown): (Vec<UncleSave>, Vec<UncleSave>) = save.uncles
+            .into_iter()
+            .partition(|saved| self.roster.get(&saved.uncle_type).is_some());
+        for saved in &unknown {
+            println!("⚠️ Dropped the {} uncle at ({}, {}): not in the uncle roster", saved.uncle_type, saved.x, saved.y);
+        }
+
+        let seed = save.world_seed.seed;
+        let mut grid = TileGrid::default();
+        for edit in &save.tile_edits {
+            grid.add_edit(edit.x, edit.y, edit.tile_type);
+        }
+        // Older saves stored the whole fixed-size map; tiles that differ from generation are edits
+        if let Some(legacy) = &save.tiles {
+            let generated = self.config.generate_region(seed, 0, 0, legacy.width, legacy.height);
+            for (i, (&saved, generated)) in legacy.tiles.iter().zip(generated).enumerate() {
+                if saved != generated {
+                    grid.add_edit((i % legacy.width) as i32, (i / legacy.width) as i32, saved);
+                }
+            }
+        }
+
+        // Fished water first, from only the chunks it spanned, so each body comes back as it was saved
+        let mut grounds = FishingGrounds::default();
+        for chunk in save.populations.iter().flat_map(|saved| &saved.chunks) {
+            load_chunk(&mut grid, &mut grounds, seed, &self.config, &self.catalogue, *chunk);
+        }
+        // Populations of species no longer in the catalogue are gone from the water
+        for saved in &save.populations {
+            let Some(index) = self.catalogue.index_of(&saved.species) else {
+                continue;
+            };
+            let population = grounds.bodies
+                .get_mut(&BodyId { chunk: saved.chunk, index: saved.body })
+                .and_then(|body| body.populations.iter_mut().find(|p| p.species.index() == index));
+            if let Some(population) = population {
+                population.count = saved.count.clamp(0.0, population.capacity);
+            }
+        }
+
+        // Then the home region and everything in reach of an uncle
+        for chunk in home_chunks(&self.config) {
+            load_chunk(&mut grid, &mut grounds, seed, &self.config, &self.catalogue, chunk);
+            spawn_chunk(&mut self.commands, &mut grid, chunk);
+        }
+        for saved in &saved_uncles {
+            let radius = self.roster.get(&saved.uncle_type).map_or(0, |def| def.casting_radius);
+            load_chunks_around(&mut grid, &mut grounds, seed, &self.config, &self.catalogue, (saved.x, saved.y), radius);
+        }
+
+        let selected_flags: Vec<bool> = saved_uncles.iter().map(|saved| saved.selected).collect();
+        let mut uncles: Vec<Uncle> = saved_uncles
+            .into_iter()
+            .filter_map(|saved| {
+                let mut uncle = Uncle::new(self.roster.get(&saved.uncle_type)?, saved.x, saved.y);
+                uncle.fishing_timer.set_elapsed(Duration::from_secs_f32(saved.timer_elapsed));
+                uncle.basket = saved.basket;
+                Some(uncle)
+            })
+            .collect();
+
+        let mut offline = None;
+        if catch_up {
+            // Catch up on idle progress made while the game was closed
+            let seconds_away = unix_now().saturating_sub(save.saved_at) as f32;
+            let report = simulate_offline(
+                seconds_away,
+                &mut uncles,
+                &mut self.game_state,
+                &mut self.day_night,
+                &mut self.world_seed,
+                &mut grounds,
+                &self.catalogue,
+                &self.roster,
+                |x, y| grid.get(x, y),
+            );
+            println!(
+                "⏳ Away for {:.0}s: {} fish caught, {} escaped, {} days passed",
+                report.seconds_away, report.caught.len(), report.escaped.len(), report.days_passed
+            );
+            offline = Some(report);
+        }
+
+        let mut entities = Vec::with_capacity(uncles.len());
+        for (uncle, selected) in uncles.into_iter().zip(selected_flags) {
+            let world_pos = TileGrid::tile_to_world(uncle.x, uncle.y);
+            let Some(def) = self.roster.get(&uncle.uncle_type) else {
+                continue;
+            };
+            let entity = spawn_uncle(&mut self.commands, self.asset_server.as_deref(), uncle, def, world_pos.x, world_pos.y);
+            if selected {
+                self.commands.entity(entity).insert(SelectedUncleMarker);
+            }
+            entities.push(entity);
+        }
+
+        // Offline catches and escapes reach the same subscribers as live ones
+        if let Some(report) = offline {
+            let caught = report.caught
+                .iter()
+                .filter_map(|offline| Some(FishCaught { uncle: *entities.get(offline.uncle)?, uncle_type: offline.fish.caught_by_uncle.clone(), fish: offline.fish.clone() }))
+                .collect();
+            let escaped = report.escaped
+                .iter()
+                .filter_map(|offline| Some(FishEscaped { uncle: *entities.get(offline.uncle)?, uncle_type: offline.fish.caught_by_uncle.clone(), fish: offline.fish.clone() }))
+                .collect();
+            self.commands.insert_resource(PendingOfflineEvents { caught, escaped });
+            if report.seconds_away >= MIN_OFFLINE_REPORT_SECONDS {
+                self.commands.insert_resource(report);
+            }
+        }
+
+        self.commands.insert_resource(grounds);
+        self.commands.insert_resource(grid);
+        self.commands.insert_resource(RestoredWorld);
+        true
+    }
+}
+
+/// Resumes the saved run if one exists, then simulates the time spent away
+pub fn load_game(
+    slot: Res<SaveSlot>,
+    mut restorer: RunRestorer,
+) {
+    if !slot.resume || !slot.path.exists() {
+        return;
+    }
+
+    let save = match SaveFile::read(&slot.path) {
+        Ok(save) => save,
+        Err(err) => {
+            println!("❌ Could not load {}: {} - starting a new world", slot.path.display(), err);
+            return;
+        }
+    };
+
+    let (seed, day_number, uncle_count) = (save.world_seed.seed, save.day_night.day_number, save.uncles.len());
+    if restorer.restore(save, true) {
+        println!("📂 Resumed day {} with {} uncles (seed {})", day_number, uncle_count, seed);
+    } else {
+        println!("❌ Could not load {} - starting a new world", slot.path.display());
+    }
+}
+
+/// Loads the Fishdex, which is kept whether or not the saved run is resumed
+pub fn load_fishdex(slot: Res<SaveSlot>, mut fishdex: ResMut<Fishdex>) {
+    *fishdex = Fishdex::read_or_default(&slot.fishdex_path);
+}
+
+/// Deletes the save of a run that was ended, so it is not resumed on the next launch. The
+/// Fishdex outlives the run and is written one last time; nothing is saved until a new run
+/// starts.
+pub fn discard_ended_run(slot: Res<SaveSlot>, fishdex: Res<Fishdex>) {
+    if let Err(err) = fishdex.write(&slot.fishdex_path) {
+        println!("❌ Failed to save the Fishdex: {}", err);
+    }
+    match std::fs::remove_file(&slot.path) {
+        Ok(()) => println!("🗑️ Deleted the save of the ended run"),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+        Err(err) => println!("❌ Failed to delete {}: {}", slot.path.display(), err),
+    }
+}
+
+/// Periodically writes the run and the Fishdex to disk
+pub fn autosave_system(
+    mut slot: ResMut<SaveSlot>,
+    snapshot: RunSnapshot,
+    fishdex: Res<Fishdex>,
+    time: Res<Time>,
+) {
+    if slot.autosave_timer.tick(time.delta()).just_finished() {
+        write_snapshot(&snapshot, &fishdex, &slot);
+    }
+}
+
+/// Saves one last time when the app is closing
+pub fn save_on_exit(
+    mut exit_events: EventReader<AppExit>,
+    slot: Res<SaveSlot>,
+    snapshot: RunSnapshot,
+    fishdex: Res<Fishdex>,
+) {
+    if exit_events.read().last().is_some() {
+        write_snapshot(&snapshot, &fishdex, &slot);
+    }
+}
@@ -0,0 +1,155 @@
+use bevy::prelude::*;
+use std::path::PathBuf;
+use crate::actions::PendingActions;
+use crate::constants::*;
+use crate::replay::{RecordedAction, ReplayFile, REPLAY_VERSION};
+use crate::resources::{GameState, SimulationTick};
+use crate::save::SaveFile;
+use crate::systems::persistence::{RunRestorer, RunSnapshot};
+
+/// Captures the session for bug reports: the world on the first tick plus every applied action
+#[derive(Resource)]
+pub struct ReplayRecorder {
+    pub path: PathBuf,
+    pub start: Option<SaveFile>,
+    pub actions: Vec<RecordedAction>,
+}
+
+impl Default for ReplayRecorder {
+    fn default() -> Self {
+        Self {
+            path: PathBuf::from(REPLAY_FILE_PATH),
+            start: None,
+            actions: Vec::new(),
+        }
+    }
+}
+
+impl ReplayRecorder {
+    /// Packages the recording so far; `None` before the first tick has run
+    pub fn to_replay(&self, end_tick: u64, final_state: &GameState) -> Option<ReplayFile> {
+        Some(ReplayFile {
+            version: REPLAY_VERSION,
+            start: self.start.clone()?,
+            actions: self.actions.clone(),
+            end_tick,
+            final_state: final_state.clone(),
+        })
+    }
+}
+
+/// The replay being played back and how far through its actions we are
+#[derive(Resource)]
+pub struct ReplayPlayer {
+    pub replay: ReplayFile,
+    pub next_action: usize,
+    pub finished: bool,
+}
+
+/// Counts fixed ticks; runs last in every tick
+pub fn count_tick(mut tick: ResMut<SimulationTick>) {
+    tick.0 += 1;
+}
+
+/// Snapshots the world on the first recorded tick
+pub fn begin_recording(
+    mut recorder: ResMut<ReplayRecorder>,
+    snapshot: RunSnapshot,
+) {
+    if recorder.start.is_none() {
+        recorder.start = Some(snapshot.capture());
+    }
+}
+
+/// Records the actions about to be applied this tick
+pub fn record_actions(
+    mut recorder: ResMut<ReplayRecorder>,
+    actions: Res<PendingActions>,
+    tick: Res<SimulationTick>,
+) {
+    for action in actions.queue.iter() {
+        recorder.actions.push(RecordedAction { tick: tick.0, action: action.clone() });
+    }
+}
+
+fn write_recording(recorder: &ReplayRecorder, tick: &SimulationTick, game_state: &GameState) {
+    let Some(replay) = recorder.to_replay(tick.0, game_state) else {
+        return;
+    };
+    match replay.write(&recorder.path) {
+        Ok(()) => println!("🎬 Wrote replay ({} actions, {} ticks) to {}", replay.actions.len(), tick.0, recorder.path.display()),
+        Err(err) => println!("❌ Failed to write replay: {}", err),
+    }
+}
+
+/// F8 writes the replay immediately, e.g. to attach to a bug report
+pub fn save_replay_hotkey(
+    keyboard: Option<Res<ButtonInput<KeyCode>>>,
+    recorder: Res<ReplayRecorder>,
+    tick: Res<SimulationTick>,
+    game_state: Res<GameState>,
+) {
+    if keyboard.is_some_and(|keyboard| keyboard.just_pressed(KeyCode::F8)) {
+        write_recording(&recorder, &tick, &game_state);
+    }
+}
+
+/// Writes the replay when the app is closing
+pub fn save_replay_on_exit(
+    mut exit_events: EventReader<AppExit>,
+    recorder: Res<ReplayRecorder>,
+    tick: Res<SimulationTick>,
+    game_state: Res<GameState>,
+) {
+    if exit_events.read().last().is_some() {
+        write_recording(&recorder, &tick, &game_state);
+    }
+}
+
+/// Rebuilds the replay's starting world instead of generating or loading one
+pub fn start_replay(
+    player: Res<ReplayPlayer>,
+    mut restorer: RunRestorer,
+) {
+    if restorer.restore(player.replay.start.clone(), false) {
+        println!("▶️ Playing replay: {} actions over {} ticks", player.replay.actions.len(), player.replay.end_tick);
+    }
+}
+
+/// Feeds recorded actions into the queue on the tick they were originally applied.
+/// Live input is discarded so it cannot change the outcome.
+pub fn feed_replay_actions(
+    mut player: ResMut<ReplayPlayer>,
+    mut actions: ResMut<PendingActions>,
+    tick: Res<SimulationTick>,
+) {
+    actions.queue.clear();
+
+    while let Some(recorded) = player.replay.actions.get(player.next_action) {
+        if recorded.tick > tick.0 {
+            break;
+        }
+        if recorded.tick == tick.0 {
+            actions.push(recorded.action.clone());
+        }
+        player.next_action += 1;
+    }
+}
+
+/// Reports whether the replay reproduced the recorded outcome once it reaches its last tick
+pub fn check_replay_finished(
+    mut player: ResMut<ReplayPlayer>,
+    tick: Res<SimulationTick>,
+    game_state: Res<GameState>,
+) {
+    if player.finished || tick.0 < player.replay.end_tick {
+        return;
+    }
+    player.finished = true;
+
+    if *game_state == player.replay.final_state {
+        println!("✅ Replay finished at tick {}: state matches the recording", tick.0);
+    } else {
+        println!("❌ Replay diverged at tick {}: expected {:?}, got {:?}", tick.0, player.replay.final_state, *game_state);
+    }
+}
@@ -1,11 +1,20 @@
 use bevy::prelude::*;
 use crate::components::*;
+use crate::systems::tilemap::home_center;
+use crate::resources::SelectedUncle;
+use crate::uncles::{UncleDef, UncleRoster};
+use crate::worldgen::WorldGenConfig;
 
-pub fn setup_camera(mut commands: Commands) {
-    commands.spawn(Camera2dBundle::default());
+/// Starts the camera over the middle of the home region
+pub fn setup_camera(mut commands: Commands, config: Res<WorldGenConfig>) {
+    let center = home_center(&config);
+    commands.spawn(Camera2dBundle {
+        transform: Transform::from_xyz(center.x, center.y, 999.9),
+        ..default()
+    });
 }
 
-pub fn setup_ui(mut commands: Commands) {
+pub fn setup_ui(mut commands: Commands, roster: Res<UncleRoster>, selected_uncle: Res<SelectedUncle>) {
     // Root UI container - transparent to show game world
     commands.spawn(NodeBundle {
         style: Style {
@@ -208,18 +217,61 @@ pub fn setup_ui(mut commands: Commands) {
                 });
             });
 
-            // Seed display
-            header.spawn((
-                TextBundle::from_section(
-                    "Seed: 0",
-                    TextStyle {
-                        font_size: 14.0,
-                        color: Color::srgb(0.796, 0.835, 0.882),
+            // Seed code (click to copy) and the button for starting over
+            header.spawn(NodeBundle {
+                style: Style {
+                    column_gap: Val::Px(10.0),
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                ..default()
+            })
+            .with_children(|seed| {
+                seed.spawn((
+                    ButtonBundle {
+                        background_color: Color::NONE.into(),
                         ..default()
                     },
-                ),
-                SeedText,
-            ));
+                    SeedCodeButton,
+                ))
+                .with_children(|button| {
+                    button.spawn((
+                        TextBundle::from_section(
+                            "Seed: 0",
+                            TextStyle {
+                                font_size: 14.0,
+                                color: Color::srgb(0.796, 0.835, 0.882),
+                                ..default()
+                            },
+                        ),
+                        SeedText,
+                    ));
+                });
+
+                seed.spawn((
+                    ButtonBundle {
+                        style: Style {
+                            height: Val::Px(28.0),
+                            padding: UiRect::horizontal(Val::Px(10.0)),
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        background_color: Color::srgb(0.247, 0.596, 0.757).into(),
+                        ..default()
+                    },
+                    NewWorldButton,
+                ))
+                .with_children(|button| {
+                    button.spawn(TextBundle::from_section(
+                        "NEW WORLD",
+                        TextStyle {
+                            font_size: 14.0,
+                            color: Color::srgb(0.945, 0.961, 0.973),
+                            ..default()
+                        },
+                    ));
+                });
+            });
         });
 
         // === MAIN CONTENT AREA (Three panels) ===
@@ -258,9 +310,9 @@ pub fn setup_ui(mut commands: Commands) {
                     },
                 ));
 
-                // Uncle cards
-                for uncle_type in [UncleType::Mongolian, UncleType::Somali, UncleType::Japanese] {
-                    spawn_uncle_card(sidebar, uncle_type);
+                // Uncle cards, one per roster entry
+                for def in roster.iter() {
+                    spawn_uncle_card(sidebar, def, def.id == selected_uncle.uncle_type);
                 }
 
                 // Spacer
@@ -281,6 +333,47 @@ pub fn setup_ui(mut commands: Commands) {
                         ..default()
                     },
                 ));
+
+                // Event log
+                sidebar.spawn(TextBundle::from_section(
+                    "EVENT LOG",
+                    TextStyle {
+                        font_size: 16.0,
+                        color: Color::srgb(0.796, 0.835, 0.882),
+                        ..default()
+                    },
+                ));
+
+                // Scroll viewport; `Interaction` lets the wheel scroll only while hovered
+                sidebar.spawn((
+                    NodeBundle {
+                        style: Style {
+                            width: Val::Percent(100.0),
+                            flex_grow: 1.0,
+                            flex_direction: FlexDirection::Column,
+                            padding: UiRect::all(Val::Px(6.0)),
+                            overflow: Overflow::clip_y(),
+                            ..default()
+                        },
+                        background_color: Color::srgba(0.059, 0.090, 0.165, 0.5).into(),
+                        ..default()
+                    },
+                    Interaction::default(),
+                ))
+                .with_children(|viewport| {
+                    viewport.spawn((
+                        NodeBundle {
+                            style: Style {
+                                width: Val::Percent(100.0),
+                                flex_direction: FlexDirection::Column,
+                                row_gap: Val::Px(4.0),
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        NotificationHistoryList::default(),
+                    ));
+                });
             });
 
             // === CENTER - Game World ===
@@ -469,10 +562,29 @@ pub fn setup_ui(mut commands: Commands) {
     });
 }
 
+/// Bottom-center stack for toast notifications, floating over the game world
+pub fn setup_toasts(mut commands: Commands) {
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                left: Val::Percent(15.0),
+                width: Val::Percent(65.0),
+                bottom: Val::Px(20.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(6.0),
+                ..default()
+            },
+            z_index: ZIndex::Global(5),
+            ..default()
+        },
+        ToastContainer,
+    ));
+}
+
 /// Helper function to spawn an uncle selection card
-fn spawn_uncle_card(parent: &mut ChildBuilder, uncle_type: UncleType) {
-    let is_selected = matches!(uncle_type, UncleType::Mongolian); // Default selection
-    
+fn spawn_uncle_card(parent: &mut ChildBuilder, def: &UncleDef, is_selected: bool) {
     parent.spawn((
         ButtonBundle {
             style: Style {
@@ -491,7 +603,7 @@ fn spawn_uncle_card(parent: &mut ChildBuilder, uncle_type: UncleType) {
             },
             ..default()
         },
-        UncleSelectButton { uncle_type },
+        UncleSelectButton { uncle_type: def.id.clone() },
     ))
     .with_children(|card| {
         // Uncle identifier with color
@@ -503,12 +615,12 @@ fn spawn_uncle_card(parent: &mut ChildBuilder, uncle_type: UncleType) {
                 align_items: AlignItems::Center,
                 ..default()
             },
-            background_color: uncle_type.color().into(),
+            background_color: def.color().into(),
             ..default()
         })
         .with_children(|icon| {
             icon.spawn(TextBundle::from_section(
-                uncle_type.letter(),
+                def.letter.clone(),
                 TextStyle {
                     font_size: 24.0,
                     color: Color::srgb(0.1, 0.1, 0.15),
@@ -519,7 +631,7 @@ fn spawn_uncle_card(parent: &mut ChildBuilder, uncle_type: UncleType) {
 
         // Name
         card.spawn(TextBundle::from_section(
-            uncle_type.name(),
+            def.name.clone(),
             TextStyle {
                 font_size: 13.0,
                 color: Color::srgb(0.945, 0.961, 0.973),
@@ -531,9 +643,9 @@ fn spawn_uncle_card(parent: &mut ChildBuilder, uncle_type: UncleType) {
         card.spawn(TextBundle::from_section(
             format!(
                 "{} gold • {:.1}s\nBasket: {} fish",
-                uncle_type.cost(),
-                uncle_type.speed_ms() as f32 / 1000.0,
-                uncle_type.basket_capacity()
+                def.cost,
+                def.speed_ms as f32 / 1000.0,
+                def.basket_capacity
             ),
             TextStyle {
                 font_size: 11.0,
@@ -544,7 +656,7 @@ fn spawn_uncle_card(parent: &mut ChildBuilder, uncle_type: UncleType) {
 
         // Ability
         card.spawn(TextBundle::from_section(
-            uncle_type.ability(),
+            def.ability.clone(),
             TextStyle {
                 font_size: 10.0,
                 color: Color::srgb(0.796, 0.835, 0.882),
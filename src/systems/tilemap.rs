@@ -1,83 +1,221 @@
 use bevy::prelude::*;
-use rand::Rng;
-use crate::components::{Tile, TileType};
+use bevy::window::PrimaryWindow;
+use crate::actions::PendingActions;
+use crate::components::{CatchAnimation, FishOverlayLabel, OfflineReportPanel, Tile, TileType, Uncle};
 use crate::constants::*;
-use crate::resources::WorldSeed;
+use crate::events::NewWorldRequested;
+use crate::fishing::FishingGrounds;
+use crate::grid::{chunks_between, ChunkCoord, TileGrid};
+use crate::resources::{DayNightCycle, FishingStats, SpeciesRecords, GameState, OfflineReport, PendingOfflineEvents, SelectedUncle, SimulationTick, WorldSeed};
+use crate::species::SpeciesCatalogue;
+use crate::systems::replay::ReplayRecorder;
+use crate::uncles::UncleRoster;
+use crate::worldgen::WorldGenConfig;
 
+/// Generates the home region `WorldGenConfig` describes for the current seed, spawns its
+/// tiles and stocks its water. Further chunks are generated as the camera or actions reach them.
 pub fn generate_tilemap(
     mut commands: Commands,
+    world_seed: Res<WorldSeed>,
+    config: Res<WorldGenConfig>,
+    catalogue: Res<SpeciesCatalogue>,
+) {
+    spawn_home_region(&mut commands, world_seed.seed, &config, &catalogue);
+}
+
+/// Throws the run away and starts over in the world of the requested seed: despawns the map,
+/// every uncle (with their sprites and labels), catch animations and any offline report or its
+/// unsent events, resets gold, the clock, the statistics, the selected uncle and the camera,
+/// and generates the new home region
+pub fn start_new_world(
+    mut requests: EventReader<NewWorldRequested>,
+    mut commands: Commands,
+    world_q: Query<Entity, Or<(With<Tile>, With<Uncle>, With<FishOverlayLabel>, With<OfflineReportPanel>, With<CatchAnimation>)>>,
+    mut camera_q: Query<(&mut Transform, &mut OrthographicProjection), With<Camera>>,
     mut world_seed: ResMut<WorldSeed>,
+    config: Res<WorldGenConfig>,
+    catalogue: Res<SpeciesCatalogue>,
+    mut game_state: ResMut<GameState>,
+    mut day_night: ResMut<DayNightCycle>,
+    mut stats: ResMut<FishingStats>,
+    mut records: ResMut<SpeciesRecords>,
+    (mut selected_uncle, roster): (ResMut<SelectedUncle>, Res<UncleRoster>),
+    mut actions: ResMut<PendingActions>,
+    mut tick: ResMut<SimulationTick>,
+    recorder: Option<ResMut<ReplayRecorder>>,
 ) {
-    let center_x = TILE_WIDTH as f32 / 2.0;
-    let center_y = TILE_HEIGHT as f32 / 2.0;
-
-    for y in 0..TILE_HEIGHT {
-        for x in 0..TILE_WIDTH {
-            // Use seeded RNG for deterministic generation
-            let noise = world_seed.rng.gen::<f32>();
-
-            // Calculate distance from center
-            let dist_from_center = (
-                (x as f32 - center_x).powi(2) + (y as f32 - center_y).powi(2)
-            ).sqrt();
-
-            // Determine tile type
-            let tile_type = if noise < WATER_THRESHOLD || dist_from_center < CENTER_WATER_RADIUS {
-                TileType::Water
-            } else {
-                TileType::Land
-            };
-
-            // Calculate world position
-            let world_x = (x as f32 - (TILE_WIDTH as f32 / 2.0)) * TILE_SIZE;
-            let world_y = (y as f32 - (TILE_HEIGHT as f32 / 2.0)) * TILE_SIZE;
-
-            // Spawn tile entity
-            let color = match tile_type {
-                TileType::Water => Color::srgb(0.047, 0.290, 0.431),
-                TileType::Land => Color::srgb(0.086, 0.639, 0.290),
-            };
-
-            commands.spawn((
-                SpriteBundle {
-                    sprite: Sprite {
-                        color,
-                        custom_size: Some(Vec2::new(TILE_SIZE - 1.0, TILE_SIZE - 1.0)),
-                        ..default()
-                    },
-                    transform: Transform::from_xyz(world_x, world_y, 0.0),
-                    ..default()
-                },
-                Tile {
-                    x,
-                    y,
-                    tile_type,
-                },
-            ));
-        }
+    let Some(&NewWorldRequested { seed }) = requests.read().last() else {
+        return;
+    };
+
+    for entity in &world_q {
+        commands.entity(entity).despawn_recursive();
+    }
+    *world_seed = WorldSeed::from_seed(seed);
+    *game_state = GameState::default();
+    *day_night = DayNightCycle::default();
+    *stats = FishingStats::default();
+    *records = SpeciesRecords::default();
+    selected_uncle.uncle_type = roster.first().id.clone();
+    actions.queue.clear();
+    commands.remove_resource::<OfflineReport>();
+    commands.remove_resource::<PendingOfflineEvents>();
+
+    // A replay covers one world, so recording starts over with the new one
+    tick.0 = 0;
+    if let Some(mut recorder) = recorder {
+        recorder.start = None;
+        recorder.actions.clear();
+    }
+
+    spawn_home_region(&mut commands, seed, &config, &catalogue);
+    for (mut transform, mut projection) in &mut camera_q {
+        let center = home_center(&config);
+        transform.translation.x = center.x;
+        transform.translation.y = center.y;
+        projection.scale = 1.0;
+    }
+    println!("🌱 New world from seed {}", world_seed.code());
+}
+
+/// Generates and spawns the home region, replacing the `TileGrid` and `FishingGrounds`
+fn spawn_home_region(commands: &mut Commands, seed: u64, config: &WorldGenConfig, catalogue: &SpeciesCatalogue) {
+    let mut grid = TileGrid::default();
+    let mut grounds = FishingGrounds::default();
+    for chunk in home_chunks(config) {
+        load_chunk(&mut grid, &mut grounds, seed, config, catalogue, chunk);
+        spawn_chunk(commands, &mut grid, chunk);
+    }
+
+    commands.insert_resource(grounds);
+    commands.insert_resource(grid);
+}
+
+/// World-space middle of the home region, where the camera starts
+pub fn home_center(config: &WorldGenConfig) -> Vec2 {
+    (TileGrid::tile_to_world(0, 0) + TileGrid::tile_to_world(config.width as i32 - 1, config.height as i32 - 1)) / 2.0
+}
+
+/// Chunks covering the `width` × `height` home region at the origin
+pub fn home_chunks(config: &WorldGenConfig) -> Vec<ChunkCoord> {
+    chunks_between((0, 0), (config.width as i32 - 1, config.height as i32 - 1))
+}
+
+/// Generates a chunk's tiles and stocks its water, if that has not happened yet
+pub fn load_chunk(
+    grid: &mut TileGrid,
+    grounds: &mut FishingGrounds,
+    seed: u64,
+    config: &WorldGenConfig,
+    catalogue: &SpeciesCatalogue,
+    chunk: ChunkCoord,
+) {
+    if grid.generate_chunk(seed, config, chunk) {
+        grounds.add_chunk(seed, config, catalogue, grid, chunk);
     }
 }
 
-/// Check if a tile position is adjacent to water
-pub fn is_near_water(x: usize, y: usize, tiles: &Query<&Tile>) -> bool {
-    let directions = [
-        (-1i32, -1i32), (0, -1), (1, -1),
-        (-1, 0),                 (1, 0),
-        (-1, 1),        (0, 1),  (1, 1),
-    ];
-
-    for (dx, dy) in directions {
-        let check_x = x as i32 + dx;
-        let check_y = y as i32 + dy;
-
-        if check_x >= 0 && check_x < TILE_WIDTH as i32 && check_y >= 0 && check_y < TILE_HEIGHT as i32 {
-            for tile in tiles.iter() {
-                if tile.x == check_x as usize && tile.y == check_y as usize && tile.tile_type == TileType::Water {
-                    return true;
-                }
-            }
+/// Loads every chunk within `radius` tiles of (x, y), so rules looking that far see the whole map
+pub fn load_chunks_around(
+    grid: &mut TileGrid,
+    grounds: &mut FishingGrounds,
+    seed: u64,
+    config: &WorldGenConfig,
+    catalogue: &SpeciesCatalogue,
+    (x, y): (i32, i32),
+    radius: u32,
+) {
+    let r = radius as i32;
+    for chunk in chunks_between((x - r, y - r), (x + r, y + r)) {
+        load_chunk(grid, grounds, seed, config, catalogue, chunk);
+    }
+}
+
+/// Spawns the tile sprites of a generated chunk and records their entities in the grid
+pub fn spawn_chunk(commands: &mut Commands, grid: &mut TileGrid, chunk: ChunkCoord) {
+    if grid.is_spawned(chunk) {
+        return;
+    }
+    let entities = grid
+        .chunk_tiles(chunk)
+        .into_iter()
+        .map(|(x, y, tile_type)| spawn_tile(commands, x, y, tile_type))
+        .collect();
+    grid.set_entities(chunk, entities);
+}
+
+/// Despawns a chunk's tile sprites; its tiles stay in the grid
+pub fn despawn_chunk(commands: &mut Commands, grid: &mut TileGrid, chunk: ChunkCoord) {
+    for entity in grid.take_entities(chunk) {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// Spawns a single tile sprite at its grid position
+fn spawn_tile(commands: &mut Commands, x: i32, y: i32, tile_type: TileType) -> Entity {
+    let world_pos = TileGrid::tile_to_world(x, y);
+
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: tile_type.color(),
+                custom_size: Some(Vec2::new(TILE_SIZE - 1.0, TILE_SIZE - 1.0)),
+                ..default()
+            },
+            transform: Transform::from_xyz(world_pos.x, world_pos.y, 0.0),
+            ..default()
+        },
+        Tile {
+            x,
+            y,
+            tile_type,
+        },
+    )).id()
+}
+
+/// Changes a tile in the grid and on its entity (if spawned), recoloring the sprite
+pub fn set_tile(grid: &mut TileGrid, tiles_q: &mut Query<(&mut Tile, &mut Sprite)>, x: i32, y: i32, tile_type: TileType) {
+    grid.set(x, y, tile_type);
+    if let Some((mut tile, mut sprite)) = grid.entity(x, y).and_then(|e| tiles_q.get_mut(e).ok()) {
+        tile.tile_type = tile_type;
+        sprite.color = tile_type.color();
+    }
+}
+
+/// Spawns chunks coming into view (generating them first if needed) and despawns chunks
+/// that have scrolled well out of it
+pub fn stream_chunks(
+    mut commands: Commands,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    camera_q: Query<(&Camera, &GlobalTransform)>,
+    mut grid: ResMut<TileGrid>,
+    mut grounds: ResMut<FishingGrounds>,
+    world_seed: Res<WorldSeed>,
+    config: Res<WorldGenConfig>,
+    catalogue: Res<SpeciesCatalogue>,
+) {
+    let (Ok(window), Ok((camera, camera_transform))) = (windows.get_single(), camera_q.get_single()) else {
+        return;
+    };
+    let corners = [Vec2::ZERO, window.size()].map(|corner| camera.viewport_to_world_2d(camera_transform, corner));
+    let [Some(a), Some(b)] = corners else {
+        return;
+    };
+    let (min, max) = (TileGrid::world_to_tile(a.min(b)), TileGrid::world_to_tile(a.max(b)));
+    let margin = |chunks: i32| chunks * CHUNK_SIZE as i32;
+
+    let near = |m: i32| chunks_between((min.0 - margin(m), min.1 - margin(m)), (max.0 + margin(m), max.1 + margin(m)));
+    for chunk in near(CHUNK_SPAWN_MARGIN) {
+        if !grid.is_spawned(chunk) {
+            load_chunk(&mut grid, &mut grounds, world_seed.seed, &config, &catalogue, chunk);
+            spawn_chunk(&mut commands, &mut grid, chunk);
         }
     }
 
-    false
+    let keep = near(CHUNK_DESPAWN_MARGIN);
+    for chunk in grid.spawned_chunks() {
+        if !keep.contains(&chunk) {
+            despawn_chunk(&mut commands, &mut grid, chunk);
+        }
+    }
 }
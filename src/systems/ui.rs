@@ -1,7 +1,19 @@
+use arboard::Clipboard;
 use bevy::prelude::*;
 use bevy::ecs::system::ParamSet;
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::input::ButtonState;
+use bevy::ui::FocusPolicy;
+use crate::actions::{PendingActions, PlayerAction};
 use crate::components::*;
-use crate::resources::{GameState, WorldSeed, SelectedUncle};
+use crate::constants::{FISH_SPRITE_HEIGHT, FISH_SPRITE_WIDTH, SEED_ENTRY_MAX_LEN};
+use crate::events::NewWorldRequested;
+use crate::fish_sprite::{FishLook, FishSprites};
+use crate::resources::{BasketSort, SpeciesRecords, GameState, WorldSeed, OfflineReport, DayNightCycle, SeedEntry, NewWorldDialog, Notification, NotificationLog};
+use crate::seed::{parse_seed, random_seed};
+use crate::states::AppState;
+use crate::systems::economy::cash_out_available;
+use crate::uncles::{UncleRoster, UncleType};
 
 /// Updates all UI text displays based on current game state
 pub fn update_ui_system(
@@ -30,7 +42,7 @@ pub fn update_ui_system(
 
     // Update seed
     if let Ok(mut text) = seed_text_q.get_single_mut() {
-        text.sections[0].value = format!("Seed: {}", world_seed.seed);
+        text.sections[0].value = format!("Seed: {}", world_seed.code());
     }
 
     // Update cooldown display
@@ -66,12 +78,16 @@ pub fn update_basket_value_display(
 
 /// Updates basket display showing fish in selected uncle's basket
 pub fn update_basket_display(
-    uncles_q: Query<&Uncle, (With<SelectedUncleMarker>, Changed<Uncle>)>,
+    uncles_q: Query<Ref<Uncle>, With<SelectedUncleMarker>>,
     mut commands: Commands,
     basket_container: Query<Entity, With<UncleBasketDisplay>>,
     existing_entries: Query<Entity, With<FishFeedEntry>>,
+    roster: Res<UncleRoster>,
+    sort: Res<BasketSort>,
+    records: Res<SpeciesRecords>,
+    mut sprites: ResMut<FishSprites>,
+    mut images: ResMut<Assets<Image>>,
 ) {
-    // Only update if selection changed or uncle basket changed
     let uncle = match uncles_q.get_single() {
         Ok(u) => u,
         Err(_) => {
@@ -102,6 +118,10 @@ pub fn update_basket_display(
             return;
         }
     };
+    // Only update if the uncle's basket, the sort order or a record changed
+    if !uncle.is_changed() && !sort.is_changed() && !records.is_changed() {
+        return;
+    }
 
     let container = match basket_container.get_single() {
         Ok(entity) => entity,
@@ -114,6 +134,7 @@ pub fn update_basket_display(
     }
 
     // Show uncle info header
+    let def = roster.get(&uncle.uncle_type);
     commands.entity(container).with_children(|parent| {
         parent.spawn((
             NodeBundle {
@@ -125,14 +146,14 @@ pub fn update_basket_display(
                     margin: UiRect::bottom(Val::Px(10.0)),
                     ..default()
                 },
-                background_color: uncle.uncle_type.color().with_alpha(0.3).into(),
+                background_color: def.map_or(Color::NONE, |def| def.color().with_alpha(0.3)).into(),
                 ..default()
             },
             FishFeedEntry,
         ))
         .with_children(|header| {
             header.spawn(TextBundle::from_section(
-                format!("{} {}", uncle.uncle_type.emoji(), uncle.uncle_type.name()),
+                def.map_or(uncle.uncle_type.to_string(), |def| format!("{} {}", def.emoji, def.name)),
                 TextStyle {
                     font_size: 14.0,
                     color: Color::srgb(0.945, 0.961, 0.973),
@@ -155,6 +176,28 @@ pub fn update_basket_display(
                     ..default()
                 },
             ));
+            header.spawn((
+                ButtonBundle {
+                    style: Style {
+                        padding: UiRect::axes(Val::Px(6.0), Val::Px(2.0)),
+                        align_self: AlignSelf::FlexStart,
+                        ..default()
+                    },
+                    background_color: Color::srgb(0.2, 0.255, 0.333).into(),
+                    ..default()
+                },
+                BasketSortButton,
+            ))
+            .with_children(|button| {
+                button.spawn(TextBundle::from_section(
+                    format!("SORT: {}", sort.name()),
+                    TextStyle {
+                        font_size: 10.0,
+                        color: Color::srgb(0.796, 0.835, 0.882),
+                        ..default()
+                    },
+                ));
+            });
         });
     });
 
@@ -174,7 +217,9 @@ pub fn update_basket_display(
             ));
         });
     } else {
-        for fish in uncle.basket.fish.iter() {
+        let mut fish_list: Vec<&Fish> = uncle.basket.fish.iter().collect();
+        sort.sort(&mut fish_list);
+        for fish in fish_list {
             commands.entity(container).with_children(|parent| {
                 parent.spawn((
                     NodeBundle {
@@ -191,12 +236,44 @@ pub fn update_basket_display(
                     FishFeedEntry,
                 ))
                 .with_children(|entry| {
-                    // Fish name
+                    // Sprite and name, marked if it is the heaviest of its species this run
+                    let is_record = records.records.get(&fish.species).is_some_and(|record| record.weight_kg == fish.weight_kg);
+                    entry.spawn(NodeBundle {
+                        style: Style {
+                            align_items: AlignItems::Center,
+                            column_gap: Val::Px(6.0),
+                            ..default()
+                        },
+                        ..default()
+                    })
+                    .with_children(|row| {
+                        row.spawn(ImageBundle {
+                            style: Style {
+                                width: Val::Px(FISH_SPRITE_WIDTH as f32 * 0.75),
+                                height: Val::Px(FISH_SPRITE_HEIGHT as f32 * 0.75),
+                                flex_shrink: 0.0,
+                                ..default()
+                            },
+                            image: UiImage::new(sprites.get(FishLook::of(fish), &mut images)),
+                            ..default()
+                        });
+                        row.spawn(TextBundle::from_section(
+                            if is_record { format!("{} • RECORD", fish.name) } else { fish.name.clone() },
+                            TextStyle {
+                                font_size: 12.0,
+                                color: if is_record { Color::srgb(0.984, 0.749, 0.141) } else { Color::srgb(0.945, 0.961, 0.973) },
+                                ..default()
+                            },
+                        ));
+                    });
                     entry.spawn(TextBundle::from_section(
-                        &fish.name,
+                        match fish.measurements() {
+                            Some(measurements) => format!("{} • {}", fish.traits.describe(), measurements),
+                            None => fish.traits.describe(),
+                        },
                         TextStyle {
-                            font_size: 12.0,
-                            color: Color::srgb(0.945, 0.961, 0.973),
+                            font_size: 10.0,
+                            color: fish.traits.color.color(),
                             ..default()
                         },
                     ));
@@ -233,20 +310,30 @@ pub fn update_basket_display(
     }
 }
 
+/// The basket panel's SORT button moves on to the next `BasketSort`
+pub fn cycle_basket_sort(
+    interaction_q: Query<&Interaction, (Changed<Interaction>, With<BasketSortButton>)>,
+    mut sort: ResMut<BasketSort>,
+) {
+    if interaction_q.iter().any(|interaction| *interaction == Interaction::Pressed) {
+        *sort = sort.next();
+    }
+}
+
 /// Handles uncle type selection button clicks with visual feedback
 pub fn handle_uncle_selection(
     interaction_q: Query<(&Interaction, &UncleSelectButton), Changed<Interaction>>,
     mut button_query: ParamSet<(
         Query<(&UncleSelectButton, &mut BorderColor)>,
     )>,
-    mut selected_uncle: ResMut<SelectedUncle>,
+    mut actions: ResMut<PendingActions>,
 ) {
     // Check for interactions first
     let mut selected_type: Option<UncleType> = None;
     for (interaction, button) in interaction_q.iter() {
         if *interaction == Interaction::Pressed {
-            selected_type = Some(button.uncle_type);
-            selected_uncle.uncle_type = button.uncle_type;
+            selected_type = Some(button.uncle_type.clone());
+            actions.push(PlayerAction::SelectUncleType(button.uncle_type.clone()));
             break;
         }
     }
@@ -266,30 +353,11 @@ pub fn handle_uncle_selection(
 /// Handles cash out button clicks (selected uncle only)
 pub fn handle_cash_out_button(
     interaction_q: Query<&Interaction, (Changed<Interaction>, With<CashOutButton>)>,
-    mut uncles_q: Query<&mut Uncle, With<SelectedUncleMarker>>,
-    mut game_state: ResMut<GameState>,
+    mut actions: ResMut<PendingActions>,
 ) {
     for interaction in interaction_q.iter() {
         if *interaction == Interaction::Pressed {
-            if game_state.cash_out_cooldown > 0.0 {
-                continue;
-            }
-
-            // Cash out selected uncle if any
-            if let Ok(mut uncle) = uncles_q.get_single_mut() {
-                if uncle.basket.fish.is_empty() {
-                    continue;
-                }
-
-                let caught_fish = uncle.basket.cash_out();
-                let total_value: u32 = caught_fish.iter().map(|f| f.value).sum();
-                let gold_earned = (total_value as f32 * game_state.multiplier) as u32;
-
-                game_state.gold += gold_earned;
-                game_state.fish_count += caught_fish.len() as u32;
-                game_state.multiplier = (game_state.multiplier + 0.1).min(5.0);
-                game_state.cash_out_cooldown = 30.0;
-            }
+            actions.push(PlayerAction::CashOutSelected);
         }
     }
 }
@@ -297,33 +365,11 @@ pub fn handle_cash_out_button(
 /// Handles cash out ALL button clicks
 pub fn handle_cash_out_all_button(
     interaction_q: Query<&Interaction, (Changed<Interaction>, With<CashOutAllButton>)>,
-    mut uncles_q: Query<&mut Uncle>,
-    mut game_state: ResMut<GameState>,
+    mut actions: ResMut<PendingActions>,
 ) {
     for interaction in interaction_q.iter() {
         if *interaction == Interaction::Pressed {
-            if game_state.cash_out_cooldown > 0.0 {
-                continue;
-            }
-
-            let mut total_fish = 0;
-            let mut total_value = 0;
-
-            for mut uncle in uncles_q.iter_mut() {
-                if !uncle.basket.fish.is_empty() {
-                    let caught_fish = uncle.basket.cash_out();
-                    total_fish += caught_fish.len();
-                    total_value += caught_fish.iter().map(|f| f.value).sum::<u32>();
-                }
-            }
-
-            if total_fish > 0 {
-                let gold_earned = (total_value as f32 * game_state.multiplier) as u32;
-                game_state.gold += gold_earned;
-                game_state.fish_count += total_fish as u32;
-                game_state.multiplier = (game_state.multiplier + 0.1).min(5.0);
-                game_state.cash_out_cooldown = 30.0;
-            }
+            actions.push(PlayerAction::CashOutAll);
         }
     }
 }
@@ -332,9 +378,10 @@ pub fn handle_cash_out_all_button(
 pub fn cash_out_button_visual(
     mut interaction_q: Query<(&Interaction, &mut BackgroundColor), (Changed<Interaction>, With<CashOutButton>)>,
     game_state: Res<GameState>,
+    day_night: Res<DayNightCycle>,
 ) {
     for (interaction, mut color) in interaction_q.iter_mut() {
-        let can_cash_out = game_state.cash_out_cooldown <= 0.0;
+        let can_cash_out = cash_out_available(&game_state, &day_night);
         
         match *interaction {
             Interaction::Pressed => {
@@ -364,9 +411,10 @@ pub fn cash_out_button_visual(
 pub fn cash_out_all_button_visual(
     mut interaction_q: Query<(&Interaction, &mut BackgroundColor), (Changed<Interaction>, With<CashOutAllButton>)>,
     game_state: Res<GameState>,
+    day_night: Res<DayNightCycle>,
 ) {
     for (interaction, mut color) in interaction_q.iter_mut() {
-        let can_cash_out = game_state.cash_out_cooldown <= 0.0;
+        let can_cash_out = cash_out_available(&game_state, &day_night);
         
         match *interaction {
             Interaction::Pressed => {
@@ -411,29 +459,370 @@ pub fn uncle_button_visual(
     }
 }
 
-/// Handles new world generation button click (currently unused but ready for future)
-pub fn handle_new_world(
-    interaction_q: Query<&Interaction, (Changed<Interaction>, With<NewWorldButton>)>,
+/// Clicking the seed code copies it to the system clipboard, with a toast to confirm.
+/// The clipboard is kept open afterwards: on X11 and Wayland the copied text is only
+/// available while the program that copied it holds on to it.
+pub fn copy_seed_code(
+    interaction_q: Query<&Interaction, (Changed<Interaction>, With<SeedCodeButton>)>,
+    world_seed: Res<WorldSeed>,
+    day_night: Res<DayNightCycle>,
+    mut log: ResMut<NotificationLog>,
+    mut clipboard: Local<Option<Clipboard>>,
+) {
+    if !interaction_q.iter().any(|interaction| *interaction == Interaction::Pressed) {
+        return;
+    }
+    let code = world_seed.code();
+    if clipboard.is_none() {
+        *clipboard = Clipboard::new().map_err(|e| eprintln!("⚠️ No clipboard: {}", e)).ok();
+    }
+    let copied = clipboard.as_mut().is_some_and(|clipboard| {
+        clipboard.set_text(code.clone()).map_err(|e| eprintln!("⚠️ Could not copy the seed code: {}", e)).is_ok()
+    });
+
+    let (text, color) = if copied {
+        println!("📋 Copied seed code {} (seed {})", code, world_seed.seed);
+        (format!("Seed code {} copied", code), Color::srgb(0.4, 0.8, 0.5))
+    } else {
+        (format!("Could not copy the seed code {}", code), Color::srgb(0.9, 0.4, 0.4))
+    };
+    log.push(Notification {
+        text,
+        color,
+        timestamp: format!("Day {} {}", day_night.day_number, day_night.time_string()),
+        toast: true,
+    });
+}
+
+/// Run condition: false while the seed field has keyboard focus, so typing a seed does not
+/// also trigger hotkeys or pan the camera
+pub fn seed_entry_closed(entry: Res<SeedEntry>) -> bool {
+    !entry.focused
+}
+
+/// Typing into the focused seed field: a seed code, a number or any phrase
+pub fn type_seed_input(
+    mut keys: EventReader<KeyboardInput>,
+    mut entry: ResMut<SeedEntry>,
+) {
+    if !entry.focused {
+        keys.clear();
+        return;
+    }
+    for key in keys.read().filter(|key| key.state == ButtonState::Pressed) {
+        match &key.logical_key {
+            Key::Backspace => {
+                entry.text.pop();
+            }
+            Key::Space => push_seed_chars(&mut entry.text, " "),
+            Key::Character(chars) => push_seed_chars(&mut entry.text, chars),
+            _ => {}
+        }
+    }
+}
+
+fn push_seed_chars(text: &mut String, chars: &str) {
+    for c in chars.chars().filter(|c| !c.is_control()) {
+        if text.chars().count() < SEED_ENTRY_MAX_LEN {
+            text.push(c);
+        }
+    }
+}
+
+/// Shows the typed seed with a caret while focused, or a placeholder while empty
+pub fn update_seed_input_text(
+    entry: Res<SeedEntry>,
+    mut text_q: Query<&mut Text, With<SeedInputText>>,
+) {
+    if !entry.is_changed() {
+        return;
+    }
+    for mut text in &mut text_q {
+        let section = &mut text.sections[0];
+        if !entry.text.is_empty() {
+            section.value = format!("{}{}", entry.text, if entry.focused { "|" } else { "" });
+            section.style.color = Color::srgb(0.945, 0.961, 0.973);
+        } else {
+            section.value = "Random seed".to_string();
+            section.style.color = Color::srgb(0.6, 0.65, 0.7);
+        }
+    }
+}
+
+/// Spawns the "while you were away" summary after a save is resumed
+pub fn show_offline_report(
+    mut commands: Commands,
+    report: Res<OfflineReport>,
+) {
+    let hours = (report.seconds_away / 3600.0) as u32;
+    let minutes = ((report.seconds_away % 3600.0) / 60.0) as u32;
+
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            background_color: Color::srgba(0.0, 0.0, 0.0, 0.5).into(),
+            z_index: ZIndex::Global(10),
+            ..default()
+        },
+        OfflineReportPanel,
+    ))
+    .with_children(|overlay| {
+        overlay.spawn(NodeBundle {
+            style: Style {
+                width: Val::Px(340.0),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(20.0)),
+                row_gap: Val::Px(10.0),
+                ..default()
+            },
+            background_color: Color::srgba(0.118, 0.161, 0.231, 0.98).into(),
+            ..default()
+        })
+        .with_children(|panel| {
+            panel.spawn(TextBundle::from_section(
+                "While you were away",
+                TextStyle {
+                    font_size: 22.0,
+                    color: Color::srgb(0.945, 0.961, 0.973),
+                    ..default()
+                },
+            ));
+            panel.spawn(TextBundle::from_section(
+                format!("{}h {}m of fishing", hours, minutes),
+                TextStyle {
+                    font_size: 14.0,
+                    color: Color::srgb(0.796, 0.835, 0.882),
+                    ..default()
+                },
+            ));
+            panel.spawn(TextBundle::from_section(
+                format!(
+                    "Fish caught: {}\nFish escaped: {}\nDays passed: {}",
+                    report.caught.len(), report.escaped.len(), report.days_passed
+                ),
+                TextStyle {
+                    font_size: 16.0,
+                    color: Color::srgb(0.984, 0.749, 0.141),
+                    ..default()
+                },
+            ));
+            panel.spawn((
+                ButtonBundle {
+                    style: Style {
+                        width: Val::Percent(100.0),
+                        height: Val::Px(40.0),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    background_color: Color::srgb(0.13, 0.77, 0.37).into(),
+                    ..default()
+                },
+                OfflineReportDismissButton,
+            ))
+            .with_children(|button| {
+                button.spawn(TextBundle::from_section(
+                    "CONTINUE",
+                    TextStyle {
+                        font_size: 14.0,
+                        color: Color::srgb(0.945, 0.961, 0.973),
+                        ..default()
+                    },
+                ));
+            });
+        });
+    });
+}
+
+/// Closes the "while you were away" summary
+pub fn dismiss_offline_report(
+    interaction_q: Query<&Interaction, (Changed<Interaction>, With<OfflineReportDismissButton>)>,
+    panel_q: Query<Entity, With<OfflineReportPanel>>,
     mut commands: Commands,
-    mut world_seed: ResMut<WorldSeed>,
-    mut game_state: ResMut<GameState>,
-    tiles_q: Query<Entity, With<Tile>>,
-    uncles_q: Query<Entity, With<Uncle>>,
 ) {
     for interaction in interaction_q.iter() {
         if *interaction == Interaction::Pressed {
-            world_seed.new_seed();
-            game_state.fish_count = 0;
-            game_state.gold = 100;
-            game_state.multiplier = 1.0;
-            game_state.cash_out_cooldown = 0.0;
-
-            for entity in tiles_q.iter() {
-                commands.entity(entity).despawn();
-            }
-            for entity in uncles_q.iter() {
-                commands.entity(entity).despawn();
+            for panel in panel_q.iter() {
+                commands.entity(panel).despawn_recursive();
             }
+            commands.remove_resource::<OfflineReport>();
         }
     }
 }
+
+/// The header's NEW WORLD button opens the confirmation dialog
+pub fn open_new_world_dialog(
+    interaction_q: Query<&Interaction, (Changed<Interaction>, With<NewWorldButton>)>,
+    mut commands: Commands,
+) {
+    if interaction_q.iter().any(|interaction| *interaction == Interaction::Pressed) {
+        commands.init_resource::<NewWorldDialog>();
+    }
+}
+
+/// Spawns the New World confirmation: a warning, the seed field (focused) and START / CANCEL
+pub fn show_new_world_dialog(
+    mut commands: Commands,
+    mut entry: ResMut<SeedEntry>,
+) {
+    entry.text.clear();
+    entry.focused = true;
+
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            background_color: Color::srgba(0.0, 0.0, 0.0, 0.5).into(),
+            focus_policy: FocusPolicy::Block,
+            z_index: ZIndex::Global(30),  // Above the menu screens it can be opened from
+            ..default()
+        },
+        NewWorldPanel,
+    ))
+    .with_children(|overlay| {
+        overlay.spawn(NodeBundle {
+            style: Style {
+                width: Val::Px(340.0),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(20.0)),
+                row_gap: Val::Px(10.0),
+                ..default()
+            },
+            background_color: Color::srgba(0.118, 0.161, 0.231, 0.98).into(),
+            ..default()
+        })
+        .with_children(|panel| {
+            panel.spawn(TextBundle::from_section(
+                "Start a new world?",
+                TextStyle {
+                    font_size: 22.0,
+                    color: Color::srgb(0.945, 0.961, 0.973),
+                    ..default()
+                },
+            ));
+            panel.spawn(TextBundle::from_section(
+                "Your uncles, fish, gold and docks will be lost.\nType a seed code or phrase, or leave it empty for a random world.",
+                TextStyle {
+                    font_size: 14.0,
+                    color: Color::srgb(0.796, 0.835, 0.882),
+                    ..default()
+                },
+            ));
+            panel.spawn((
+                NodeBundle {
+                    style: Style {
+                        width: Val::Percent(100.0),
+                        height: Val::Px(32.0),
+                        padding: UiRect::horizontal(Val::Px(8.0)),
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    background_color: Color::srgb(0.2, 0.255, 0.333).into(),
+                    ..default()
+                },
+                SeedInputField,
+            ))
+            .with_children(|field| {
+                field.spawn((
+                    TextBundle::from_section(
+                        "Random seed",
+                        TextStyle {
+                            font_size: 14.0,
+                            color: Color::srgb(0.6, 0.65, 0.7),
+                            ..default()
+                        },
+                    ),
+                    SeedInputText,
+                ));
+            });
+            panel.spawn(NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    column_gap: Val::Px(10.0),
+                    ..default()
+                },
+                ..default()
+            })
+            .with_children(|row| {
+                for (label, color, confirm) in [
+                    ("START", Color::srgb(0.13, 0.77, 0.37), true),
+                    ("CANCEL", Color::srgb(0.392, 0.455, 0.545), false),
+                ] {
+                    let mut button = row.spawn(ButtonBundle {
+                        style: Style {
+                            flex_grow: 1.0,
+                            height: Val::Px(40.0),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        background_color: color.into(),
+                        ..default()
+                    });
+                    if confirm {
+                        button.insert(NewWorldConfirmButton);
+                    } else {
+                        button.insert(NewWorldCancelButton);
+                    }
+                    button.with_children(|button| {
+                        button.spawn(TextBundle::from_section(
+                            label,
+                            TextStyle {
+                                font_size: 14.0,
+                                color: Color::srgb(0.945, 0.961, 0.973),
+                                ..default()
+                            },
+                        ));
+                    });
+                }
+            });
+        });
+    });
+}
+
+/// START or Enter starts playing a new world from the typed seed (a random one if the field is
+/// empty); CANCEL or Escape closes the dialog and goes back to whatever was underneath
+pub fn handle_new_world(
+    confirm_q: Query<&Interaction, (Changed<Interaction>, With<NewWorldConfirmButton>)>,
+    cancel_q: Query<&Interaction, (Changed<Interaction>, With<NewWorldCancelButton>)>,
+    panel_q: Query<Entity, With<NewWorldPanel>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut entry: ResMut<SeedEntry>,
+    mut new_world: EventWriter<NewWorldRequested>,
+    mut next_state: ResMut<NextState<AppState>>,
+    mut commands: Commands,
+) {
+    let confirmed = keyboard.just_pressed(KeyCode::Enter)
+        || keyboard.just_pressed(KeyCode::NumpadEnter)
+        || confirm_q.iter().any(|interaction| *interaction == Interaction::Pressed);
+    let cancelled = keyboard.just_pressed(KeyCode::Escape)
+        || cancel_q.iter().any(|interaction| *interaction == Interaction::Pressed);
+    if !confirmed && !cancelled {
+        return;
+    }
+
+    if confirmed {
+        let seed = parse_seed(&entry.text).unwrap_or_else(random_seed);
+        new_world.send(NewWorldRequested { seed });
+        next_state.set(AppState::Playing);
+    }
+    for panel in panel_q.iter() {
+        commands.entity(panel).despawn_recursive();
+    }
+    commands.remove_resource::<NewWorldDialog>();
+    entry.text.clear();
+    entry.focused = false;
+}
@@ -0,0 +1,174 @@
+//! The uncle roster: every kind of uncle that can be hired, loaded from data.
+//!
+//! Uncle definitions (stats, ability text, colors and an optional sprite) live in
+//! `assets/uncles.json`. The game reads that file on startup, so uncles can be added or
+//! rebalanced without recompiling; the same file is built into the binary as the default
+//! roster for headless runs and for when the file is missing. Everything else refers to an
+//! uncle by its definition id (`UncleType`) and looks the definition up in `UncleRoster`.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt;
+use std::path::Path;
+
+/// Where the game looks for the roster, relative to the working directory
+pub const UNCLE_ROSTER_PATH: &str = "assets/uncles.json";
+
+/// The roster the game ships with
+const BUILT_IN_ROSTER: &str = include_str!("../assets/uncles.json");
+
+/// Id of an uncle definition in the `UncleRoster`, e.g. `"Mongolian"`.
+/// Saves, replays and statistics store uncles by this id.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct UncleType(String);
+
+impl UncleType {
+    pub fn new(id: &str) -> Self {
+        Self(id.to_string())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for UncleType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// One kind of uncle: what hiring it costs and how it fishes
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct UncleDef {
+    pub id: UncleType,
+    pub name: String,
+    pub letter: String,                    // Drawn on the colored square when there is no sprite
+    pub emoji: String,
+    pub color: [f32; 3],                   // sRGB
+    #[serde(default)]
+    pub sprite: Option<String>,            // Asset path of the uncle sprite
+    pub cost: u32,
+    pub speed_ms: u64,                     // Time between catches in fully stocked water
+    pub basket_capacity: usize,
+    pub casting_radius: u32,               // How many tiles out (diagonals included) the uncle can cast
+    #[serde(default)]
+    pub rare_bonus: f32,                   // Added to the rare chance of every catch
+    pub retention: f32,                    // Multiplies the escape chance of fish in the basket
+    pub ability: String,
+    pub description: String,
+}
+
+impl UncleDef {
+    pub fn color(&self) -> Color {
+        let [r, g, b] = self.color;
+        Color::srgb(r, g, b)
+    }
+}
+
+#[derive(Debug)]
+pub enum RosterError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    Invalid(String),
+}
+
+impl fmt::Display for RosterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RosterError::Io(err) => write!(f, "I/O error: {}", err),
+            RosterError::Json(err) => write!(f, "malformed roster: {}", err),
+            RosterError::Invalid(reason) => write!(f, "invalid roster: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for RosterError {}
+
+impl From<std::io::Error> for RosterError {
+    fn from(err: std::io::Error) -> Self {
+        RosterError::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for RosterError {
+    fn from(err: serde_json::Error) -> Self {
+        RosterError::Json(err)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct RosterFile {
+    uncles: Vec<UncleDef>,
+}
+
+/// Every uncle that can be hired, in the order the sidebar lists them
+#[derive(Resource, Clone, Debug)]
+pub struct UncleRoster {
+    uncles: Vec<UncleDef>,
+}
+
+impl Default for UncleRoster {
+    fn default() -> Self {
+        Self::from_json(BUILT_IN_ROSTER).expect("built-in uncle roster is valid")
+    }
+}
+
+impl UncleRoster {
+    /// Checks that there is at least one uncle, ids are unique and every uncle can fish
+    pub fn new(uncles: Vec<UncleDef>) -> Result<Self, RosterError> {
+        if uncles.is_empty() {
+            return Err(RosterError::Invalid("no uncles".to_string()));
+        }
+        let mut ids = HashSet::new();
+        for uncle in &uncles {
+            if !ids.insert(&uncle.id) {
+                return Err(RosterError::Invalid(format!("uncle {} is defined twice", uncle.id)));
+            }
+            if uncle.speed_ms == 0 || uncle.basket_capacity == 0 {
+                return Err(RosterError::Invalid(format!("uncle {} needs a speed and a basket", uncle.id)));
+            }
+        }
+        Ok(Self { uncles })
+    }
+
+    pub fn from_json(text: &str) -> Result<Self, RosterError> {
+        let file: RosterFile = serde_json::from_str(text)?;
+        Self::new(file.uncles)
+    }
+
+    pub fn read(path: &Path) -> Result<Self, RosterError> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+
+    /// The roster at `path`, or the built-in one if the file is missing or broken
+    pub fn read_or_default(path: &Path) -> Self {
+        match Self::read(path) {
+            Ok(roster) => {
+                println!("🧔 Loaded {} uncles from {}", roster.uncles.len(), path.display());
+                roster
+            }
+            Err(RosterError::Io(err)) if err.kind() == std::io::ErrorKind::NotFound => Self::default(),
+            Err(err) => {
+                eprintln!("⚠️ Could not load {}: {}; using the built-in uncles", path.display(), err);
+                Self::default()
+            }
+        }
+    }
+
+    pub fn get(&self, id: &UncleType) -> Option<&UncleDef> {
+        self.uncles.iter().find(|uncle| uncle.id == *id)
+    }
+
+    /// Every definition, in roster order
+    pub fn iter(&self) -> std::slice::Iter<'_, UncleDef> {
+        self.uncles.iter()
+    }
+
+    /// The first uncle, selected for hiring at the start of a run
+    pub fn first(&self) -> &UncleDef {
+        &self.uncles[0]
+    }
+}
@@ -0,0 +1,287 @@
+//! Deterministic terrain generation.
+//!
+//! Elevation is fractal Perlin noise sampled at tile coordinates, shaped by a falloff
+//! (island, central lake or coast) and thresholded into water and land. A few passes of
+//! cellular-automata smoothing then clean up the coastline. Water is split into deep water
+//! and shallows by depth; land into beaches along low coasts, rock on high ground and forest
+//! where a second moisture noise is high. Rivers follow the zero contour of a third noise.
+//! Every tile depends only on the seed, the config and the tiles around it, so any rectangle
+//! of the world can be generated on its own and still line up with its neighbours.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::components::TileType;
+use crate::constants::*;
+
+/// Shape pushed onto the noise so maps have a recognisable layout
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum Falloff {
+    /// Pure noise
+    None,
+    /// Land in the middle, sea towards the edges
+    Island,
+    /// A lake around the centre, land towards the edges
+    Lake,
+    /// Sea along the left (west) edge, land to the east
+    Coast,
+}
+
+/// Tunable terrain generator parameters
+#[derive(Resource, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct WorldGenConfig {
+    pub width: usize,
+    pub height: usize,
+    pub noise_scale: f32,       // Size in tiles of the largest noise features
+    pub octaves: u32,           // Noise layers summed for detail
+    pub persistence: f32,       // Amplitude kept by each successive octave
+    pub lacunarity: f32,        // Frequency gain of each successive octave
+    pub water_level: f32,       // Elevation (0..1) below which a tile is water
+    pub falloff: Falloff,
+    pub falloff_strength: f32,  // How strongly the falloff shape overrides the noise
+    pub smoothing_passes: u32,  // Cellular-automata passes that tidy the coastline
+    pub deep_water_depth: f32,  // Water this far below `water_level` is deep
+    pub shallows_depth: f32,    // Water within this of `water_level` is shallow
+    pub beach_height: f32,      // Coastal land within this of `water_level` is beach
+    pub rock_level: f32,        // Elevation above which land is rock
+    pub forest_level: f32,      // Moisture (0..1) above which land is forest
+    pub river_scale: f32,       // Size in tiles of river bends
+    pub river_width: f32,       // Band of river noise around zero that is river
+}
+
+impl Default for WorldGenConfig {
+    fn default() -> Self {
+        Self {
+            width: TILE_WIDTH,
+            height: TILE_HEIGHT,
+            noise_scale: WORLDGEN_NOISE_SCALE,
+            octaves: WORLDGEN_OCTAVES,
+            persistence: WORLDGEN_PERSISTENCE,
+            lacunarity: WORLDGEN_LACUNARITY,
+            water_level: WORLDGEN_WATER_LEVEL,
+            falloff: Falloff::Lake,
+            falloff_strength: WORLDGEN_FALLOFF_STRENGTH,
+            smoothing_passes: WORLDGEN_SMOOTHING_PASSES,
+            deep_water_depth: WORLDGEN_DEEP_WATER_DEPTH,
+            shallows_depth: WORLDGEN_SHALLOWS_DEPTH,
+            beach_height: WORLDGEN_BEACH_HEIGHT,
+            rock_level: WORLDGEN_ROCK_LEVEL,
+            forest_level: WORLDGEN_FOREST_LEVEL,
+            river_scale: WORLDGEN_RIVER_SCALE,
+            river_width: WORLDGEN_RIVER_WIDTH,
+        }
+    }
+}
+
+impl WorldGenConfig {
+    /// Elevation of a tile in 0..1, before thresholding
+    pub fn elevation(&self, seed: u64, x: i32, y: i32) -> f32 {
+        let noise = fbm(
+            seed,
+            x as f32 / self.noise_scale,
+            y as f32 / self.noise_scale,
+            self.octaves,
+            self.persistence,
+            self.lacunarity,
+        );
+        (noise - self.falloff_strength * self.falloff_shape(x, y)).clamp(0.0, 1.0)
+    }
+
+    /// 0..1 amount the falloff lowers a tile, centred on the middle of the map
+    fn falloff_shape(&self, x: i32, y: i32) -> f32 {
+        let half_w = self.width as f32 / 2.0;
+        let half_h = self.height as f32 / 2.0;
+        // Normalised offset from the centre: the map edges sit at ±1
+        let nx = (x as f32 + 0.5 - half_w) / half_w;
+        let ny = (y as f32 + 0.5 - half_h) / half_h;
+
+        match self.falloff {
+            Falloff::None => 0.0,
+            Falloff::Island => {
+                let edge = nx.abs().max(ny.abs()).min(1.0);
+                smoothstep(0.5, 1.0, edge)
+            }
+            Falloff::Lake => {
+                let dist = (nx * nx + ny * ny).sqrt();
+                1.0 - smoothstep(0.0, 0.6, dist)
+            }
+            Falloff::Coast => 1.0 - smoothstep(-1.0, -0.2, nx),
+        }
+    }
+
+    /// Generates the whole configured map, row-major
+    pub fn generate(&self, seed: u64) -> Vec<TileType> {
+        self.generate_region(seed, 0, 0, self.width, self.height)
+    }
+
+    /// Generates a `width` × `height` rectangle of tiles starting at (x0, y0), row-major.
+    /// Smoothing and beaches read a margin around the rectangle, so adjacent regions agree on their borders.
+    pub fn generate_region(&self, seed: u64, x0: i32, y0: i32, width: usize, height: usize) -> Vec<TileType> {
+        // One extra ring so beaches can see the smoothed water around them
+        let margin = self.smoothing_passes as i32 + 1;
+        let padded_w = width + 2 * margin as usize;
+        let padded_h = height + 2 * margin as usize;
+        let world_pos = |px: usize, py: usize| (x0 - margin + px as i32, y0 - margin + py as i32);
+
+        let elevation: Vec<f32> = (0..padded_h)
+            .flat_map(|py| (0..padded_w).map(move |px| (px, py)))
+            .map(|(px, py)| {
+                let (x, y) = world_pos(px, py);
+                self.elevation(seed, x, y)
+            })
+            .collect();
+        let mut water: Vec<bool> = elevation.iter().map(|&e| e < self.water_level).collect();
+
+        // Each pass only trusts tiles one step further in from the padded edge
+        for pass in 0..self.smoothing_passes as usize {
+            water = smooth(&water, padded_w, padded_h, pass);
+        }
+
+        let margin = margin as usize;
+        (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x + margin, y + margin)))
+            .map(|(px, py)| {
+                let (x, y) = world_pos(px, py);
+                let e = elevation[py * padded_w + px];
+
+                if water[py * padded_w + px] {
+                    // Tiles the smoothing flooded are above the water level, so they come out shallow
+                    return if e < self.water_level - self.deep_water_depth {
+                        TileType::DeepWater
+                    } else if e > self.water_level - self.shallows_depth {
+                        TileType::Shallows
+                    } else {
+                        TileType::Water
+                    };
+                }
+
+                let coastal = [(0, -1), (-1, 0), (1, 0), (0, 1)]
+                    .iter()
+                    .any(|&(dx, dy)| water[(py as i32 + dy) as usize * padded_w + (px as i32 + dx) as usize]);
+
+                if coastal && e < self.water_level + self.beach_height {
+                    TileType::Beach
+                } else if e > self.rock_level {
+                    TileType::Rock
+                } else if self.is_river(seed, x, y) {
+                    TileType::River
+                } else if self.moisture(seed, x, y) > self.forest_level {
+                    TileType::Forest
+                } else {
+                    TileType::Land
+                }
+            })
+            .collect()
+    }
+
+    /// Rivers are the thin band where a smooth noise crosses zero, which traces long winding lines
+    fn is_river(&self, seed: u64, x: i32, y: i32) -> bool {
+        let noise = perlin(seed ^ RIVER_SEED, x as f32 / self.river_scale, y as f32 / self.river_scale);
+        noise.abs() < self.river_width
+    }
+
+    /// 0..1 moisture that decides where forests grow
+    fn moisture(&self, seed: u64, x: i32, y: i32) -> f32 {
+        fbm(seed ^ MOISTURE_SEED, x as f32 / self.noise_scale, y as f32 / self.noise_scale, 2, 0.5, 2.0)
+    }
+}
+
+// Mixed into the seed so rivers and forests do not follow the elevation noise
+const RIVER_SEED: u64 = 0x5249_5645_5253_0001;
+const MOISTURE_SEED: u64 = 0x4D4F_4953_5455_0002;
+
+/// One cellular-automata pass: tiles surrounded by mostly water become water, and vice versa.
+/// Cells within `border` of the edge are copied unchanged because their neighbourhood is incomplete.
+fn smooth(water: &[bool], width: usize, height: usize, border: usize) -> Vec<bool> {
+    let mut next = water.to_vec();
+    for y in border + 1..height.saturating_sub(border + 1) {
+        for x in border + 1..width.saturating_sub(border + 1) {
+            let neighbours = [
+                (x - 1, y - 1), (x, y - 1), (x + 1, y - 1),
+                (x - 1, y),                 (x + 1, y),
+                (x - 1, y + 1), (x, y + 1), (x + 1, y + 1),
+            ]
+            .iter()
+            .filter(|&&(nx, ny)| water[ny * width + nx])
+            .count();
+
+            if neighbours >= 5 {
+                next[y * width + x] = true;
+            } else if neighbours <= 2 {
+                next[y * width + x] = false;
+            }
+        }
+    }
+    next
+}
+
+/// Fractal Brownian motion: `octaves` layers of Perlin noise, normalised to 0..1
+pub fn fbm(seed: u64, x: f32, y: f32, octaves: u32, persistence: f32, lacunarity: f32) -> f32 {
+    let mut total = 0.0;
+    let mut amplitude = 1.0;
+    let mut frequency = 1.0;
+    let mut max = 0.0;
+
+    for octave in 0..octaves.max(1) {
+        let octave_seed = seed.wrapping_add((octave as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+        total += perlin(octave_seed, x * frequency, y * frequency) * amplitude;
+        max += amplitude;
+        amplitude *= persistence;
+        frequency *= lacunarity;
+    }
+
+    (total / max) * 0.5 + 0.5
+}
+
+const DIAGONAL: f32 = std::f32::consts::FRAC_1_SQRT_2;
+const GRADIENTS: [(f32, f32); 8] = [
+    (1.0, 0.0), (-1.0, 0.0), (0.0, 1.0), (0.0, -1.0),
+    (DIAGONAL, DIAGONAL), (-DIAGONAL, DIAGONAL), (DIAGONAL, -DIAGONAL), (-DIAGONAL, -DIAGONAL),
+];
+
+/// 2D Perlin gradient noise in roughly -1..1
+pub fn perlin(seed: u64, x: f32, y: f32) -> f32 {
+    let x0 = x.floor();
+    let y0 = y.floor();
+    let (fx, fy) = (x - x0, y - y0);
+    let (ix, iy) = (x0 as i32, y0 as i32);
+
+    // Gradients come from a fixed table rather than trig, so maps match on every platform
+    let dot = |cx: i32, cy: i32, dx: f32, dy: f32| {
+        let (gx, gy) = GRADIENTS[(hash01(seed, cx, cy) * GRADIENTS.len() as f32) as usize % GRADIENTS.len()];
+        gx * dx + gy * dy
+    };
+
+    let n00 = dot(ix, iy, fx, fy);
+    let n10 = dot(ix + 1, iy, fx - 1.0, fy);
+    let n01 = dot(ix, iy + 1, fx, fy - 1.0);
+    let n11 = dot(ix + 1, iy + 1, fx - 1.0, fy - 1.0);
+
+    let u = fade(fx);
+    let v = fade(fy);
+    let nx0 = n00 + u * (n10 - n00);
+    let nx1 = n01 + u * (n11 - n01);
+    // Unit gradients give at most ±√0.5; rescale to about ±1
+    (nx0 + v * (nx1 - nx0)) * std::f32::consts::SQRT_2
+}
+
+/// Stateless hash of a lattice point to 0..1 (SplitMix64 finaliser)
+pub fn hash01(seed: u64, x: i32, y: i32) -> f32 {
+    let mut h = seed
+        ^ (x as u32 as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+        ^ (y as u32 as u64).wrapping_mul(0xC2B2_AE3D_27D4_EB4F);
+    h = (h ^ (h >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    h = (h ^ (h >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    h ^= h >> 31;
+    (h >> 40) as f32 / (1u64 << 24) as f32
+}
+
+/// Perlin's quintic fade curve
+fn fade(t: f32) -> f32 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32 {
+    let t = ((x - edge0) / (edge1 - edge0)).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
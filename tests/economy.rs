@@ -0,0 +1,115 @@
+use bevy::prelude::*;
+
+use rarephish_bevy::actions::{PendingActions, PlayerAction};
+use rarephish_bevy::components::{Fish, FishRarity, SelectedUncleMarker, Uncle};
+use rarephish_bevy::constants::{CASH_OUT_COOLDOWN, MULTIPLIER_INCREMENT, SIMULATION_TICK_HZ, STARTING_GOLD};
+use rarephish_bevy::events::{CashOutDenial, CashOutDenied, CashOutScope, CashedOut};
+use rarephish_bevy::replay::{headless_app, run_until_tick};
+use rarephish_bevy::resources::{DayNightCycle, GameState, SimulationTick};
+use rarephish_bevy::TickSet;
+use rarephish_bevy::uncles::{UncleRoster, UncleType};
+
+/// Every cash-out result, in the order the economy produced them
+#[derive(Resource, Default)]
+struct Results(Vec<Result<CashedOut, CashOutDenied>>);
+
+fn collect_results(
+    mut results: ResMut<Results>,
+    mut cashed_out: EventReader<CashedOut>,
+    mut denied: EventReader<CashOutDenied>,
+) {
+    results.0.extend(cashed_out.read().map(|event| Ok(*event)));
+    results.0.extend(denied.read().map(|event| Err(*event)));
+}
+
+fn economy_app() -> App {
+    let mut app = headless_app();
+    app.init_resource::<Results>()
+        .add_systems(FixedUpdate, collect_results.after(TickSet::ApplyActions).before(TickSet::Simulate));
+    app.finish();
+    app.cleanup();
+    run_until_tick(&mut app, 1);
+    app
+}
+
+/// An uncle whose basket holds `fish` fish worth 10g each
+fn spawn_stocked_uncle(app: &mut App, x: i32, fish: usize) -> Entity {
+    let somali = UncleType::new("Somali");
+    let mut uncle = Uncle::new(UncleRoster::default().get(&somali).unwrap(), x, 0);
+    for _ in 0..fish {
+        uncle.basket.add_fish(Fish::new("Test fish".to_string(), FishRarity::Common, 10, somali.clone()));
+    }
+    app.world_mut().spawn(uncle).id()
+}
+
+/// Queues `action` and runs the tick that applies it
+fn apply(app: &mut App, action: PlayerAction) {
+    app.world_mut().resource_mut::<PendingActions>().push(action);
+    let tick = app.world().resource::<SimulationTick>().0;
+    run_until_tick(app, tick + 1);
+}
+
+fn wait_out_cooldown(app: &mut App) {
+    let tick = app.world().resource::<SimulationTick>().0;
+    run_until_tick(app, tick + (CASH_OUT_COOLDOWN as f64 * SIMULATION_TICK_HZ) as u64 + 1);
+}
+
+fn results(app: &App) -> &[Result<CashedOut, CashOutDenied>] {
+    &app.world().resource::<Results>().0
+}
+
+#[test]
+fn selected_and_all_share_one_rule_set() {
+    let mut app = economy_app();
+    let selected = spawn_stocked_uncle(&mut app, 0, 3);
+    let other = spawn_stocked_uncle(&mut app, 1, 2);
+    app.world_mut().entity_mut(selected).insert(SelectedUncleMarker);
+    app.world_mut().resource_mut::<DayNightCycle>().cashouts_remaining = 3;
+
+    apply(&mut app, PlayerAction::CashOutSelected);
+    let Ok(first) = results(&app)[0] else { panic!("selected cash-out was denied") };
+    assert_eq!(first.scope, CashOutScope::Selected);
+    assert_eq!(first.fish_sold, 3);
+    assert_eq!(first.gold_earned, 30);
+    assert!(app.world().get::<Uncle>(selected).unwrap().basket.fish.is_empty());
+    assert!(!app.world().get::<Uncle>(other).unwrap().basket.fish.is_empty());
+
+    // The cooldown applies no matter which input asks
+    apply(&mut app, PlayerAction::CashOutAll);
+    let Err(denied) = results(&app)[1] else { panic!("cash-out ignored the cooldown") };
+    assert_eq!(denied.reason, CashOutDenial::Cooldown);
+
+    // Fish may have escaped while waiting; the sale is whatever is still in the baskets
+    wait_out_cooldown(&mut app);
+    let waiting = app.world().get::<Uncle>(other).unwrap().basket.fish.len();
+    apply(&mut app, PlayerAction::CashOutAll);
+    let Ok(second) = results(&app)[2] else { panic!("cash-out all was denied") };
+    assert_eq!(second.scope, CashOutScope::All);
+    assert_eq!(second.fish_sold, waiting as u32);
+    assert!((second.multiplier - (1.0 + MULTIPLIER_INCREMENT)).abs() < 1e-6);
+
+    let game_state = app.world().resource::<GameState>();
+    assert_eq!(game_state.gold, STARTING_GOLD + first.gold_earned + second.gold_earned);
+    assert_eq!(second.cashouts_remaining, 1);
+}
+
+#[test]
+fn daily_limit_blocks_every_scope() {
+    let mut app = economy_app();
+    let selected = spawn_stocked_uncle(&mut app, 0, 2);
+    spawn_stocked_uncle(&mut app, 1, 2);
+    app.world_mut().entity_mut(selected).insert(SelectedUncleMarker);
+    app.world_mut().resource_mut::<DayNightCycle>().cashouts_remaining = 1;
+
+    apply(&mut app, PlayerAction::CashOutAll);
+    assert!(results(&app)[0].is_ok());
+
+    spawn_stocked_uncle(&mut app, 2, 2);
+    for (i, action) in [PlayerAction::CashOutSelected, PlayerAction::CashOutAll].into_iter().enumerate() {
+        wait_out_cooldown(&mut app);
+        apply(&mut app, action.clone());
+        let Err(denied) = results(&app)[i + 1] else { panic!("{:?} ignored the daily limit", action) };
+        assert_eq!(denied.reason, CashOutDenial::NoCashoutsRemaining);
+    }
+    assert_eq!(app.world().resource::<GameState>().fish_count, 4);
+}
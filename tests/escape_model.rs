@@ -0,0 +1,135 @@
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use rarephish_bevy::components::{Fish, FishRarity};
+use rarephish_bevy::save::SaveFile;
+use rarephish_bevy::uncles::{UncleRoster, UncleType};
+
+const FISH_PER_SAMPLE: usize = 20_000;
+const BASKET_SECONDS: f32 = 60.0;
+
+fn fish(rarity: FishRarity, uncle_type: &str) -> Fish {
+    Fish::new("Test fish".to_string(), rarity, 10, UncleType::new(uncle_type))
+}
+
+fn retention(uncle_type: &str) -> f32 {
+    UncleRoster::default().get(&UncleType::new(uncle_type)).unwrap().retention
+}
+
+/// Fraction of fish that escape within `seconds`, stepping every fish by `dt`
+/// through one shared RNG the way `advance_escapes` does
+fn escape_rate(rarity: FishRarity, uncle_type: &str, dt: f32, seconds: f32, seed: u64) -> f32 {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let retention = retention(uncle_type);
+    let mut basket: Vec<Fish> = (0..FISH_PER_SAMPLE).map(|_| fish(rarity, uncle_type)).collect();
+    let mut escaped = 0;
+
+    let steps = (seconds / dt).round() as usize;
+    for _ in 0..steps {
+        basket.retain_mut(|fish| {
+            let got_away = fish.advance(dt, retention, &mut rng);
+            escaped += got_away as usize;
+            !got_away
+        });
+    }
+    escaped as f32 / FISH_PER_SAMPLE as f32
+}
+
+#[test]
+fn single_fish_flops_identically_at_any_step_size() {
+    let run = |dt: f32| {
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        let mut fish = fish(FishRarity::Rare, "Somali");
+        let steps = (BASKET_SECONDS / dt).round() as usize;
+        for step in 0..steps {
+            if fish.advance(dt, retention("Somali"), &mut rng) {
+                return (Some(step as f32 * dt), fish.failed_escape_attempts);
+            }
+        }
+        (None, fish.failed_escape_attempts)
+    };
+
+    let (escape_30, flops_30) = run(1.0 / 30.0);
+    for dt in [1.0 / 20.0, 1.0 / 60.0, 1.0 / 144.0] {
+        let (escape, flops) = run(dt);
+        assert_eq!(flops, flops_30, "failed flops differ at dt {}", dt);
+        assert_eq!(escape.is_some(), escape_30.is_some(), "outcome differs at dt {}", dt);
+        if let (Some(a), Some(b)) = (escape, escape_30) {
+            // Escape lands on the same flop, so only the step it falls in can differ
+            assert!((a - b).abs() <= 1.0 / 30.0, "escape time {} vs {}", a, b);
+        }
+    }
+}
+
+#[test]
+fn escape_rate_does_not_depend_on_frame_rate() {
+    for rarity in FishRarity::ALL {
+        let at_30 = escape_rate(rarity, "Mongolian", 1.0 / 30.0, BASKET_SECONDS, 1);
+        let at_144 = escape_rate(rarity, "Mongolian", 1.0 / 144.0, BASKET_SECONDS, 2);
+        assert!(
+            (at_30 - at_144).abs() < 0.02,
+            "{:?}: {:.3} at 30 Hz vs {:.3} at 144 Hz",
+            rarity,
+            at_30,
+            at_144
+        );
+    }
+}
+
+#[test]
+fn failed_flops_are_counted_per_flop() {
+    let mut rng = ChaCha8Rng::seed_from_u64(3);
+    let mut fish = fish(FishRarity::Common, "Japanese");
+
+    // One second of burst phase is four flops, however it is sliced
+    let mut elapsed = 0.0;
+    while elapsed < 1.0 && !fish.advance(1.0 / 144.0, retention("Japanese"), &mut rng) {
+        elapsed += 1.0 / 144.0;
+    }
+    assert!(fish.failed_escape_attempts <= 4, "{} failed flops in one second", fish.failed_escape_attempts);
+}
+
+#[test]
+fn escape_rates_follow_rarity_and_uncle_retention() {
+    let common = escape_rate(FishRarity::Common, "Mongolian", 0.05, BASKET_SECONDS, 4);
+    let uncommon = escape_rate(FishRarity::Uncommon, "Mongolian", 0.05, BASKET_SECONDS, 5);
+    let rare = escape_rate(FishRarity::Rare, "Mongolian", 0.05, BASKET_SECONDS, 6);
+    assert!(common < uncommon && uncommon < rare, "{} / {} / {}", common, uncommon, rare);
+
+    // Somali uncles hold on better than Japanese ones
+    let somali = escape_rate(FishRarity::Rare, "Somali", 0.05, BASKET_SECONDS, 8);
+    let japanese = escape_rate(FishRarity::Rare, "Japanese", 0.05, BASKET_SECONDS, 9);
+    assert!(somali < japanese, "{} vs {}", somali, japanese);
+
+    // Most of the danger is in the burst phase
+    let burst_only = escape_rate(FishRarity::Rare, "Mongolian", 0.05, 10.0, 10);
+    assert!(burst_only > rare * 0.6, "burst {} of total {}", burst_only, rare);
+}
+
+#[test]
+fn v1_saves_get_flop_counts_for_their_age() {
+    let v1 = r#"{
+        "version": 1,
+        "saved_at": 0,
+        "game_state": { "fish_count": 0, "gold": 100, "multiplier": 1.0, "cash_out_cooldown": 0.0 },
+        "day_night": {
+            "time_elapsed": 0.0, "day_progress": 0.0, "day_number": 1, "is_day": true,
+            "cashouts_remaining": 3, "max_cashouts_per_day": 3
+        },
+        "world_seed": { "seed": 1, "word_pos": 0 },
+        "tiles": { "width": 0, "height": 0, "tiles": [] },
+        "uncles": [{
+            "uncle_type": "Mongolian", "x": 0, "y": 0, "timer_elapsed": 0.0,
+            "basket": { "fish": [{
+                "name": "Old fish", "rarity": "Common", "value": 5, "time_alive": 40.0,
+                "failed_escape_attempts": 2400, "caught_by_uncle": "Mongolian"
+            }], "capacity": 5 }
+        }]
+    }"#;
+
+    let save = SaveFile::from_json(v1).expect("v1 save migrates");
+    let fish = &save.uncles[0].basket.fish[0];
+    // 10 s at 4 Hz + 20 s at 1.5 Hz + 10 s at 0.4 Hz
+    assert_eq!(fish.failed_escape_attempts, 74);
+    assert_eq!(fish.next_flop_at, 40.0);
+}
@@ -0,0 +1,99 @@
+use bevy::prelude::*;
+
+use rarephish_bevy::actions::{PendingActions, PlayerAction};
+use rarephish_bevy::components::{Tile, TileType};
+use rarephish_bevy::constants::DAY_LENGTH_SECONDS;
+use rarephish_bevy::events::*;
+use rarephish_bevy::replay::{headless_app, run_until_tick};
+use rarephish_bevy::resources::{FishingStats, WorldSeed};
+use rarephish_bevy::uncles::UncleType;
+
+/// How many of each gameplay event a run produced
+#[derive(Resource, Default)]
+struct EventCounts {
+    caught: u32,
+    escaped: u32,
+    discarded: u32,
+    placed: Vec<UnclePlaced>,
+    days_started: Vec<u32>,
+    daylight_changes: u32,
+}
+
+fn count_events(
+    mut counts: ResMut<EventCounts>,
+    mut caught: EventReader<FishCaught>,
+    mut escaped: EventReader<FishEscaped>,
+    mut discarded: EventReader<FishDiscarded>,
+    mut placed: EventReader<UnclePlaced>,
+    mut day_started: EventReader<DayStarted>,
+    mut daylight_changed: EventReader<DaylightChanged>,
+) {
+    counts.caught += caught.read().count() as u32;
+    counts.escaped += escaped.read().count() as u32;
+    counts.discarded += discarded.read().count() as u32;
+    counts.placed.extend(placed.read().cloned());
+    counts.days_started.extend(day_started.read().map(|event| event.day_number));
+    counts.daylight_changes += daylight_changed.read().count() as u32;
+}
+
+/// First land tile touching water, scanning row by row
+fn coastal_tile(app: &mut App) -> (i32, i32) {
+    let world = app.world_mut();
+    let tiles: Vec<(i32, i32, TileType)> = world
+        .query::<&Tile>()
+        .iter(world)
+        .map(|tile| (tile.x, tile.y, tile.tile_type))
+        .collect();
+    let is_water = |x: i32, y: i32| tiles.iter().any(|&(tx, ty, t)| (tx, ty) == (x, y) && t.is_water());
+
+    let mut coast: Vec<(i32, i32)> = tiles
+        .iter()
+        .filter(|&&(x, y, t)| {
+            t.can_place_uncle()
+                && (x - 1..=x + 1)
+                    .any(|nx| (y - 1..=y + 1).any(|ny| is_water(nx, ny)))
+        })
+        .map(|&(x, y, _)| (y, x))
+        .collect();
+    coast.sort();
+    let (y, x) = coast[0];
+    (x, y)
+}
+
+#[test]
+fn simulation_reports_through_events() {
+    let mut app = headless_app();
+    app.insert_resource(WorldSeed::from_seed(11))
+        .init_resource::<EventCounts>()
+        .add_systems(Update, count_events);
+    app.finish();
+    app.cleanup();
+    run_until_tick(&mut app, 1);
+
+    let (x, y) = coastal_tile(&mut app);
+    let mut actions = app.world_mut().resource_mut::<PendingActions>();
+    actions.push(PlayerAction::SelectUncleType(UncleType::new("Mongolian")));
+    actions.push(PlayerAction::PlaceUncle { x, y });
+
+    // A little over one full day
+    let ticks = ((DAY_LENGTH_SECONDS + 10.0) * 20.0) as u64;
+    run_until_tick(&mut app, ticks / 2);
+    let mut actions = app.world_mut().resource_mut::<PendingActions>();
+    actions.push(PlayerAction::SelectPlacedUncle { x, y });
+    actions.push(PlayerAction::RemoveLowestFish);
+    run_until_tick(&mut app, ticks);
+
+    let counts = app.world().resource::<EventCounts>();
+    assert_eq!(counts.placed.len(), 1);
+    assert_eq!((counts.placed[0].x, counts.placed[0].y), (x, y));
+    assert_eq!(counts.placed[0].uncle_type, UncleType::new("Mongolian"));
+    assert_eq!(counts.days_started, vec![2]);
+    assert!(counts.daylight_changes >= 2, "no dawn and dusk in a whole day");
+    assert!(counts.caught > 0);
+
+    // Statistics are built from the same events
+    let stats = app.world().resource::<FishingStats>();
+    assert_eq!(stats.caught_by_rarity.values().sum::<u32>(), counts.caught);
+    assert_eq!(stats.escaped_by_rarity.values().sum::<u32>(), counts.escaped);
+    assert_eq!(counts.discarded, 1);
+}
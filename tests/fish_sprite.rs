@@ -0,0 +1,111 @@
+use bevy::prelude::*;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use rarephish_bevy::components::Fish;
+use rarephish_bevy::constants::{FISH_SPRITE_HEIGHT, FISH_SPRITE_VARIANTS, FISH_SPRITE_WIDTH};
+use rarephish_bevy::fish_sprite::{render_fish, FishLook, FishSprites};
+use rarephish_bevy::species::{FishColor, FishPattern, FishShape, SpeciesCatalogue, SpeciesId};
+use rarephish_bevy::uncles::UncleType;
+
+const WIDTH: usize = FISH_SPRITE_WIDTH as usize;
+const HEIGHT: usize = FISH_SPRITE_HEIGHT as usize;
+
+fn look(shape: FishShape, color: FishColor, pattern: FishPattern, variant: u32) -> FishLook {
+    FishLook { shape, color, pattern, variant }
+}
+
+fn pixels(look: FishLook) -> Vec<[u8; 4]> {
+    let image = render_fish(look);
+    assert_eq!(image.data.len(), WIDTH * HEIGHT * 4);
+    image.data.chunks(4).map(|p| [p[0], p[1], p[2], p[3]]).collect()
+}
+
+/// Width and height of the opaque part of a sprite
+fn extent(pixels: &[[u8; 4]]) -> (usize, usize) {
+    let opaque: Vec<(usize, usize)> = (0..pixels.len()).filter(|&i| pixels[i][3] > 0).map(|i| (i % WIDTH, i / WIDTH)).collect();
+    let span = |coords: Vec<usize>| coords.iter().max().unwrap() - coords.iter().min().unwrap() + 1;
+    (span(opaque.iter().map(|p| p.0).collect()), span(opaque.iter().map(|p| p.1).collect()))
+}
+
+#[test]
+fn every_look_is_a_fish_on_a_transparent_background() {
+    for shape in FishShape::ALL {
+        for pattern in FishPattern::ALL {
+            let pixels = pixels(look(shape, FishColor::Blue, pattern, 1));
+            for corner in [0, WIDTH - 1, WIDTH * (HEIGHT - 1), WIDTH * HEIGHT - 1] {
+                assert_eq!(pixels[corner][3], 0, "{:?} {:?}", shape, pattern);
+            }
+            let opaque: Vec<&[u8; 4]> = pixels.iter().filter(|p| p[3] > 0).collect();
+            let coverage = opaque.len() as f32 / pixels.len() as f32;
+            assert!((0.1..0.7).contains(&coverage), "{:?} {:?} covers {}", shape, pattern, coverage);
+            assert!(opaque.iter().all(|p| p[3] == 255));
+
+            // Painted in its color
+            let total = |channel: usize| opaque.iter().map(|p| p[channel] as u32).sum::<u32>();
+            assert!(total(2) > total(0) * 2, "{:?} {:?} is not blue", shape, pattern);
+        }
+    }
+}
+
+#[test]
+fn shapes_have_their_own_outlines() {
+    let extent_of = |shape: FishShape| extent(&pixels(look(shape, FishColor::Red, FishPattern::Solid, 0)));
+    let (long, slim, round) = (extent_of(FishShape::Long), extent_of(FishShape::Slim), extent_of(FishShape::Round));
+    assert!(long.1 < slim.1 && slim.1 < round.1, "heights {:?} {:?} {:?}", long, slim, round);
+    assert!(long.0 >= slim.0 && slim.0 > round.0, "lengths {:?} {:?} {:?}", long, slim, round);
+
+    let masks: Vec<Vec<bool>> = FishShape::ALL
+        .into_iter()
+        .map(|shape| pixels(look(shape, FishColor::Red, FishPattern::Solid, 0)).iter().map(|p| p[3] > 0).collect())
+        .collect();
+    for (i, a) in masks.iter().enumerate() {
+        assert!(masks[i + 1..].iter().all(|b| a != b), "{:?} shares its outline", FishShape::ALL[i]);
+    }
+}
+
+#[test]
+fn patterns_and_variants_change_the_paint() {
+    let paint = |pattern: FishPattern, variant: u32| pixels(look(FishShape::Bulky, FishColor::Green, pattern, variant));
+    let solid = paint(FishPattern::Solid, 0);
+    for pattern in [FishPattern::Striped, FishPattern::Spotted, FishPattern::Marbled, FishPattern::Gradient] {
+        assert_ne!(paint(pattern, 0), solid, "{:?} looks solid", pattern);
+    }
+    assert_ne!(paint(FishPattern::Spotted, 0), paint(FishPattern::Spotted, 1));
+    // Nothing to lay out on a plain fish
+    assert_eq!(paint(FishPattern::Solid, 3), solid);
+}
+
+#[test]
+fn a_fish_always_gets_the_same_sprite() {
+    let catalogue = SpeciesCatalogue::default();
+    let koi = catalogue.get(&SpeciesId::new("koi")).unwrap();
+    let fish_of = |length_cm: f32, value: u32| {
+        let traits = koi.roll_traits(length_cm, &mut ChaCha8Rng::seed_from_u64(0));
+        Fish::of_species(koi, traits, length_cm, value, UncleType::new("Mongolian"))
+    };
+
+    let fish = fish_of(61.5, 52);
+    let look = FishLook::of(&fish);
+    assert_eq!(look, FishLook::of(&fish.clone()));
+    assert_eq!((look.shape, look.pattern, look.color), (koi.shape, koi.pattern, fish.traits.color));
+    assert_eq!(pixels(look), pixels(FishLook::of(&fish)));
+
+    // Different fish of a kind spread over the variants
+    let variants: std::collections::HashSet<u32> = (0..40).map(|i| FishLook::of(&fish_of(50.0 + i as f32 * 0.7, 40 + i)).variant).collect();
+    assert_eq!(variants.len(), FISH_SPRITE_VARIANTS as usize);
+    assert_eq!(FishLook::of_species(koi, FishColor::Red).variant, 0);
+}
+
+#[test]
+fn sprites_are_drawn_once_per_look() {
+    let mut images = Assets::<Image>::default();
+    let mut sprites = FishSprites::default();
+    let koi = look(FishShape::Round, FishColor::Orange, FishPattern::Marbled, 2);
+
+    let first = sprites.get(koi, &mut images);
+    assert_eq!(sprites.get(koi, &mut images), first);
+    assert_ne!(sprites.get(FishLook { variant: 3, ..koi }, &mut images), first);
+    assert_eq!((sprites.len(), images.len()), (2, 2));
+    assert_eq!(images.get(&first).unwrap().data, render_fish(koi).data);
+}
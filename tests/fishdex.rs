@@ -0,0 +1,166 @@
+use bevy::ecs::system::RunSystemOnce;
+use bevy::prelude::*;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use rarephish_bevy::actions::{PendingActions, PlayerAction};
+use rarephish_bevy::components::{Fish, FishRarity, Tile, TileType};
+use rarephish_bevy::events::{FishCaught, FishDiscovered};
+use rarephish_bevy::fishdex::Fishdex;
+use rarephish_bevy::replay::{headless_app, run_until_tick};
+use rarephish_bevy::resources::{FishingStats, OfflineReport, SpeciesRecords, WorldSeed};
+use rarephish_bevy::save::SaveError;
+use rarephish_bevy::species::{FishColor, SpeciesCatalogue, SpeciesDef, SpeciesId};
+use rarephish_bevy::systems::persistence::{RunRestorer, RunSnapshot};
+use rarephish_bevy::uncles::UncleType;
+use rarephish_bevy::RestoreWorldSet;
+
+fn species(id: &str) -> SpeciesDef {
+    SpeciesCatalogue::default().get(&SpeciesId::new(id)).unwrap().clone()
+}
+
+fn fish_of(def: &SpeciesDef, color: FishColor, length_cm: f32, value: u32, uncle: &str) -> Fish {
+    let mut traits = def.roll_traits(length_cm, &mut ChaCha8Rng::seed_from_u64(0));
+    traits.color = color;
+    Fish::of_species(def, traits, length_cm, value, UncleType::new(uncle))
+}
+
+#[test]
+fn entries_keep_counts_and_best_specimens() {
+    let koi = species("koi");
+    let (orange, other) = (koi.colors[0], koi.colors[1]);
+    let mut fishdex = Fishdex::default();
+
+    assert!(fishdex.record_catch(&fish_of(&koi, orange, 60.0, 30, "Mongolian"), 2));
+    assert!(!fishdex.record_catch(&fish_of(&koi, orange, 80.0, 20, "Somali"), 3));
+    assert!(!fishdex.record_catch(&fish_of(&koi, orange, 50.0, 45, "Somali"), 4));
+    fishdex.record_escape(&fish_of(&koi, orange, 55.0, 10, "Mongolian"));
+    // Another color is its own entry
+    assert!(fishdex.record_catch(&fish_of(&koi, other, 60.0, 30, "Somali"), 4));
+
+    let entry = fishdex.get(&koi.id, orange).unwrap();
+    assert_eq!((entry.caught, entry.escaped), (3, 1));
+    assert_eq!((entry.first_caught_day, &entry.first_caught_by), (2, &UncleType::new("Mongolian")));
+    assert_eq!((entry.largest.length_cm, entry.largest.day), (80.0, 3));
+    assert_eq!((entry.most_valuable.value, entry.most_valuable.day), (45, 4));
+    assert_eq!(fishdex.len(), 2);
+
+    // Escapes of never-caught entries and fish from before the catalogue are not collected
+    fishdex.record_escape(&fish_of(&species("pike"), species("pike").colors[0], 40.0, 5, "Somali"));
+    assert!(!fishdex.record_catch(&Fish::new("Old fish".to_string(), FishRarity::Rare, 50, UncleType::new("Somali")), 5));
+    assert_eq!(fishdex.len(), 2);
+}
+
+#[test]
+fn completion_counts_species_colors_by_rarity() {
+    let catalogue = SpeciesCatalogue::default();
+    let total = |rarity: FishRarity| {
+        catalogue.iter().filter(|def| def.rarity == rarity).map(|def| def.colors.len()).sum::<usize>()
+    };
+    let mut fishdex = Fishdex::default();
+    for rarity in FishRarity::ALL {
+        assert_eq!(fishdex.completion(&catalogue, rarity), (0, total(rarity)));
+    }
+
+    let koi = species("koi");
+    for &color in &koi.colors {
+        fishdex.record_catch(&fish_of(&koi, color, 60.0, 30, "Mongolian"), 1);
+    }
+    assert_eq!(fishdex.completion(&catalogue, koi.rarity), (koi.colors.len(), total(koi.rarity)));
+}
+
+#[test]
+fn fishdex_survives_a_json_roundtrip() {
+    let koi = species("koi");
+    let mut fishdex = Fishdex::default();
+    fishdex.record_catch(&fish_of(&koi, koi.colors[0], 70.0, 40, "Somali"), 6);
+
+    let loaded = Fishdex::from_json(&fishdex.to_json().unwrap()).unwrap();
+    let entry = loaded.get(&koi.id, koi.colors[0]).unwrap();
+    assert_eq!((entry.caught, entry.first_caught_day, entry.largest.length_cm), (1, 6, 70.0));
+
+    assert!(matches!(Fishdex::from_json(r#"{ "version": 99, "entries": [] }"#), Err(SaveError::UnsupportedVersion(99))));
+}
+
+/// How many entries the run announced and how many catches it reported
+#[derive(Resource, Default)]
+struct Announced {
+    discoveries: usize,
+    catches: usize,
+}
+
+fn count_announcements(mut announced: ResMut<Announced>, mut discovered: EventReader<FishDiscovered>, mut caught: EventReader<FishCaught>) {
+    announced.discoveries += discovered.read().count();
+    announced.catches += caught.read().count();
+}
+
+/// Hires Mongolian uncles on the first few tiles with water to the east
+fn hire_uncles_on_the_coast(app: &mut App) {
+    let world = app.world_mut();
+    let tiles: Vec<(i32, i32, TileType)> = world.query::<&Tile>().iter(world).map(|tile| (tile.x, tile.y, tile.tile_type)).collect();
+    let is_water = |x: i32, y: i32| tiles.iter().any(|&(tx, ty, t)| (tx, ty) == (x, y) && t.is_water());
+    let coast: Vec<(i32, i32)> = tiles
+        .iter()
+        .filter(|&&(x, y, t)| t.can_place_uncle() && is_water(x + 1, y))
+        .map(|&(x, y, _)| (x, y))
+        .take(5)
+        .collect();
+    let mut actions = app.world_mut().resource_mut::<PendingActions>();
+    actions.push(PlayerAction::SelectUncleType(UncleType::new("Mongolian")));
+    for &(x, y) in &coast {
+        actions.push(PlayerAction::PlaceUncle { x, y });
+    }
+}
+
+#[test]
+fn catches_fill_the_fishdex() {
+    let mut app = headless_app();
+    app.insert_resource(WorldSeed::from_seed(11))
+        .init_resource::<Announced>()
+        .add_systems(Update, count_announcements);
+    app.finish();
+    app.cleanup();
+    run_until_tick(&mut app, 1);
+    hire_uncles_on_the_coast(&mut app);
+    run_until_tick(&mut app, 6000);
+
+    let fishdex = app.world().resource::<Fishdex>();
+    let caught: u32 = fishdex.iter().map(|entry| entry.caught).sum();
+    assert!(caught > 0, "nothing caught");
+    assert_eq!(caught, app.world().resource::<FishingStats>().caught_by_rarity.values().sum::<u32>());
+    assert_eq!(app.world().resource::<Announced>().discoveries, fishdex.len());
+}
+
+#[test]
+fn offline_catches_fill_the_fishdex() {
+    let mut app = headless_app();
+    app.insert_resource(WorldSeed::from_seed(12));
+    app.finish();
+    app.cleanup();
+    run_until_tick(&mut app, 1);
+    hire_uncles_on_the_coast(&mut app);
+    run_until_tick(&mut app, 20);
+    let mut save = app.world_mut().run_system_once(|snapshot: RunSnapshot| snapshot.capture());
+    save.saved_at -= 2 * 3600;
+    let saved_caught: u32 = save.stats.caught_by_rarity.values().sum();
+
+    // Resume two hours later in a fresh app
+    let mut app = headless_app();
+    app.init_resource::<Announced>()
+        .add_systems(Update, count_announcements)
+        .add_systems(Startup, (move |mut restorer: RunRestorer| assert!(restorer.restore(save.clone(), true))).in_set(RestoreWorldSet));
+    app.finish();
+    app.cleanup();
+    app.update();
+    let offline = app.world().resource::<OfflineReport>().caught.len();
+    assert!(offline > 0, "nothing caught offline");
+
+    // Offline catches are announced on the first tick, like the ones made during it
+    run_until_tick(&mut app, 1);
+    let announced = app.world().resource::<Announced>().catches as u32;
+    assert!(announced >= offline as u32);
+    let fishdex = app.world().resource::<Fishdex>();
+    assert_eq!(fishdex.iter().map(|entry| entry.caught).sum::<u32>(), announced);
+    assert_eq!(app.world().resource::<FishingStats>().caught_by_rarity.values().sum::<u32>(), saved_caught + announced);
+    assert!(!app.world().resource::<SpeciesRecords>().records.is_empty());
+}
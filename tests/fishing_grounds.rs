@@ -0,0 +1,261 @@
+use std::time::Duration;
+
+use rarephish_bevy::components::{FishRarity, TileType, Uncle};
+use rarephish_bevy::fishing::{FishingGrounds, FishingSpot, WaterBody};
+use rarephish_bevy::grid::{chunks_between, TileGrid};
+use rarephish_bevy::resources::{RngStream, WorldSeed};
+use rarephish_bevy::species::SpeciesCatalogue;
+use rarephish_bevy::systems::gameplay::advance_fishing;
+use rarephish_bevy::uncles::{UncleDef, UncleRoster, UncleType};
+use rarephish_bevy::worldgen::WorldGenConfig;
+
+/// One chunk of land with `tiles` set
+fn grid_with(tiles: &[(i32, i32, TileType)]) -> TileGrid {
+    let mut grid = TileGrid::filled(8, 4, TileType::Land);
+    for &(x, y, tile_type) in tiles {
+        grid.set(x, y, tile_type);
+    }
+    grid
+}
+
+fn uncle_def(id: &str) -> UncleDef {
+    UncleRoster::default().get(&UncleType::new(id)).unwrap().clone()
+}
+
+/// Fish each uncle catches over `seconds`, emptying baskets every second
+fn fish_for(seconds: u32, uncles: &mut [Uncle], grounds: &mut FishingGrounds, world_seed: &mut WorldSeed) -> u32 {
+    let defs: Vec<UncleDef> = uncles.iter().map(|u| uncle_def(u.uncle_type.as_str())).collect();
+    let spots: Vec<FishingSpot> = uncles
+        .iter()
+        .zip(&defs)
+        .map(|(u, def)| FishingSpot::at(u.x, u.y, def.casting_radius, Some(TileType::Land), grounds).unwrap())
+        .collect();
+    let catalogue = SpeciesCatalogue::default();
+    let mut caught = 0;
+    for _ in 0..seconds {
+        for ((uncle, def), spot) in uncles.iter_mut().zip(&defs).zip(&spots) {
+            caught += advance_fishing(uncle, def, Duration::from_secs(1), true, world_seed, grounds, &catalogue, spot).len() as u32;
+            uncle.basket.fish.clear();
+        }
+    }
+    caught
+}
+
+#[test]
+fn uncles_sharing_water_deplete_the_same_populations() {
+    // One water tile at (1, 0), reached by uncles on either side of it
+    let mut grounds = FishingGrounds::new(3, &WorldGenConfig::default(), &SpeciesCatalogue::default(), &grid_with(&[(1, 0, TileType::Water)]));
+    let capacity = grounds.get(1, 0).unwrap().capacity();
+    let mut uncles = [Uncle::new(&uncle_def("Somali"), 0, 1), Uncle::new(&uncle_def("Somali"), 2, 1)];
+    let mut world_seed = WorldSeed::from_seed(3);
+
+    let caught = fish_for(1200, &mut uncles, &mut grounds, &mut world_seed);
+    assert!(caught as f32 <= capacity.ceil(), "{} fish caught from a tile holding {}", caught, capacity);
+    assert!(caught as f32 >= capacity * 0.8, "only {} of {} fish caught", caught, capacity);
+    assert!(grounds.body_at(1, 0).unwrap().populations.iter().all(|p| p.count < 1.0));
+
+    // Nothing left to bite until the populations regrow
+    assert_eq!(fish_for(60, &mut uncles, &mut grounds, &mut world_seed), 0);
+    grounds.regrow(10.0);
+    assert!(fish_for(60, &mut uncles, &mut grounds, &mut world_seed) > 0);
+}
+
+#[test]
+fn every_water_tile_has_a_species_pool() {
+    let config = WorldGenConfig::default();
+    for seed in [1, 2, 3] {
+        let mut grid = TileGrid::default();
+        for chunk in chunks_between((-20, -20), (20, 20)) {
+            grid.generate_chunk(seed, &config, chunk);
+        }
+        let grounds = FishingGrounds::new(seed, &config, &SpeciesCatalogue::default(), &grid);
+
+        assert_eq!(grounds.tiles.len(), grid.iter().filter(|(_, _, t)| t.is_water()).count());
+        for tile in grounds.tiles.values() {
+            for rarity in FishRarity::ALL {
+                assert!(tile.pool.iter().any(|species| species.rarity() == rarity), "no {:?} species", rarity);
+            }
+            assert!((0.0..=1.0).contains(&tile.depth));
+        }
+        for body in grounds.bodies.values() {
+            assert!(body.populations.iter().all(|p| p.count == p.capacity));
+        }
+    }
+}
+
+#[test]
+fn connected_water_forms_one_body() {
+    // Two lakes along the bottom edge, split by land at x = 2 and a dock at x = 5
+    let row = [
+        TileType::Water, TileType::Shallows, TileType::Land, TileType::DeepWater,
+        TileType::Water, TileType::Dock, TileType::River,
+    ];
+    let row: Vec<_> = row.iter().enumerate().map(|(x, &t)| (x as i32, 0, t)).collect();
+    let grounds = FishingGrounds::new(4, &WorldGenConfig::default(), &SpeciesCatalogue::default(), &grid_with(&row));
+
+    // Docks join the water on either side; land splits it
+    let bodies: Vec<_> = grounds.bodies.values().collect();
+    assert_eq!(bodies.len(), 2);
+    assert_eq!(bodies[0].tiles, vec![(0, 0), (1, 0)]);
+    assert_eq!(bodies[1].tiles, vec![(3, 0), (4, 0), (5, 0), (6, 0)]);
+    assert!(grounds.get(5, 0).is_none(), "docks are not fished");
+    assert_eq!(grounds.body_at(6, 0), grounds.body_at(3, 0));
+
+    // Every body holds the species of all of its tiles' pools, as much fish as its tiles together
+    for body in grounds.bodies.values() {
+        let tiles: Vec<_> = body.tiles.iter().filter_map(|&(x, y)| grounds.get(x, y)).collect();
+        let capacity: f32 = tiles.iter().map(|t| t.capacity()).sum();
+        let held: f32 = body.populations.iter().map(|p| p.capacity).sum();
+        assert!((capacity - held).abs() < 0.01, "{} vs {}", capacity, held);
+        for species in tiles.iter().flat_map(|t| &t.pool) {
+            assert!(body.population(*species).is_some());
+        }
+    }
+}
+
+#[test]
+fn chunks_stock_their_water_without_their_neighbours() {
+    let config = WorldGenConfig::default();
+    let mut alone = TileGrid::default();
+    alone.generate_chunk(8, &config, (1, 0));
+    let mut surrounded = alone.clone();
+    for chunk in chunks_between((-16, -16), (47, 31)) {
+        surrounded.generate_chunk(8, &config, chunk);
+    }
+
+    let catalogue = SpeciesCatalogue::default();
+    let (alone, surrounded) = (FishingGrounds::new(8, &config, &catalogue, &alone), FishingGrounds::new(8, &config, &catalogue, &surrounded));
+    let in_chunk = |grounds: &FishingGrounds| -> Vec<_> {
+        grounds.bodies.iter().filter(|(id, _)| id.chunk == (1, 0)).map(|(&id, body)| (id, body.clone())).collect()
+    };
+    assert!(!in_chunk(&alone).is_empty());
+    // Only the bodies may differ, where the chunk's water runs on into its neighbours
+    for (&(x, y), tile) in &alone.tiles {
+        let neighbour = surrounded.get(x, y).unwrap();
+        assert_eq!((neighbour.depth, neighbour.density, &neighbour.pool), (tile.depth, tile.density, &tile.pool));
+    }
+    for (id, body) in in_chunk(&alone) {
+        if body.chunks() == [(1, 0)] && body.tiles.iter().all(|&(x, y)| (17..31).contains(&x) && (1..15).contains(&y)) {
+            assert_eq!(surrounded.bodies.get(&id), Some(&body));
+        }
+    }
+}
+
+#[test]
+fn a_lake_across_chunks_is_one_body() {
+    // Water from x = 10 to 21 on both sides of the border at x = 16
+    let config = WorldGenConfig::default();
+    let catalogue = SpeciesCatalogue::default();
+    let mut grid = TileGrid::filled(32, 4, TileType::Land);
+    for x in 10..22 {
+        grid.set(x, 0, TileType::Water);
+    }
+
+    // Streamed in either order, the halves join under the lower id with both halves' fish
+    let mut grounds = FishingGrounds::default();
+    grounds.add_chunk(8, &config, &catalogue, &grid, (1, 0));
+    assert_eq!(grounds.bodies.len(), 1);
+    let right_half = grounds.bodies.values().next().unwrap().clone();
+    grounds.add_chunk(8, &config, &catalogue, &grid, (0, 0));
+    let mut reversed = FishingGrounds::default();
+    reversed.add_chunk(8, &config, &catalogue, &grid, (0, 0));
+    reversed.add_chunk(8, &config, &catalogue, &grid, (1, 0));
+    assert_eq!(grounds.bodies, reversed.bodies);
+    assert_eq!(grounds.bodies.len(), 1);
+
+    let (&id, lake) = grounds.bodies.iter().next().unwrap();
+    assert_eq!((id.chunk, id.index), ((0, 0), 0));
+    assert_eq!(lake.tiles.len(), 12);
+    assert_eq!(lake.chunks(), vec![(0, 0), (1, 0)]);
+    assert!((10..22).all(|x| grounds.get(x, 0).unwrap().body == id));
+    let capacity = |body: &WaterBody| body.populations.iter().map(|p| p.capacity).sum::<f32>();
+    assert!(capacity(lake) > capacity(&right_half));
+
+    // Fishing the west end empties the east end too
+    let full = grounds.clone();
+    let mut uncles = [Uncle::new(&uncle_def("Somali"), 10, 1), Uncle::new(&uncle_def("Somali"), 11, 1)];
+    fish_for(1200, &mut uncles, &mut grounds, &mut WorldSeed::from_seed(8));
+    assert!(grounds.body_at(21, 0).unwrap().fullness() < 0.9, "{}", grounds.body_at(21, 0).unwrap().fullness());
+    let somali = uncle_def("Somali");
+    let east = FishingSpot::at(21, 1, somali.casting_radius, Some(TileType::Land), &grounds).unwrap();
+    assert!(east.catch_speed(&grounds, somali.rare_bonus, true) < east.catch_speed(&full, somali.rare_bonus, true));
+}
+
+#[test]
+fn populations_regrow_logistically() {
+    let water: Vec<_> = (0..4).map(|x| (x, 0, TileType::Water)).collect();
+    let mut grounds = FishingGrounds::new(5, &WorldGenConfig::default(), &SpeciesCatalogue::default(), &grid_with(&water));
+    let lake = *grounds.bodies.keys().next().unwrap();
+    let fill = |body: &mut WaterBody, share: f32| {
+        for population in &mut body.populations {
+            population.count = population.capacity * share;
+        }
+    };
+
+    // Half-full water grows fastest, and step size does not change the result
+    fill(grounds.bodies.get_mut(&lake).unwrap(), 0.5);
+    let mut coarse = grounds.clone();
+    coarse.regrow(1.0);
+    for _ in 0..100 {
+        grounds.regrow(0.01);
+    }
+    let (fine, coarse) = (grounds.bodies[&lake].fullness(), coarse.bodies[&lake].fullness());
+    assert!((fine - coarse).abs() < 1e-3, "{} vs {}", fine, coarse);
+    assert!(fine > 0.7 && fine < 1.0);
+
+    // Nearly empty water recovers slowly at first, then approaches capacity without passing it
+    fill(grounds.bodies.get_mut(&lake).unwrap(), 0.0);
+    grounds.regrow(1.0);
+    assert!(grounds.bodies[&lake].fullness() < 0.2);
+    grounds.regrow(30.0);
+    assert!(grounds.bodies[&lake].fullness() > 0.99);
+    assert!(grounds.bodies[&lake].populations.iter().all(|p| p.count <= p.capacity));
+}
+
+#[test]
+fn overfished_water_bites_slower_and_gives_fewer_rares() {
+    let full = FishingGrounds::new(6, &WorldGenConfig::default(), &SpeciesCatalogue::default(), &grid_with(&[(1, 0, TileType::DeepWater)]));
+    let spot = FishingSpot::at(1, 1, 1, Some(TileType::Land), &full).unwrap();
+    let mut overfished = full.clone();
+    let japanese = uncle_def("Japanese");
+    let mut uncles = [Uncle::new(&japanese, 1, 1)];
+    let mut world_seed = WorldSeed::from_seed(6);
+    fish_for(240, &mut uncles, &mut overfished, &mut world_seed);
+
+    let body = overfished.body_at(1, 0).unwrap();
+    let fullness = |rarity: FishRarity| {
+        let populations = body.populations.iter().filter(|p| p.species.rarity() == rarity);
+        let (count, capacity) = populations.fold((0.0, 0.0), |(n, k), p| (n + p.count, k + p.capacity));
+        count / capacity
+    };
+    assert!(fullness(FishRarity::Rare) < fullness(FishRarity::Common));
+    assert!(spot.catch_speed(&overfished, japanese.rare_bonus, true) < spot.catch_speed(&full, japanese.rare_bonus, true));
+
+    // Sample single catches from each state, putting the fish back every time
+    let rare_share = |grounds: &FishingGrounds, world_seed: &mut WorldSeed| {
+        let rares = (0..4000)
+            .filter(|_| {
+                let species = grounds.clone().take_fish(&spot.reach, japanese.rare_bonus, true, world_seed.rng(RngStream::Fishing { x: 1, y: 1 })).unwrap();
+                species.rarity() == FishRarity::Rare
+            })
+            .count();
+        rares as f32 / 4000.0
+    };
+    assert!(rare_share(&overfished, &mut world_seed) < rare_share(&full, &mut world_seed));
+}
+
+#[test]
+fn a_full_basket_leaves_the_fish_in_the_water() {
+    let mut grounds = FishingGrounds::new(7, &WorldGenConfig::default(), &SpeciesCatalogue::default(), &grid_with(&[(1, 0, TileType::DeepWater)]));
+    let somali = uncle_def("Somali");
+    let spot = FishingSpot::at(1, 1, somali.casting_radius, Some(TileType::Land), &grounds).unwrap();
+    let mut uncle = Uncle::new(&somali, 1, 1);
+    uncle.basket.capacity = 2;
+    let stock = |grounds: &FishingGrounds| grounds.body_at(1, 0).unwrap().populations.iter().map(|p| p.count).sum::<f32>();
+    let before = stock(&grounds);
+
+    // Long enough for many bites in one tick; only two fit
+    let caught = advance_fishing(&mut uncle, &somali, Duration::from_secs(600), true, &mut WorldSeed::from_seed(7), &mut grounds, &SpeciesCatalogue::default(), &spot);
+    assert_eq!((caught.len(), uncle.basket.fish.len()), (2, 2));
+    assert!((before - stock(&grounds) - 2.0).abs() < 1e-3, "{} fish taken", before - stock(&grounds));
+}
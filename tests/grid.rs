@@ -0,0 +1,144 @@
+use bevy::prelude::*;
+
+use rarephish_bevy::components::{Tile, TileType};
+use rarephish_bevy::constants::{CHUNK_SIZE, TILE_SIZE};
+use rarephish_bevy::grid::{chunk_of, chunks_between, TileGrid};
+use rarephish_bevy::replay::{headless_app, run_until_tick};
+use rarephish_bevy::worldgen::WorldGenConfig;
+
+/// One chunk of land with a pond at (0, 0)-(1, 1) and a lone water tile at (5, 3)
+fn pond_grid() -> TileGrid {
+    let mut grid = TileGrid::filled(6, 4, TileType::Land);
+    for (x, y) in [(0, 0), (1, 0), (0, 1), (1, 1), (5, 3)] {
+        grid.set(x, y, TileType::Water);
+    }
+    grid
+}
+
+#[test]
+fn chunk_coordinates_handle_negative_tiles() {
+    let size = CHUNK_SIZE as i32;
+    assert_eq!(chunk_of(0, 0), (0, 0));
+    assert_eq!(chunk_of(size - 1, size), (0, 1));
+    assert_eq!(chunk_of(-1, -size), (-1, -1));
+    assert_eq!(chunk_of(-size - 1, 0), (-2, 0));
+    assert_eq!(chunks_between((-1, 0), (size, size - 1)), vec![(-1, 0), (0, 0), (1, 0)]);
+}
+
+#[test]
+fn neighbourhoods_stop_at_ungenerated_chunks() {
+    let mut grid = pond_grid();
+
+    assert_eq!(grid.edge_neighbours(0, 0).collect::<Vec<_>>(), vec![(1, 0), (0, 1)]);
+    assert_eq!(grid.edge_neighbours(2, 2).count(), 4);
+    assert_eq!(grid.within(0, 0, 1).count(), 3);
+    assert_eq!(grid.within(2, 2, 1).count(), 8);
+    assert_eq!(grid.within(2, 2, 2).count(), 24, "clipped by the chunks to the left and below");
+    assert!(grid.get(-1, 0).is_none() && grid.get(CHUNK_SIZE as i32, 0).is_none());
+    assert!(grid.entity(0, 0).is_none(), "nothing is spawned");
+
+    grid.insert_chunk((-1, 0), vec![TileType::Land; CHUNK_SIZE * CHUNK_SIZE]);
+    assert_eq!(grid.edge_neighbours(0, 0).count(), 3);
+    assert_eq!(grid.within(0, 0, 1).count(), 5);
+}
+
+#[test]
+fn flood_fill_and_distance_to_water() {
+    let grid = pond_grid();
+    let water = |_: i32, _: i32, t: TileType| t.is_water();
+
+    let mut pond = grid.flood_fill((1, 1), water);
+    pond.sort();
+    assert_eq!(pond, vec![(0, 0), (0, 1), (1, 0), (1, 1)]);
+    assert_eq!(grid.flood_fill((5, 3), water), vec![(5, 3)]);
+    assert!(grid.flood_fill((3, 0), water).is_empty());
+    assert_eq!(grid.flood_fill((3, 0), |_, _, t| !t.is_water()).len(), CHUNK_SIZE * CHUNK_SIZE - 5);
+    // The predicate sees positions too, so fills can be kept inside a rectangle
+    assert_eq!(grid.flood_fill((3, 0), |x, y, t| x < 6 && y < 4 && !t.is_water()).len(), 24 - 5);
+
+    // Diagonals count as one step, like casting radii
+    assert_eq!(grid.distance_to_water(1, 1, 5), Some(0));
+    assert_eq!(grid.distance_to_water(2, 2, 5), Some(1));
+    assert_eq!(grid.distance_to_water(3, 1, 5), Some(2));
+    assert_eq!(grid.distance_to_water(3, 1, 1), None);
+}
+
+#[test]
+fn chunks_generate_the_same_tiles_in_any_order() {
+    let config = WorldGenConfig::default();
+    let chunks = chunks_between((-20, -20), (40, 40));
+
+    let mut forward = TileGrid::default();
+    for &chunk in &chunks {
+        assert!(forward.generate_chunk(12, &config, chunk));
+    }
+    let mut backward = TileGrid::default();
+    for &chunk in chunks.iter().rev() {
+        backward.generate_chunk(12, &config, chunk);
+    }
+    assert!(!backward.generate_chunk(12, &config, chunks[0]), "already generated");
+
+    assert_eq!(forward.iter().collect::<Vec<_>>(), backward.iter().collect::<Vec<_>>());
+    // Chunks line up with generating the whole region at once
+    let region = config.generate_region(12, -16, -32, 3 * CHUNK_SIZE, 2 * CHUNK_SIZE);
+    for (i, &tile_type) in region.iter().enumerate() {
+        let (x, y) = (-16 + (i % (3 * CHUNK_SIZE)) as i32, -32 + (i / (3 * CHUNK_SIZE)) as i32);
+        assert_eq!(forward.get(x, y), Some(tile_type), "tile ({x}, {y})");
+    }
+}
+
+#[test]
+fn edits_survive_regenerating_a_chunk() {
+    let config = WorldGenConfig::default();
+    let mut grid = TileGrid::default();
+    grid.generate_chunk(3, &config, (-2, 1));
+    grid.set(-20, 17, TileType::Dock);
+    assert_eq!(grid.edits(), vec![(-20, 17, TileType::Dock)]);
+
+    // Edits restored before their chunk exists are applied when it is generated
+    let mut restored = TileGrid::default();
+    for (x, y, tile_type) in grid.edits() {
+        restored.add_edit(x, y, tile_type);
+    }
+    assert!(restored.get(-20, 17).is_none());
+    restored.generate_chunk(3, &config, (-2, 1));
+    assert_eq!(restored.iter().collect::<Vec<_>>(), grid.iter().collect::<Vec<_>>());
+}
+
+#[test]
+fn world_and_grid_coordinates_round_trip() {
+    for y in -20..20 {
+        for x in -20..20 {
+            let center = TileGrid::tile_to_world(x, y);
+            assert_eq!(TileGrid::world_to_tile(center), (x, y));
+            // Anywhere inside the tile hits it
+            for corner in [Vec2::new(1.0, 1.0), Vec2::new(-1.0, 1.0), Vec2::new(1.0, -1.0), Vec2::new(-1.0, -1.0)] {
+                let inside = center + corner * (TILE_SIZE / 2.0 - 0.5);
+                assert_eq!(TileGrid::world_to_tile(inside), (x, y));
+            }
+        }
+    }
+}
+
+#[test]
+fn grid_tracks_the_spawned_tiles() {
+    let mut app = headless_app();
+    app.finish();
+    app.cleanup();
+    run_until_tick(&mut app, 1);
+
+    let grid = app.world().resource::<TileGrid>().clone();
+    let config = app.world().resource::<WorldGenConfig>().clone();
+    let home = chunks_between((0, 0), (config.width as i32 - 1, config.height as i32 - 1));
+    assert_eq!(grid.spawned_chunks(), home);
+
+    let world = app.world_mut();
+    let mut tiles = 0;
+    for (entity, tile, transform) in world.query::<(Entity, &Tile, &Transform)>().iter(world) {
+        assert_eq!(grid.entity(tile.x, tile.y), Some(entity));
+        assert_eq!(grid.get(tile.x, tile.y), Some(tile.tile_type));
+        assert_eq!(TileGrid::world_to_tile(transform.translation.truncate()), (tile.x, tile.y));
+        tiles += 1;
+    }
+    assert_eq!(tiles, home.len() * CHUNK_SIZE * CHUNK_SIZE);
+}
@@ -0,0 +1,114 @@
+use rarephish_bevy::components::{TileType, Uncle};
+use rarephish_bevy::constants::{DAY_LENGTH_SECONDS, MAX_OFFLINE_SECONDS};
+use rarephish_bevy::fishing::FishingGrounds;
+use rarephish_bevy::grid::TileGrid;
+use rarephish_bevy::resources::{DayNightCycle, GameState, OfflineReport, WorldSeed};
+use rarephish_bevy::species::SpeciesCatalogue;
+use rarephish_bevy::systems::offline::simulate_offline;
+use rarephish_bevy::uncles::{UncleRoster, UncleType};
+use rarephish_bevy::worldgen::WorldGenConfig;
+
+/// Land with one small pond at (1, 0)-(2, 0) and its fishing grounds
+fn pond() -> (TileGrid, FishingGrounds) {
+    let mut grid = TileGrid::filled(8, 4, TileType::Land);
+    grid.set(1, 0, TileType::Water);
+    grid.set(2, 0, TileType::Water);
+    let grounds = FishingGrounds::new(9, &WorldGenConfig::default(), &SpeciesCatalogue::default(), &grid);
+    (grid, grounds)
+}
+
+fn fish_left(grounds: &FishingGrounds) -> f32 {
+    grounds.body_at(1, 0).unwrap().populations.iter().map(|p| p.count).sum()
+}
+
+/// Runs the catch-up for `seconds` with `uncles` around the pond
+fn away(seconds: f32, uncles: &mut [Uncle], day_night: &mut DayNightCycle, grid: &TileGrid, grounds: &mut FishingGrounds) -> OfflineReport {
+    simulate_offline(
+        seconds,
+        uncles,
+        &mut GameState::default(),
+        day_night,
+        &mut WorldSeed::from_seed(9),
+        grounds,
+        &SpeciesCatalogue::default(),
+        &UncleRoster::default(),
+        |x, y| grid.get(x, y),
+    )
+}
+
+fn somali(x: i32, y: i32) -> Uncle {
+    Uncle::new(UncleRoster::default().get(&UncleType::new("Somali")).unwrap(), x, y)
+}
+
+#[test]
+fn time_away_is_capped() {
+    let (grid, mut grounds) = pond();
+    let mut day_night = DayNightCycle::default();
+    let report = away(30.0 * 24.0 * 3600.0, &mut [], &mut day_night, &grid, &mut grounds);
+    assert_eq!(report.seconds_away, MAX_OFFLINE_SECONDS);
+    assert_eq!(report.days_passed, (MAX_OFFLINE_SECONDS / DAY_LENGTH_SECONDS) as u32);
+
+    // A clock set back since the save does not run time backwards
+    let report = away(-500.0, &mut [], &mut day_night, &grid, &mut grounds);
+    assert_eq!((report.seconds_away, report.days_passed), (0.0, 0));
+}
+
+#[test]
+fn days_passed_match_the_clock() {
+    let (grid, mut grounds) = pond();
+    let mut day_night = DayNightCycle::default();
+    let start = day_night.day_number;
+
+    // Short of a full day nothing turns over
+    let report = away(DAY_LENGTH_SECONDS - 1.0, &mut [], &mut day_night, &grid, &mut grounds);
+    assert_eq!((report.days_passed, day_night.day_number), (0, start));
+
+    let report = away(DAY_LENGTH_SECONDS * 3.5, &mut [], &mut day_night, &grid, &mut grounds);
+    assert_eq!(report.days_passed, 4, "the leftover second of the first call completes a day");
+    assert_eq!(day_night.day_number, start + 4);
+    assert_eq!(day_night.cashouts_remaining, day_night.max_cashouts_per_day);
+    assert_eq!(day_night.is_day, day_night.is_daytime());
+}
+
+#[test]
+fn uncles_deplete_the_water_while_away() {
+    let (grid, mut grounds) = pond();
+    let full = fish_left(&grounds);
+    let mut uncles = [somali(1, 1), somali(2, 1)];
+    let mut day_night = DayNightCycle::default();
+
+    let report = away(600.0, &mut uncles, &mut day_night, &grid, &mut grounds);
+    assert!(!report.caught.is_empty(), "nothing caught");
+    assert!(report.caught.iter().all(|offline| offline.uncle < uncles.len()));
+    assert!(report.escaped.iter().all(|offline| offline.uncle < uncles.len()));
+    let in_baskets: usize = uncles.iter().map(|uncle| uncle.basket.fish.len()).sum();
+    assert_eq!(in_baskets + report.escaped.len(), report.caught.len(), "caught fish are kept or escaped");
+
+    // Every fish caught came out of the pond, less what regrew meanwhile
+    let left = fish_left(&grounds);
+    assert!(left < full, "{} of {} fish left", left, full);
+    assert!(full - left <= report.caught.len() as f32 + 1e-3);
+}
+
+#[test]
+fn the_water_regrows_while_away() {
+    let (grid, mut grounds) = pond();
+    let full = fish_left(&grounds);
+    for body in grounds.bodies.values_mut() {
+        for population in &mut body.populations {
+            population.count = population.capacity * 0.1;
+        }
+    }
+    let depleted = fish_left(&grounds);
+    let mut day_night = DayNightCycle::default();
+
+    // Closed for three in-game days with nobody fishing
+    away(DAY_LENGTH_SECONDS * 3.0, &mut [], &mut day_night, &grid, &mut grounds);
+    let regrown = fish_left(&grounds);
+    assert!(regrown > depleted * 2.0, "{} -> {}", depleted, regrown);
+    assert!(regrown < full);
+
+    // Up to capacity and no further
+    away(MAX_OFFLINE_SECONDS, &mut [], &mut day_night, &grid, &mut grounds);
+    assert!((fish_left(&grounds) - full).abs() < 1e-2, "{} of {}", fish_left(&grounds), full);
+}
@@ -0,0 +1,102 @@
+use bevy::prelude::*;
+
+use rarephish_bevy::actions::{PendingActions, PlayerAction};
+use rarephish_bevy::components::{Tile, TileType};
+use rarephish_bevy::replay::{headless_app, play_headless, run_until_tick, ReplayFile};
+use rarephish_bevy::resources::{GameState, SimulationTick, WorldSeed};
+use rarephish_bevy::systems::replay::ReplayRecorder;
+use rarephish_bevy::ReplayPlugin;
+use rarephish_bevy::uncles::UncleType;
+
+const TICKS_PER_MINUTE: u64 = 20 * 60;
+
+/// Land tiles next to water, in a stable order
+fn coastal_tiles(app: &mut App) -> Vec<(i32, i32)> {
+    let world = app.world_mut();
+    let tiles: Vec<(i32, i32, TileType)> = world
+        .query::<&Tile>()
+        .iter(world)
+        .map(|tile| (tile.x, tile.y, tile.tile_type))
+        .collect();
+
+    let is_water = |x: i32, y: i32| {
+        tiles.iter().any(|&(tx, ty, t)| tx == x && ty == y && t.is_water())
+    };
+
+    let mut coastal: Vec<(i32, i32)> = tiles
+        .iter()
+        .filter(|&&(_, _, t)| t.can_place_uncle())
+        .filter(|&&(x, y, _)| {
+            (x - 1..=x + 1)
+                .flat_map(|nx| (y - 1..=y + 1).map(move |ny| (nx, ny)))
+                .any(|(nx, ny)| (nx, ny) != (x, y) && is_water(nx, ny))
+        })
+        .map(|&(x, y, _)| (x, y))
+        .collect();
+    coastal.sort();
+    coastal
+}
+
+fn queue(app: &mut App, action: PlayerAction) {
+    app.world_mut().resource_mut::<PendingActions>().push(action);
+}
+
+/// Plays a short scripted session with recording on and returns its replay
+fn record_session(seed: u64) -> ReplayFile {
+    let mut app = headless_app();
+    app.insert_resource(WorldSeed::from_seed(seed))
+        .add_plugins(ReplayPlugin::Record);
+    app.finish();
+    app.cleanup();
+
+    run_until_tick(&mut app, 1);
+    let coast = coastal_tiles(&mut app);
+    assert!(coast.len() >= 2, "seed {} has too little coastline for the test", seed);
+
+    queue(&mut app, PlayerAction::SelectUncleType(UncleType::new("Mongolian")));
+    queue(&mut app, PlayerAction::PlaceUncle { x: coast[0].0, y: coast[0].1 });
+    run_until_tick(&mut app, TICKS_PER_MINUTE);
+
+    queue(&mut app, PlayerAction::SelectPlacedUncle { x: coast[0].0, y: coast[0].1 });
+    queue(&mut app, PlayerAction::RemoveLowestFish);
+    queue(&mut app, PlayerAction::CashOutSelected);
+    run_until_tick(&mut app, 3 * TICKS_PER_MINUTE);
+
+    queue(&mut app, PlayerAction::PlaceUncle { x: coast[1].0, y: coast[1].1 });
+    run_until_tick(&mut app, 5 * TICKS_PER_MINUTE);
+    queue(&mut app, PlayerAction::CashOutAll);
+    run_until_tick(&mut app, 6 * TICKS_PER_MINUTE);
+
+    let world = app.world();
+    world
+        .resource::<ReplayRecorder>()
+        .to_replay(world.resource::<SimulationTick>().0, world.resource::<GameState>())
+        .expect("recording started on the first tick")
+}
+
+#[test]
+fn replay_reproduces_recorded_session() {
+    let replay = record_session(7);
+    assert!(replay.final_state.fish_count > 0, "session should have cashed out some fish");
+    assert_eq!(replay.actions.len(), 7);
+
+    let final_state = replay.final_state.clone();
+    assert_eq!(play_headless(replay), final_state);
+}
+
+#[test]
+fn replay_survives_a_round_trip_through_json() {
+    let replay = record_session(1234);
+    let json = serde_json::to_string(&replay).unwrap();
+    let loaded = ReplayFile::from_json(&json).unwrap();
+
+    assert_eq!(play_headless(loaded), replay.final_state);
+}
+
+#[test]
+fn same_seed_and_actions_give_identical_runs() {
+    let first = record_session(99);
+    let second = record_session(99);
+
+    assert_eq!(first.final_state, second.final_state);
+}
@@ -0,0 +1,117 @@
+use std::time::Duration;
+
+use rand::Rng;
+
+use rarephish_bevy::components::{Fish, FishRarity, TileType, Uncle};
+use rarephish_bevy::fishing::{FishingGrounds, FishingSpot};
+use rarephish_bevy::grid::TileGrid;
+use rarephish_bevy::resources::{RngStream, WorldSeed};
+use rarephish_bevy::species::SpeciesCatalogue;
+use rarephish_bevy::systems::gameplay::{advance_escapes, advance_fishing};
+use rarephish_bevy::uncles::{UncleDef, UncleRoster, UncleType};
+use rarephish_bevy::worldgen::WorldGenConfig;
+
+/// A chunk of land with a row of water along the bottom
+fn lake() -> FishingGrounds {
+    let mut grid = TileGrid::filled(16, 16, TileType::Land);
+    for x in 0..16 {
+        grid.set(x, 0, TileType::Water);
+    }
+    FishingGrounds::new(2, &WorldGenConfig::default(), &SpeciesCatalogue::default(), &grid)
+}
+
+fn uncle_def(id: &str) -> UncleDef {
+    UncleRoster::default().get(&UncleType::new(id)).unwrap().clone()
+}
+
+/// The fish an uncle at (x, 1) lands over `seconds`; `between` runs after every second
+fn catches(world_seed: &mut WorldSeed, x: i32, seconds: u32, mut between: impl FnMut(&mut WorldSeed)) -> Vec<Fish> {
+    let mut grounds = lake();
+    let somali = uncle_def("Somali");
+    let mut uncle = Uncle::new(&somali, x, 1);
+    let spot = FishingSpot::at(x, 1, 1, Some(TileType::Land), &grounds).unwrap();
+    let catalogue = SpeciesCatalogue::default();
+    let mut caught = Vec::new();
+    for _ in 0..seconds {
+        caught.extend(advance_fishing(&mut uncle, &somali, Duration::from_secs(1), true, world_seed, &mut grounds, &catalogue, &spot));
+        uncle.basket.fish.clear();
+        between(world_seed);
+    }
+    caught
+}
+
+fn summary(fish: &[Fish]) -> Vec<(String, u32)> {
+    fish.iter().map(|f| (f.name.clone(), f.value)).collect()
+}
+
+#[test]
+fn escape_rolls_do_not_change_catches() {
+    let quiet = catches(&mut WorldSeed::from_seed(7), 3, 120, |_| {});
+
+    // Another uncle's basket flopping away every second, a different number of times each second
+    let mongolian = uncle_def("Mongolian");
+    let mut other = Uncle::new(&mongolian, 10, 1);
+    let mut rolls = 0;
+    let busy = catches(&mut WorldSeed::from_seed(7), 3, 120, |world_seed| {
+        rolls += 1;
+        for _ in 0..rolls % 5 {
+            other.basket.fish.push(Fish::new("Flopper".to_string(), FishRarity::Common, 1, mongolian.id.clone()));
+        }
+        advance_escapes(&mut other, &mongolian, 0.5, world_seed);
+        world_seed.rng(RngStream::Events).gen::<u64>();
+    });
+
+    assert!(!quiet.is_empty());
+    assert_eq!(summary(&quiet), summary(&busy));
+}
+
+#[test]
+fn each_uncle_has_its_own_fish_sequence() {
+    let alone = catches(&mut WorldSeed::from_seed(7), 3, 120, |_| {});
+
+    // A second uncle fishing other water between every catch of the first
+    let mut other_grounds = lake();
+    let japanese = uncle_def("Japanese");
+    let mut other = Uncle::new(&japanese, 12, 1);
+    let other_spot = FishingSpot::at(12, 1, 2, Some(TileType::Land), &other_grounds).unwrap();
+    let catalogue = SpeciesCatalogue::default();
+    let mut other_caught = 0;
+    let shared = catches(&mut WorldSeed::from_seed(7), 3, 120, |world_seed| {
+        other_caught += advance_fishing(&mut other, &japanese, Duration::from_secs(1), true, world_seed, &mut other_grounds, &catalogue, &other_spot).len();
+        other.basket.fish.clear();
+    });
+
+    assert!(other_caught > 0);
+    assert_eq!(summary(&alone), summary(&shared));
+    // Standing somewhere else means different fish
+    assert_ne!(summary(&alone), summary(&catches(&mut WorldSeed::from_seed(7), 4, 120, |_| {})));
+}
+
+#[test]
+fn streams_are_independent_and_resume_where_they_stopped() {
+    let mut world_seed = WorldSeed::from_seed(7);
+    let firsts: Vec<u64> = [
+        RngStream::Fishing { x: 0, y: 0 },
+        RngStream::Fishing { x: 0, y: 1 },
+        RngStream::Fishing { x: 1, y: 0 },
+        RngStream::Escapes,
+        RngStream::Events,
+    ]
+    .into_iter()
+    .map(|stream| world_seed.rng(stream).gen())
+    .collect();
+    for (i, a) in firsts.iter().enumerate() {
+        assert!(firsts[i + 1..].iter().all(|b| a != b), "streams overlap");
+    }
+    assert_ne!(WorldSeed::from_seed(8).rng(RngStream::Escapes).gen::<u64>(), firsts[3]);
+
+    for _ in 0..37 {
+        world_seed.rng(RngStream::Escapes).gen::<u32>();
+    }
+    let mut restored = WorldSeed::restore(7, world_seed.positions());
+    for stream in [RngStream::Fishing { x: 0, y: 1 }, RngStream::Escapes, RngStream::Events] {
+        assert_eq!(world_seed.rng(stream).gen::<u64>(), restored.rng(stream).gen::<u64>());
+    }
+    // Streams nobody drew from are not saved
+    assert_eq!(WorldSeed::from_seed(7).positions(), Vec::new());
+}
@@ -0,0 +1,261 @@
+use bevy::ecs::system::RunSystemOnce;
+use bevy::prelude::*;
+use serde_json::{json, Value};
+
+use rarephish_bevy::actions::{PendingActions, PlayerAction};
+use rarephish_bevy::components::{FishRarity, Uncle};
+use rarephish_bevy::fishing::{BodyId, FishingGrounds};
+use rarephish_bevy::grid::TileGrid;
+use rarephish_bevy::replay::{headless_app, run_until_tick};
+use rarephish_bevy::resources::{DayNightCycle, FishingStats, GameState, RngStream, SimulationTick, WorldSeed};
+use rarephish_bevy::save::{SaveError, SaveFile, SAVE_VERSION};
+use rarephish_bevy::species::{FishColor, FishPattern, FishShape};
+use rarephish_bevy::systems::persistence::{RunRestorer, RunSnapshot};
+use rarephish_bevy::uncles::UncleType;
+use rarephish_bevy::RestoreWorldSet;
+
+/// Where an uncle stands and the names and values of the fish in their basket
+type Basket = ((i32, i32), Vec<(String, u32)>);
+
+/// Baskets by uncle position
+fn baskets(app: &mut App) -> Vec<Basket> {
+    let world = app.world_mut();
+    let mut baskets: Vec<Basket> = world
+        .query::<&Uncle>()
+        .iter(world)
+        .map(|uncle| ((uncle.x, uncle.y), uncle.basket.fish.iter().map(|fish| (fish.name.clone(), fish.value)).collect()))
+        .collect();
+    baskets.sort_by_key(|&((x, y), _)| (y, x));
+    baskets
+}
+
+/// Fishing timer progress by uncle position
+fn timers(app: &mut App) -> Vec<f32> {
+    let world = app.world_mut();
+    let mut timers: Vec<_> = world.query::<&Uncle>().iter(world).map(|uncle| ((uncle.y, uncle.x), uncle.fishing_timer.elapsed_secs())).collect();
+    timers.sort_by_key(|&(position, _)| position);
+    timers.into_iter().map(|(_, elapsed)| elapsed).collect()
+}
+
+/// A run with a dock, three uncles and a day of fishing behind it
+fn played_run() -> App {
+    let mut app = headless_app();
+    app.insert_resource(WorldSeed::from_seed(21));
+    app.finish();
+    app.cleanup();
+    run_until_tick(&mut app, 1);
+
+    let (coast, dock) = {
+        let grid = app.world().resource::<TileGrid>();
+        let coast: Vec<(i32, i32)> = grid
+            .iter()
+            .filter(|&(x, y, t)| t.can_place_uncle() && grid.distance_to_water(x, y, 1) == Some(1))
+            .map(|(x, y, _)| (x, y))
+            .take(3)
+            .collect();
+        let dock = grid
+            .iter()
+            .find(|&(x, y, t)| t.can_build_dock() && grid.edge_neighbours(x, y).any(|(nx, ny)| grid.get(nx, ny).is_some_and(|n| n.can_place_uncle())))
+            .map(|(x, y, _)| (x, y))
+            .unwrap();
+        (coast, dock)
+    };
+    app.world_mut().resource_mut::<GameState>().gold = 1000;
+    let mut actions = app.world_mut().resource_mut::<PendingActions>();
+    actions.push(PlayerAction::BuildDock { x: dock.0, y: dock.1 });
+    for &(x, y) in &coast {
+        actions.push(PlayerAction::PlaceUncle { x, y });
+    }
+    run_until_tick(&mut app, 3000);
+    app
+}
+
+/// A headless app whose Startup rebuilds the world from `save` instead of generating one
+fn resumed(save: SaveFile) -> App {
+    let mut app = headless_app();
+    app.add_systems(Startup, (move |mut restorer: RunRestorer| assert!(restorer.restore(save.clone(), false))).in_set(RestoreWorldSet));
+    app.finish();
+    app.cleanup();
+    app.update();
+    app
+}
+
+#[test]
+fn a_saved_run_resumes_exactly() {
+    let mut app = played_run();
+    let save = app.world_mut().run_system_once(|snapshot: RunSnapshot| snapshot.capture());
+    assert_eq!(save.version, SAVE_VERSION);
+    assert!(!save.tile_edits.is_empty(), "the dock is an edit");
+    assert!(save.populations.iter().any(|p| p.chunks.len() > 1), "no lake across chunks fished");
+    assert!(baskets(&mut app).iter().any(|(_, fish)| !fish.is_empty()), "nothing caught");
+
+    let mut resumed = resumed(SaveFile::from_json(&save.to_json().unwrap()).unwrap());
+    assert_eq!(resumed.world().resource::<GameState>(), app.world().resource::<GameState>());
+    let clock = |app: &App| {
+        let day_night = app.world().resource::<DayNightCycle>();
+        (day_night.day_number, day_night.time_elapsed, day_night.day_progress, day_night.is_day, day_night.cashouts_remaining)
+    };
+    assert_eq!(clock(&resumed), clock(&app));
+    let streams = |app: &App| app.world().resource::<WorldSeed>().positions();
+    assert!(streams(&app).iter().any(|&(stream, _)| stream == RngStream::Escapes));
+    assert_eq!(streams(&resumed), streams(&app));
+    assert_eq!(baskets(&mut resumed), baskets(&mut app));
+    // Timers are saved as seconds and come back to the nearest nanosecond
+    for (resumed, original) in timers(&mut resumed).into_iter().zip(timers(&mut app)) {
+        assert!((resumed - original).abs() < 1e-6, "{} vs {}", resumed, original);
+    }
+    assert_eq!(resumed.world().resource::<TileGrid>().edits(), app.world().resource::<TileGrid>().edits());
+    // Fished lakes come back with their fish, whichever chunks they span
+    let fished = |app: &App| {
+        let bodies = &app.world().resource::<FishingGrounds>().bodies;
+        save.populations.iter().map(|p| bodies.get(&BodyId { chunk: p.chunk, index: p.body }).cloned()).collect::<Vec<_>>()
+    };
+    assert!(fished(&app).iter().all(Option::is_some));
+    assert_eq!(fished(&resumed), fished(&app));
+    let caught = |app: &App| app.world().resource::<FishingStats>().caught_by_rarity.clone();
+    assert_eq!(caught(&resumed), caught(&app));
+
+    // Both carry on the same way
+    let (from_original, from_resumed) = (app.world().resource::<SimulationTick>().0, resumed.world().resource::<SimulationTick>().0);
+    run_until_tick(&mut app, from_original + 600);
+    run_until_tick(&mut resumed, from_resumed + 600);
+    assert_eq!(resumed.world().resource::<GameState>(), app.world().resource::<GameState>());
+    assert_eq!(baskets(&mut resumed), baskets(&mut app));
+    assert_eq!(streams(&resumed), streams(&app));
+}
+
+/// A save of `version` in the shape that version wrote
+fn fixture(version: u32) -> Value {
+    let fish = json!({
+        "name": "Blue Striped Slimfish",
+        "rarity": "Uncommon",
+        "value": 25,
+        "time_alive": 4.0,
+        "failed_escape_attempts": 200,
+        "caught_by_uncle": "Mongolian",
+    });
+    let mut save = json!({
+        "version": version,
+        "saved_at": 0,
+        "game_state": { "fish_count": 3, "gold": 250, "multiplier": 1.5, "cash_out_cooldown": 0.0 },
+        "day_night": {
+            "time_elapsed": 30.0, "day_progress": 0.4, "day_number": 3, "is_day": true,
+            "cashouts_remaining": 2, "max_cashouts_per_day": 3,
+        },
+        "world_seed": { "seed": 5, "word_pos": 4096 },
+        "tiles": { "width": 2, "height": 1, "tiles": ["Land", "Water"] },
+        "uncles": [{
+            "uncle_type": "Mongolian", "x": 0, "y": 0, "timer_elapsed": 1.5,
+            "basket": { "fish": [fish], "capacity": 5 },
+        }],
+    });
+    if version >= 2 {
+        save["uncles"][0]["basket"]["fish"][0]["next_flop_at"] = json!(4.5);
+        save["uncles"][0]["basket"]["fish"][0]["failed_escape_attempts"] = json!(7);
+        save["populations"] = json!([{ "body": 0, "species": 3, "count": 2.0 }]);
+    }
+    if version >= 3 {
+        save.as_object_mut().unwrap().remove("tiles");
+        save["tile_edits"] = json!([{ "x": 1, "y": 0, "tile_type": "Dock" }]);
+        save["populations"] = json!([{ "chunk": [0, 0], "body": 0, "species": 3, "count": 2.0 }]);
+    }
+    if version >= 4 {
+        save["world_seed"] = json!({ "seed": 5, "streams": [{ "stream": "Escapes", "word_pos": 4096 }] });
+    }
+    if version >= 5 {
+        save["populations"] = json!([{ "chunk": [0, 0], "body": 0, "species": "koi", "count": 2.0 }]);
+        save["uncles"][0]["basket"]["fish"][0]["traits"] = json!({ "color": "Red", "pattern": "Solid", "shape": "Round", "size": "Medium" });
+        save["records"] = json!([]);
+    }
+    if version >= 6 {
+        save["stats"] = json!({ "caught_by_rarity": {}, "escaped_by_rarity": {}, "caught_by_uncle": {}, "escaped_by_uncle": {} });
+    }
+    if version >= 7 {
+        save["populations"][0]["chunks"] = json!([[0, 0]]);
+    }
+    save
+}
+
+fn load(version: u32) -> SaveFile {
+    SaveFile::from_json(&fixture(version).to_string()).unwrap_or_else(|err| panic!("v{}: {}", version, err))
+}
+
+#[test]
+fn every_version_loads() {
+    for version in 1..=SAVE_VERSION {
+        let save = load(version);
+        assert_eq!(save.version, SAVE_VERSION);
+        assert_eq!((save.game_state.gold, save.day_night.day_number), (250, 3));
+        assert_eq!(save.uncles[0].basket.fish[0].rarity, FishRarity::Uncommon);
+        assert!(save.stats.caught_by_rarity.is_empty(), "v{} stats", version);
+    }
+    assert!(matches!(SaveFile::from_json(&fixture(SAVE_VERSION + 1).to_string()), Err(SaveError::UnsupportedVersion(_))));
+    assert!(matches!(SaveFile::from_json(r#"{ "saved_at": 0 }"#), Err(SaveError::MissingVersion)));
+}
+
+#[test]
+fn v1_fish_get_the_flops_of_their_age() {
+    let fish = &load(1).uncles[0].basket.fish[0];
+    assert!(fish.failed_escape_attempts > 0 && fish.failed_escape_attempts < 200, "{} flops", fish.failed_escape_attempts);
+    assert_eq!(fish.next_flop_at, fish.time_alive, "flops on the next tick");
+    let fish = &load(2).uncles[0].basket.fish[0];
+    assert_eq!((fish.failed_escape_attempts, fish.next_flop_at), (7, 4.5));
+}
+
+#[test]
+fn v2_populations_restock_and_the_map_becomes_edits() {
+    let save = load(2);
+    assert!(save.populations.is_empty());
+    let legacy = save.tiles.expect("the old map is kept until it is turned into edits");
+    assert_eq!((legacy.width, legacy.height, legacy.tiles.len()), (2, 1, 2));
+}
+
+#[test]
+fn v3_rng_streams_start_over() {
+    let save = load(3);
+    assert_eq!(save.world_seed.seed, 5);
+    assert!(save.world_seed.streams.is_empty());
+    assert_eq!((save.populations.len(), save.tile_edits.len()), (0, 1), "v3 populations go with v4's catalogue step");
+
+    let save = load(4);
+    assert_eq!(save.world_seed.streams.len(), 1);
+    assert_eq!((save.world_seed.streams[0].stream, save.world_seed.streams[0].word_pos), (RngStream::Escapes, 4096));
+}
+
+#[test]
+fn v4_fish_get_their_traits_from_their_names() {
+    let save = load(4);
+    assert!(save.populations.is_empty());
+    let traits = save.uncles[0].basket.fish[0].traits;
+    assert_eq!((traits.color, traits.pattern, traits.shape), (FishColor::Blue, FishPattern::Striped, FishShape::Slim));
+
+    // From v5 on, traits are saved and kept
+    let save = load(5);
+    let traits = save.uncles[0].basket.fish[0].traits;
+    assert_eq!((traits.color, traits.pattern, traits.shape), (FishColor::Red, FishPattern::Solid, FishShape::Round));
+}
+
+#[test]
+fn v5_stats_start_from_zero() {
+    let mut save = fixture(5);
+    save["stats"] = json!({ "caught_by_rarity": { "Rare": 4 } });
+    let save = SaveFile::from_json(&save.to_string()).unwrap();
+    assert!(save.stats.caught_by_rarity.is_empty(), "v5 saves had no stats to keep");
+
+    let mut stats = FishingStats::default();
+    stats.caught_by_rarity.insert(FishRarity::Rare, 4);
+    stats.escaped_by_uncle.insert(UncleType::new("Somali"), 2);
+    let mut current = load(SAVE_VERSION);
+    current.stats = stats;
+    let reloaded = SaveFile::from_json(&current.to_json().unwrap()).unwrap();
+    assert_eq!(reloaded.stats.caught_by_rarity.get(&FishRarity::Rare), Some(&4));
+    assert_eq!(reloaded.stats.escaped_by_uncle.get(&UncleType::new("Somali")), Some(&2));
+}
+
+#[test]
+fn v6_populations_restock_as_lakes() {
+    assert!(load(6).populations.is_empty(), "v6 bodies ended at chunk borders");
+    let save = load(7);
+    assert_eq!(save.populations.len(), 1);
+    assert_eq!(save.populations[0].chunks, vec![(0, 0)]);
+}
@@ -0,0 +1,98 @@
+use bevy::prelude::*;
+
+use rarephish_bevy::actions::{PendingActions, PlayerAction};
+use rarephish_bevy::components::{CatchAnimation, Tile, Uncle};
+use rarephish_bevy::constants::STARTING_GOLD;
+use rarephish_bevy::events::NewWorldRequested;
+use rarephish_bevy::grid::TileGrid;
+use rarephish_bevy::replay::{headless_app, run_until_tick};
+use rarephish_bevy::resources::{DayNightCycle, GameState, OfflineReport, SelectedUncle, SimulationTick, WorldSeed};
+use rarephish_bevy::seed::{parse_seed, random_seed, seed_code};
+use rarephish_bevy::uncles::UncleRoster;
+use rarephish_bevy::worldgen::WorldGenConfig;
+
+#[test]
+fn seed_codes_round_trip() {
+    for seed in [0, 1, 42, 1_700_000_000, u64::MAX / 3, u64::MAX] {
+        let code = seed_code(seed);
+        assert_eq!(code.len(), 15, "{}", code);
+        assert_eq!(code.matches('-').count(), 2);
+        assert_eq!(parse_seed(&code), Some(seed), "{}", code);
+        // Codes survive being retyped sloppily
+        assert_eq!(parse_seed(&format!("  {}  ", code.to_lowercase().replace('-', ""))), Some(seed));
+    }
+    assert_eq!(seed_code(0), "0000-0000-00000");
+    assert_eq!(parse_seed("0000-0000-0000O"), Some(0), "O reads as zero");
+    assert_eq!(parse_seed("0000-0000-0000l"), Some(1), "l reads as one");
+}
+
+#[test]
+fn any_text_makes_a_seed() {
+    assert_eq!(parse_seed("42"), Some(42));
+    assert_eq!(parse_seed(""), None);
+    assert_eq!(parse_seed("   "), None);
+
+    // Phrases are hashed, ignoring case and spacing
+    let lagoon = parse_seed("Blue Lagoon").unwrap();
+    assert_eq!(parse_seed("  blue   LAGOON "), Some(lagoon));
+    assert_ne!(parse_seed("blue lagoons"), Some(lagoon));
+    assert_eq!(parse_seed(&seed_code(lagoon)), Some(lagoon));
+
+    // Too long for a code, too big for a number: still a seed
+    assert!(parse_seed("99999999999999999999999").is_some());
+    assert!(parse_seed("ZZZZ-ZZZZ-ZZZZZ").is_some(), "overflowing codes are hashed");
+
+    assert_ne!(random_seed(), random_seed());
+}
+
+#[test]
+fn playing_a_seed_starts_a_fresh_world() {
+    let mut app = headless_app();
+    app.insert_resource(WorldSeed::from_seed(1));
+    app.finish();
+    app.cleanup();
+    run_until_tick(&mut app, 1);
+
+    // Spend some gold and let time pass in the first world
+    let (x, y) = {
+        let grid = app.world().resource::<TileGrid>();
+        grid.iter()
+            .find(|&(x, y, t)| t.can_place_uncle() && grid.distance_to_water(x, y, 1) == Some(1))
+            .map(|(x, y, _)| (x, y))
+            .unwrap()
+    };
+    let other_uncle = app.world().resource::<UncleRoster>().iter().nth(1).unwrap().id.clone();
+    let mut actions = app.world_mut().resource_mut::<PendingActions>();
+    actions.push(PlayerAction::PlaceUncle { x, y });
+    actions.push(PlayerAction::SelectUncleType(other_uncle));
+    run_until_tick(&mut app, 200);
+    assert_ne!(app.world().resource::<GameState>().gold, STARTING_GOLD);
+
+    app.world_mut().init_resource::<OfflineReport>();
+    app.world_mut().spawn(CatchAnimation { origin: Vec3::ZERO, elapsed: 0.0 });
+    let seed = parse_seed("blue lagoon").unwrap();
+    app.world_mut().send_event(NewWorldRequested { seed });
+    app.update();
+
+    let world = app.world_mut();
+    assert_eq!(world.resource::<WorldSeed>().seed, seed);
+    assert_eq!(world.resource::<GameState>().gold, STARTING_GOLD);
+    assert_eq!(world.resource::<DayNightCycle>().day_number, DayNightCycle::default().day_number);
+    assert!(world.resource::<SimulationTick>().0 <= 1);
+    assert_eq!(world.query::<&Uncle>().iter(world).count(), 0);
+    assert_eq!(world.query::<&Parent>().iter(world).count(), 0, "uncle sprites and labels go with their uncles");
+    assert!(!world.contains_resource::<OfflineReport>());
+    assert_eq!(world.query::<&CatchAnimation>().iter(world).count(), 0);
+    assert_eq!(world.resource::<SelectedUncle>().uncle_type, world.resource::<UncleRoster>().first().id);
+
+    // Only the new world's tiles are left, and they match its seed
+    let config = world.resource::<WorldGenConfig>().clone();
+    let grid = world.resource::<TileGrid>().clone();
+    let home = config.generate_region(seed, 0, 0, config.width, config.height);
+    for (i, &tile_type) in home.iter().enumerate() {
+        assert_eq!(grid.get((i % config.width) as i32, (i / config.width) as i32), Some(tile_type));
+    }
+    for tile in world.query::<&Tile>().iter(world) {
+        assert_eq!(grid.entity(tile.x, tile.y).and_then(|e| world.get::<Tile>(e)).map(|t| t.tile_type), Some(tile.tile_type));
+    }
+}
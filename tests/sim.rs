@@ -0,0 +1,26 @@
+use std::process::Command;
+
+use serde_json::Value;
+
+/// Runs the simulator for six days with `step_ms` frames and returns its JSON report
+fn simulate(step_ms: u64) -> Value {
+    let out = std::env::temp_dir().join(format!("rarephish-sim-{}-{}ms.json", std::process::id(), step_ms));
+    let status = Command::new(env!("CARGO_BIN_EXE_rarephish-sim"))
+        .args(["--days", "6", "--seed", "42", "--format", "json", "--step-ms", &step_ms.to_string()])
+        .arg("--out")
+        .arg(&out)
+        .status()
+        .expect("simulator runs");
+    assert!(status.success(), "--step-ms {}: {}", step_ms, status);
+
+    let report = std::fs::read_to_string(&out).unwrap();
+    std::fs::remove_file(&out).unwrap();
+    serde_json::from_str(&report).unwrap()
+}
+
+#[test]
+fn the_report_does_not_depend_on_the_frame_length() {
+    let ticks = simulate(50);
+    assert!(ticks["uncles_hired"].as_object().is_some_and(|hired| !hired.is_empty()), "nobody hired");
+    assert_eq!(simulate(10), ticks);
+}
@@ -0,0 +1,148 @@
+use std::time::Duration;
+
+use rarephish_bevy::components::{Fish, TileType, Uncle};
+use rarephish_bevy::fishing::{FishingGrounds, FishingSpot};
+use rarephish_bevy::grid::TileGrid;
+use rarephish_bevy::resources::WorldSeed;
+use rarephish_bevy::save::SaveFile;
+use rarephish_bevy::species::{
+    CatalogueError, FishColor, FishPattern, FishShape, SpeciesCatalogue, SpeciesId, SPECIES_CATALOGUE_PATH,
+};
+use rarephish_bevy::systems::gameplay::advance_fishing;
+use rarephish_bevy::uncles::{UncleRoster, UncleType};
+use rarephish_bevy::worldgen::WorldGenConfig;
+
+/// One species of each rarity, all biting only at night
+const NIGHT_LAKE: &str = r#"{
+    "species": [
+        { "id": "a", "name": "A", "rarity": "Common", "habitats": ["Water"], "activity": "Night",
+          "shape": "Slim", "pattern": "Solid", "colors": ["Blue"], "value": { "min": 1, "max": 2 },
+          "size": { "median_cm": 20, "median_kg": 0.2, "spread": 0.2 } },
+        { "id": "b", "name": "B", "rarity": "Uncommon", "habitats": ["Water"], "activity": "Night",
+          "shape": "Flat", "pattern": "Striped", "colors": ["Red"], "value": { "min": 5, "max": 8 },
+          "size": { "median_cm": 20, "median_kg": 0.2, "spread": 0.2 } },
+        { "id": "c", "name": "C", "rarity": "Rare", "habitats": ["Water"], "activity": "Night",
+          "shape": "Bulky", "pattern": "Spotted", "colors": ["Pink"], "value": { "min": 20, "max": 30 },
+          "size": { "median_cm": 20, "median_kg": 0.2, "spread": 0.2 } }
+    ]
+}"#;
+
+/// A chunk of land with a row of `water` along the bottom
+fn lake(catalogue: &SpeciesCatalogue, water: TileType) -> FishingGrounds {
+    let mut grid = TileGrid::filled(16, 16, TileType::Land);
+    for x in 0..16 {
+        grid.set(x, 0, water);
+    }
+    FishingGrounds::new(9, &WorldGenConfig::default(), catalogue, &grid)
+}
+
+/// Everything a Somali uncle at (3, 1) catches over `seconds` in daylight
+fn catch_from(catalogue: &SpeciesCatalogue, water: TileType, seconds: u32) -> Vec<Fish> {
+    let full = lake(catalogue, water);
+    let mut grounds = full.clone();
+    let somali = UncleRoster::default().get(&UncleType::new("Somali")).unwrap().clone();
+    let mut uncle = Uncle::new(&somali, 3, 1);
+    let spot = FishingSpot::at(3, 1, 1, Some(TileType::Land), &grounds).unwrap();
+    let mut world_seed = WorldSeed::from_seed(9);
+    let mut caught = Vec::new();
+    for _ in 0..seconds {
+        caught.extend(advance_fishing(&mut uncle, &somali, Duration::from_secs(1), true, &mut world_seed, &mut grounds, catalogue, &spot));
+        uncle.basket.fish.clear();
+        grounds = full.clone();
+    }
+    caught
+}
+
+#[test]
+fn built_in_catalogue_matches_the_asset_file() {
+    let shipped = SpeciesCatalogue::read(SPECIES_CATALOGUE_PATH.as_ref()).expect("assets/species.json is valid");
+    let built_in = SpeciesCatalogue::default();
+    let ids = |catalogue: &SpeciesCatalogue| catalogue.iter().map(|def| def.id.clone()).collect::<Vec<_>>();
+    assert_eq!(ids(&shipped), ids(&built_in));
+    assert_eq!(built_in.index_of(&SpeciesId::new("koi")).map(|i| built_in.at(i).name.as_str()), Some("Golden Koi"));
+}
+
+#[test]
+fn invalid_catalogues_are_rejected() {
+    assert!(matches!(SpeciesCatalogue::from_json(r#"{ "species": [] }"#), Err(CatalogueError::Invalid(_))));
+    assert!(matches!(SpeciesCatalogue::from_json("{ not json"), Err(CatalogueError::Json(_))));
+
+    let mut defs: Vec<_> = SpeciesCatalogue::default().iter().cloned().collect();
+    defs.push(defs[0].clone());
+    assert!(matches!(SpeciesCatalogue::new(defs), Err(CatalogueError::Invalid(_))), "duplicate id accepted");
+
+    let mut landlubber = SpeciesCatalogue::default().at(0).clone();
+    landlubber.habitats = vec![TileType::Forest];
+    assert!(SpeciesCatalogue::new(vec![landlubber]).is_err());
+
+    let mut colorless = SpeciesCatalogue::default().at(0).clone();
+    colorless.colors.clear();
+    assert!(SpeciesCatalogue::new(vec![colorless]).is_err());
+}
+
+#[test]
+fn caught_fish_carry_their_species_traits() {
+    let catalogue = SpeciesCatalogue::default();
+    for water in [TileType::DeepWater, TileType::Water, TileType::Shallows, TileType::River] {
+        let caught = catch_from(&catalogue, water, 600);
+        assert!(caught.len() > 20, "only {} fish from {:?}", caught.len(), water);
+        for fish in &caught {
+            let def = catalogue.get(&fish.species).expect("caught fish are catalogued");
+            assert!(def.lives_in(water), "{} caught in {:?}", def.id, water);
+            assert_eq!((fish.name.as_str(), fish.rarity), (def.name.as_str(), def.rarity));
+            assert_eq!((fish.traits.pattern, fish.traits.shape), (def.pattern, def.shape));
+            assert!(def.colors.contains(&fish.traits.color));
+            assert!((fish.weight_kg - def.weight_at(fish.length_cm)).abs() < 1e-6);
+            let scale = fish.weight_kg / def.size.median_kg;
+            let range = (def.value.min as f32 * scale).round().max(1.0)..=(def.value.max as f32 * scale).round().max(1.0);
+            assert!(range.contains(&(fish.value as f32)), "{} worth {} outside {:?}", def.id, fish.value, range);
+            assert_eq!(fish.escape_strength, def.escape_strength(&fish.traits));
+        }
+    }
+}
+
+#[test]
+fn night_species_bite_less_by_day() {
+    let catalogue = SpeciesCatalogue::from_json(NIGHT_LAKE).unwrap();
+    let grounds = lake(&catalogue, TileType::Water);
+    let reach = grounds.reachable(3, 1, 1);
+    let (day, night) = (grounds.bite_rate(&reach, 0.0, true), grounds.bite_rate(&reach, 0.0, false));
+    assert!(night > 0.0);
+    assert!((day - night * 0.25).abs() < 1e-4, "day {} night {}", day, night);
+
+    // Species without a habitat match never show up
+    assert!(lake(&catalogue, TileType::River).tiles.values().all(|tile| tile.pool.is_empty()));
+}
+
+#[test]
+fn old_saves_get_traits_from_fish_names() {
+    let v1 = r#"{
+        "version": 1,
+        "saved_at": 0,
+        "game_state": { "fish_count": 0, "gold": 100, "multiplier": 1.0, "cash_out_cooldown": 0.0 },
+        "day_night": {
+            "time_elapsed": 0.0, "day_progress": 0.0, "day_number": 1, "is_day": true,
+            "cashouts_remaining": 3, "max_cashouts_per_day": 3
+        },
+        "world_seed": { "seed": 1, "word_pos": 0 },
+        "tiles": { "width": 0, "height": 0, "tiles": [] },
+        "uncles": [{
+            "uncle_type": "Mongolian", "x": 0, "y": 0, "timer_elapsed": 0.0,
+            "basket": { "fish": [
+                { "name": "Orange Spotted Roundfish", "rarity": "Rare", "value": 40, "time_alive": 0.0,
+                  "failed_escape_attempts": 0, "caught_by_uncle": "Mongolian" },
+                { "name": "Mystery", "rarity": "Common", "value": 2, "time_alive": 0.0,
+                  "failed_escape_attempts": 0, "caught_by_uncle": "Mongolian" }
+            ], "capacity": 5 }
+        }]
+    }"#;
+
+    let save = SaveFile::from_json(v1).expect("v1 save migrates");
+    let fish = &save.uncles[0].basket.fish;
+    assert_eq!(
+        (fish[0].traits.color, fish[0].traits.pattern, fish[0].traits.shape),
+        (FishColor::Orange, FishPattern::Spotted, FishShape::Round)
+    );
+    assert_eq!(fish[0].escape_strength, 1.0);
+    assert_eq!(fish[1].traits, Default::default());
+}
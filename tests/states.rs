@@ -0,0 +1,84 @@
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+
+use rarephish_bevy::actions::{PendingActions, PlayerAction};
+use rarephish_bevy::grid::TileGrid;
+use rarephish_bevy::replay::{headless_app, run_until_tick};
+use rarephish_bevy::resources::{DayNightCycle, GameState, SaveSlot, SimulationTick};
+use rarephish_bevy::states::AppState;
+use rarephish_bevy::RarephishPlugin;
+
+fn set_state(app: &mut App, state: AppState) {
+    app.world_mut().resource_mut::<NextState<AppState>>().set(state);
+    app.update();
+    assert_eq!(*app.world().resource::<State<AppState>>().get(), state);
+}
+
+#[test]
+fn pausing_freezes_the_simulation() {
+    let mut app = headless_app();
+    app.finish();
+    app.cleanup();
+    assert_eq!(*app.world().resource::<State<AppState>>().get(), AppState::Playing, "headless apps skip the menu");
+    run_until_tick(&mut app, 20);
+
+    set_state(&mut app, AppState::Paused);
+    let tick = app.world().resource::<SimulationTick>().0;
+    let clock = app.world().resource::<DayNightCycle>().time_elapsed;
+
+    // Actions queued while paused wait for the game to resume
+    let (x, y) = {
+        let grid = app.world().resource::<TileGrid>();
+        grid.iter()
+            .find(|&(x, y, t)| t.can_place_uncle() && grid.distance_to_water(x, y, 1) == Some(1))
+            .map(|(x, y, _)| (x, y))
+            .unwrap()
+    };
+    app.world_mut().resource_mut::<PendingActions>().push(PlayerAction::PlaceUncle { x, y });
+    for _ in 0..100 {
+        app.update();
+    }
+    assert_eq!(app.world().resource::<SimulationTick>().0, tick);
+    assert_eq!(app.world().resource::<DayNightCycle>().time_elapsed, clock);
+    assert_eq!(app.world().resource::<GameState>().gold, GameState::default().gold);
+
+    set_state(&mut app, AppState::Playing);
+    run_until_tick(&mut app, tick + 1);
+    assert!(app.world().resource::<GameState>().gold < GameState::default().gold, "the uncle was placed");
+}
+
+#[test]
+fn ending_a_run_deletes_its_save() {
+    let dir = std::env::temp_dir().join(format!("rarephish-end-run-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let slot = SaveSlot {
+        path: dir.join("save.json"),
+        fishdex_path: dir.join("fishdex.json"),
+        resume: false,
+        ..default()
+    };
+    let save_path = slot.path.clone();
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(std::time::Duration::from_millis(50)))
+        .insert_resource(slot)
+        .add_plugins(RarephishPlugin { save: true, ..RarephishPlugin::headless() });
+    app.finish();
+    app.cleanup();
+    run_until_tick(&mut app, 5);
+    app.world_mut().send_event(AppExit::Success);
+    app.update();
+    assert!(save_path.exists(), "the run is saved on exit");
+
+    // Neither the autosave nor closing the game brings the ended run back
+    set_state(&mut app, AppState::RunSummary);
+    assert!(!save_path.exists());
+    assert!(dir.join("fishdex.json").exists(), "the Fishdex outlives the run");
+    app.world_mut().resource_mut::<SaveSlot>().autosave_timer.set_elapsed(std::time::Duration::from_secs(3600));
+    app.world_mut().send_event(AppExit::Success);
+    app.update();
+    assert!(!save_path.exists());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,185 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use rarephish_bevy::actions::{PendingActions, PlayerAction};
+use rarephish_bevy::components::{FishRarity, Tile, TileType, Uncle};
+use rarephish_bevy::constants::{CHUNK_SIZE, DOCK_COST, FOREST_CLEARING_COST};
+use rarephish_bevy::fishing::{FishingGrounds, FishingSpot};
+use rarephish_bevy::grid::TileGrid;
+use rarephish_bevy::replay::{headless_app, run_until_tick};
+use rarephish_bevy::resources::{GameState, SimulationTick, WorldSeed};
+use rarephish_bevy::species::{Activity, SpeciesCatalogue, SpeciesDef};
+use rarephish_bevy::systems::gameplay::advance_fishing;
+use rarephish_bevy::uncles::{UncleRoster, UncleType};
+use rarephish_bevy::worldgen::WorldGenConfig;
+
+#[test]
+fn default_maps_use_every_generated_tile_type() {
+    let config = WorldGenConfig::default();
+    let tiles: Vec<TileType> = (0..8).flat_map(|seed| config.generate(seed)).collect();
+
+    for tile_type in TileType::ALL {
+        // Docks are only ever built by the player
+        let expected = tile_type != TileType::Dock;
+        assert_eq!(tiles.contains(&tile_type), expected, "{:?}", tile_type);
+    }
+}
+
+/// The built-in species, all biting around the clock, so catches only depend on the water
+fn always_biting() -> SpeciesCatalogue {
+    let species = SpeciesCatalogue::default().iter().cloned().map(|def| SpeciesDef { activity: Activity::Always, ..def }).collect();
+    SpeciesCatalogue::new(species).unwrap()
+}
+
+/// Three tiles of `water` along the bottom edge, all of average density and depth
+fn grounds_of(water: TileType, catalogue: &SpeciesCatalogue) -> FishingGrounds {
+    let mut grid = TileGrid::filled(3, 3, TileType::Land);
+    for x in 0..3 {
+        grid.set(x, 0, water);
+    }
+    let mut grounds = FishingGrounds::new(1, &WorldGenConfig::default(), catalogue, &grid);
+    for tile in grounds.tiles.values_mut() {
+        tile.depth = 0.5;
+        tile.density = 1.0;
+    }
+    grounds
+}
+
+/// Fish caught over `seconds` by an uncle on `stand` at (1, 1), restocking the water
+/// and emptying the basket every second so neither runs out
+fn fish_from(water: TileType, stand: TileType, seconds: u32) -> Vec<FishRarity> {
+    let catalogue = always_biting();
+    let full = grounds_of(water, &catalogue);
+    let mut grounds = full.clone();
+    let spot = FishingSpot::at(1, 1, 1, Some(stand), &grounds).unwrap();
+    let mut world_seed = WorldSeed::from_seed(5);
+    let mongolian = UncleRoster::default().get(&UncleType::new("Mongolian")).unwrap().clone();
+    let mut uncle = Uncle::new(&mongolian, 1, 1);
+    let mut caught = Vec::new();
+    for _ in 0..seconds {
+        caught.extend(advance_fishing(&mut uncle, &mongolian, Duration::from_secs(1), true, &mut world_seed, &mut grounds, &catalogue, &spot).iter().map(|f| f.rarity));
+        uncle.basket.fish.clear();
+        grounds = full.clone();
+    }
+    caught
+}
+
+#[test]
+fn water_type_shapes_catches() {
+    let rare_share = |fish: &[FishRarity]| fish.iter().filter(|&&r| r == FishRarity::Rare).count() as f32 / fish.len() as f32;
+
+    let deep = fish_from(TileType::DeepWater, TileType::Land, 4000);
+    let open = fish_from(TileType::Water, TileType::Land, 4000);
+    let shallows = fish_from(TileType::Shallows, TileType::Land, 4000);
+
+    // Deep water bites slowest but gives the most rares; shallows the opposite
+    assert!(deep.len() < open.len() && open.len() < shallows.len());
+    assert!(rare_share(&deep) > rare_share(&open) && rare_share(&open) > rare_share(&shallows));
+
+    // Standing on a beach speeds up the same water
+    assert!(fish_from(TileType::Water, TileType::Beach, 4000).len() > open.len());
+}
+
+/// A headless run on a fixed layout: water below y = 2 (the home chunk's bottom two rows and
+/// the chunks under it), land above, with a rock at (1, 2) and a forest at (3, 2)
+fn terrain_app() -> App {
+    let mut app = headless_app();
+    app.finish();
+    app.cleanup();
+    run_until_tick(&mut app, 1);
+
+    let world = app.world_mut();
+    let mut grid = world.resource::<TileGrid>().clone();
+    for mut tile in world.query::<&mut Tile>().iter_mut(world) {
+        tile.tile_type = match (tile.x, tile.y) {
+            (_, 0) | (_, 1) => TileType::Water,
+            (1, 2) => TileType::Rock,
+            (3, 2) => TileType::Forest,
+            _ => TileType::Land,
+        };
+        grid.set(tile.x, tile.y, tile.tile_type);
+    }
+    for chunk in [(-1, -1), (0, -1), (1, -1)] {
+        grid.insert_chunk(chunk, vec![TileType::Water; CHUNK_SIZE * CHUNK_SIZE]);
+    }
+    let seed = world.resource::<WorldSeed>().seed;
+    let grounds = FishingGrounds::new(seed, world.resource::<WorldGenConfig>(), world.resource::<SpeciesCatalogue>(), &grid);
+    world.insert_resource(grounds);
+    world.insert_resource(grid);
+    world.resource_mut::<GameState>().gold = 1000;
+    app
+}
+
+fn apply(app: &mut App, action: PlayerAction) {
+    app.world_mut().resource_mut::<PendingActions>().push(action);
+    let tick = app.world().resource::<SimulationTick>().0;
+    run_until_tick(app, tick + 1);
+}
+
+/// The tile type in the grid, checking the tile entity agrees
+fn tile_at(app: &mut App, x: i32, y: i32) -> TileType {
+    let grid = app.world().resource::<TileGrid>();
+    let (tile_type, entity) = (grid.get(x, y).unwrap(), grid.entity(x, y).unwrap());
+    assert_eq!(app.world().get::<Tile>(entity).unwrap().tile_type, tile_type);
+    tile_type
+}
+
+fn uncle_count(app: &mut App) -> usize {
+    let world = app.world_mut();
+    world.query::<&Uncle>().iter(world).count()
+}
+
+fn gold(app: &App) -> u32 {
+    app.world().resource::<GameState>().gold
+}
+
+#[test]
+fn rocks_block_and_forests_cost_extra() {
+    let mut app = terrain_app();
+    apply(&mut app, PlayerAction::SelectUncleType(UncleType::new("Mongolian")));
+    let mongolian_cost = UncleRoster::default().get(&UncleType::new("Mongolian")).unwrap().cost;
+
+    apply(&mut app, PlayerAction::PlaceUncle { x: 1, y: 2 });
+    assert_eq!(uncle_count(&mut app), 0, "hired onto a rock");
+    assert_eq!(gold(&app), 1000);
+
+    apply(&mut app, PlayerAction::PlaceUncle { x: 3, y: 2 });
+    assert_eq!(uncle_count(&mut app), 1);
+    assert_eq!(gold(&app), 1000 - mongolian_cost - FOREST_CLEARING_COST);
+    assert_eq!(tile_at(&mut app, 3, 2), TileType::Land, "forest was not cleared");
+}
+
+#[test]
+fn docks_grow_from_the_shore() {
+    let mut app = terrain_app();
+
+    // (5, 0) only touches water
+    apply(&mut app, PlayerAction::BuildDock { x: 5, y: 0 });
+    assert_eq!(tile_at(&mut app, 5, 0), TileType::Water);
+
+    apply(&mut app, PlayerAction::BuildDock { x: 5, y: 1 });
+    apply(&mut app, PlayerAction::BuildDock { x: 5, y: 0 });
+    assert_eq!(tile_at(&mut app, 5, 1), TileType::Dock);
+    assert_eq!(tile_at(&mut app, 5, 0), TileType::Dock);
+    assert_eq!(gold(&app), 1000 - 2 * DOCK_COST);
+
+    // The end of the dock is surrounded by water an uncle on the shore cannot reach
+    apply(&mut app, PlayerAction::SelectUncleType(UncleType::new("Mongolian")));
+    apply(&mut app, PlayerAction::PlaceUncle { x: 5, y: 0 });
+    assert_eq!(uncle_count(&mut app), 1);
+}
+
+#[test]
+fn casting_radius_decides_how_far_inland_uncles_can_fish() {
+    let mut app = terrain_app();
+
+    // (4, 3) is two tiles from the nearest water
+    apply(&mut app, PlayerAction::SelectUncleType(UncleType::new("Mongolian")));
+    apply(&mut app, PlayerAction::PlaceUncle { x: 4, y: 3 });
+    assert_eq!(uncle_count(&mut app), 0);
+
+    apply(&mut app, PlayerAction::SelectUncleType(UncleType::new("Japanese")));
+    apply(&mut app, PlayerAction::PlaceUncle { x: 4, y: 3 });
+    assert_eq!(uncle_count(&mut app), 1);
+}
@@ -0,0 +1,92 @@
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use rarephish_bevy::components::{Fish, FishRarity};
+use rarephish_bevy::resources::SpeciesRecords;
+use rarephish_bevy::species::{FishSize, FishTraits, SpeciesCatalogue, SpeciesDef, SpeciesId};
+use rarephish_bevy::uncles::UncleType;
+
+fn species(id: &str) -> SpeciesDef {
+    SpeciesCatalogue::default().get(&SpeciesId::new(id)).unwrap().clone()
+}
+
+fn fish_of(def: &SpeciesDef, length_cm: f32) -> Fish {
+    let traits = def.roll_traits(length_cm, &mut ChaCha8Rng::seed_from_u64(0));
+    Fish::of_species(def, traits, length_cm, 10, UncleType::new("Mongolian"))
+}
+
+#[test]
+fn lengths_are_log_normal_around_the_species_median() {
+    let carp = species("carp");
+    let mut rng = ChaCha8Rng::seed_from_u64(1);
+    let mut lengths: Vec<f32> = (0..20_000).map(|_| carp.roll_length(&mut rng)).collect();
+    lengths.sort_by(f32::total_cmp);
+
+    let median = lengths[lengths.len() / 2];
+    assert!((median / carp.size.median_cm - 1.0).abs() < 0.02, "median {} cm", median);
+    let logs: Vec<f32> = lengths.iter().map(|l| (l / carp.size.median_cm).ln()).collect();
+    let spread = (logs.iter().map(|x| x * x).sum::<f32>() / logs.len() as f32).sqrt();
+    assert!((spread / carp.size.spread - 1.0).abs() < 0.05, "spread {}", spread);
+
+    // Most fish are medium; huge ones are the rarest
+    let count = |size: FishSize| {
+        lengths.iter().filter(|&&l| FishSize::from_length_ratio(l / carp.size.median_cm) == size).count()
+    };
+    let counts: Vec<usize> = FishSize::ALL.into_iter().map(count).collect();
+    assert!(counts.iter().all(|&n| n > 0), "{:?}", counts);
+    assert_eq!(counts.iter().max(), Some(&counts[2]));
+    assert!(counts[4] < counts[3] && counts[4] < counts[1], "{:?}", counts);
+}
+
+#[test]
+fn value_and_size_follow_weight() {
+    let koi = species("koi");
+    let (small, median, trophy) = (fish_of(&koi, 40.0), fish_of(&koi, 60.0), fish_of(&koi, 90.0));
+    assert_eq!(median.weight_kg, koi.size.median_kg);
+    assert!((trophy.weight_kg / median.weight_kg - 3.375).abs() < 1e-4);
+    assert_eq!((small.traits.size, median.traits.size, trophy.traits.size), (FishSize::Tiny, FishSize::Medium, FishSize::Huge));
+
+    let average_value = |weight_kg: f32| {
+        let mut rng = ChaCha8Rng::seed_from_u64(2);
+        (0..2000).map(|_| koi.roll_value(weight_kg, &mut rng)).sum::<u32>() as f32 / 2000.0
+    };
+    let (median_value, trophy_value) = (average_value(median.weight_kg), average_value(trophy.weight_kg));
+    let midpoint = (koi.value.min + koi.value.max) as f32 / 2.0;
+    assert!((median_value / midpoint - 1.0).abs() < 0.05, "{} vs {}", median_value, midpoint);
+    assert!((trophy_value / median_value - 3.375).abs() < 0.05, "{} vs {}", trophy_value, median_value);
+}
+
+#[test]
+fn bigger_fish_fight_harder() {
+    let chance = |size: FishSize| {
+        let mut fish = Fish::new("Test".to_string(), FishRarity::Common, 1, UncleType::new("Mongolian"));
+        fish.traits = FishTraits { size, ..Default::default() };
+        fish.calculate_escape_chance(1.0)
+    };
+    let chances: Vec<f32> = FishSize::ALL.into_iter().map(chance).collect();
+    assert!(chances.windows(2).all(|pair| pair[0] < pair[1]), "{:?}", chances);
+    // Unmeasured fish from old saves escape as they always did
+    assert_eq!(chance(FishSize::Medium), chance(FishSize::default()));
+}
+
+#[test]
+fn records_keep_the_heaviest_fish_of_each_species() {
+    let (koi, pike) = (species("koi"), species("pike"));
+    let mut records = SpeciesRecords::default();
+
+    // The first of a species sets the record without breaking one
+    assert_eq!(records.offer(&fish_of(&koi, 60.0), 1), None);
+    assert_eq!(records.offer(&fish_of(&pike, 30.0), 1), None);
+    assert_eq!(records.offer(&fish_of(&koi, 50.0), 2), None);
+    let broken = records.offer(&fish_of(&koi, 70.0), 3);
+    assert_eq!(broken, Some(koi.size.median_kg));
+
+    let record = &records.records[&koi.id];
+    assert_eq!((record.length_cm, record.day), (70.0, 3));
+    assert_eq!(records.records[&pike.id].length_cm, 30.0);
+
+    // Fish caught before fish were measured never hold a record
+    let old = Fish::new("Old fish".to_string(), FishRarity::Rare, 50, UncleType::new("Mongolian"));
+    assert_eq!(records.offer(&old, 4), None);
+    assert_eq!(records.records.len(), 2);
+}
@@ -0,0 +1,70 @@
+use rarephish_bevy::actions::{PendingActions, PlayerAction};
+use rarephish_bevy::components::Uncle;
+use rarephish_bevy::grid::TileGrid;
+use rarephish_bevy::replay::{headless_app, run_until_tick};
+use rarephish_bevy::resources::GameState;
+use rarephish_bevy::uncles::{RosterError, UncleRoster, UncleType, UNCLE_ROSTER_PATH};
+
+const NORWEGIAN: &str = r#"{
+    "uncles": [{
+        "id": "Norwegian", "name": "Norwegian Uncle", "letter": "N", "emoji": "❄️",
+        "color": [0.8, 0.9, 1.0], "cost": 75, "speed_ms": 1800, "basket_capacity": 7,
+        "casting_radius": 1, "retention": 0.95, "ability": "Cold Hands", "description": "Test uncle"
+    }]
+}"#;
+
+#[test]
+fn built_in_roster_matches_the_asset_file() {
+    let shipped = UncleRoster::read(UNCLE_ROSTER_PATH.as_ref()).expect("assets/uncles.json is valid");
+    let built_in = UncleRoster::default();
+    let ids = |roster: &UncleRoster| roster.iter().map(|def| def.id.clone()).collect::<Vec<_>>();
+    assert_eq!(ids(&shipped), ids(&built_in));
+    assert_eq!(ids(&built_in), ["Mongolian", "Somali", "Japanese"].map(UncleType::new));
+    assert_eq!(built_in.first().cost, 50);
+}
+
+#[test]
+fn invalid_rosters_are_rejected() {
+    assert!(matches!(UncleRoster::from_json(r#"{ "uncles": [] }"#), Err(RosterError::Invalid(_))));
+    assert!(matches!(UncleRoster::from_json("{ not json"), Err(RosterError::Json(_))));
+
+    let mut defs: Vec<_> = UncleRoster::default().iter().cloned().collect();
+    defs.push(defs[0].clone());
+    assert!(matches!(UncleRoster::new(defs), Err(RosterError::Invalid(_))), "duplicate id accepted");
+
+    let mut slacker = UncleRoster::default().first().clone();
+    slacker.basket_capacity = 0;
+    assert!(UncleRoster::new(vec![slacker]).is_err());
+}
+
+#[test]
+fn uncles_from_a_custom_roster_can_be_hired() {
+    let mut app = headless_app();
+    app.insert_resource(UncleRoster::from_json(NORWEGIAN).unwrap());
+    app.finish();
+    app.cleanup();
+    run_until_tick(&mut app, 1);
+
+    let (x, y) = {
+        let grid = app.world().resource::<TileGrid>();
+        grid.iter()
+            .find(|&(x, y, t)| t.can_place_uncle() && t.placement_cost() == 0 && grid.distance_to_water(x, y, 1) == Some(1))
+            .map(|(x, y, _)| (x, y))
+            .unwrap()
+    };
+    let mut actions = app.world_mut().resource_mut::<PendingActions>();
+    // Uncles the roster doesn't know are ignored
+    actions.push(PlayerAction::SelectUncleType(UncleType::new("Mongolian")));
+    actions.push(PlayerAction::PlaceUncle { x, y });
+    run_until_tick(&mut app, 2);
+    let mut actions = app.world_mut().resource_mut::<PendingActions>();
+    actions.push(PlayerAction::SelectUncleType(UncleType::new("Norwegian")));
+    actions.push(PlayerAction::PlaceUncle { x, y });
+    run_until_tick(&mut app, 3);
+
+    let world = app.world_mut();
+    let uncles: Vec<(UncleType, usize)> =
+        world.query::<&Uncle>().iter(world).map(|u| (u.uncle_type.clone(), u.basket.capacity)).collect();
+    assert_eq!(uncles, [(UncleType::new("Norwegian"), 7)]);
+    assert_eq!(app.world().resource::<GameState>().gold, GameState::default().gold - 75);
+}
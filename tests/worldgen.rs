@@ -0,0 +1,74 @@
+use rarephish_bevy::components::TileType;
+use rarephish_bevy::worldgen::{Falloff, WorldGenConfig};
+
+const SEEDS: [u64; 6] = [1, 2, 3, 42, 1_700_000_000, u64::MAX];
+
+fn water_fraction(tiles: &[TileType]) -> f32 {
+    tiles.iter().filter(|t| t.is_water()).count() as f32 / tiles.len() as f32
+}
+
+#[test]
+fn same_seed_gives_the_same_map() {
+    let config = WorldGenConfig::default();
+    for seed in SEEDS {
+        assert_eq!(config.generate(seed), config.generate(seed));
+    }
+    assert_ne!(config.generate(1), config.generate(2));
+}
+
+#[test]
+fn regions_line_up_with_the_full_map() {
+    let config = WorldGenConfig { width: 40, height: 30, ..Default::default() };
+    let full = config.generate(7);
+
+    // Two halves generated separately match the map generated in one go
+    for (x0, width) in [(0, 17), (17, 23)] {
+        let region = config.generate_region(7, x0 as i32, 0, width, config.height);
+        for y in 0..config.height {
+            for x in 0..width {
+                assert_eq!(region[y * width + x], full[y * config.width + x0 + x], "tile ({}, {})", x0 + x, y);
+            }
+        }
+    }
+}
+
+#[test]
+fn default_maps_have_both_land_and_water_with_a_coast() {
+    let config = WorldGenConfig::default();
+    for seed in SEEDS {
+        let tiles = config.generate(seed);
+        let water = water_fraction(&tiles);
+        assert!((0.1..0.7).contains(&water), "seed {} is {:.0}% water", seed, water * 100.0);
+
+        let at = |x: i32, y: i32| {
+            (x >= 0 && y >= 0 && (x as usize) < config.width && (y as usize) < config.height)
+                .then(|| tiles[y as usize * config.width + x as usize])
+        };
+        let coastal = (0..config.height as i32)
+            .flat_map(|y| (0..config.width as i32).map(move |x| (x, y)))
+            .filter(|&(x, y)| at(x, y).is_some_and(|t| !t.is_water()))
+            .filter(|&(x, y)| {
+                (-1..=1).any(|dx| (-1..=1).any(|dy| at(x + dx, y + dy).is_some_and(|t| t.is_water())))
+            })
+            .count();
+        assert!(coastal >= 4, "seed {} has only {} coastal tiles", seed, coastal);
+    }
+}
+
+#[test]
+fn falloff_shapes_the_map() {
+    let lake = WorldGenConfig { width: 48, height: 36, falloff: Falloff::Lake, falloff_strength: 0.6, ..Default::default() };
+    let island = WorldGenConfig { falloff: Falloff::Island, ..lake.clone() };
+    let coast = WorldGenConfig { falloff: Falloff::Coast, ..lake.clone() };
+
+    for seed in SEEDS {
+        // Rivers run wherever their own noise says, so only standing water counts here
+        let is_water = |config: &WorldGenConfig, x: usize, y: usize| {
+            let tile = config.generate(seed)[y * config.width + x];
+            tile.is_water() && tile != TileType::River
+        };
+        assert!(is_water(&lake, 24, 18), "seed {}: lake has no water in the middle", seed);
+        assert!(!is_water(&island, 24, 18) && is_water(&island, 0, 0), "seed {}: island is not an island", seed);
+        assert!(is_water(&coast, 0, 18), "seed {}: coast has no sea on the west edge", seed);
+    }
+}